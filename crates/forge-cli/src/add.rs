@@ -5,10 +5,10 @@
 // Localiza la sección [dependencies] o [test-dependencies] e inserta.
 // =============================================================================
 
-use std::path::PathBuf;
+use std::path::Path;
 use colored::Colorize;
 
-pub async fn cmd_add(project_dir: &PathBuf, dep: &str, is_test: bool) -> anyhow::Result<()> {
+pub async fn cmd_add(project_dir: &Path, dep: &str, is_test: bool) -> anyhow::Result<()> {
     let toml_path = project_dir.join("forge.toml");
     
     if !toml_path.exists() {
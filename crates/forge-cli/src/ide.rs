@@ -1,6 +1,7 @@
 use anyhow::Result;
 use colored::*;
 use cyrce_forge_core::config::ForgeConfig;
+use cyrce_forge_deps::maven::CLASSPATH_FILE;
 use std::fs;
 use std::path::Path;
 
@@ -29,13 +30,31 @@ fn generate_vscode(project_dir: &Path, config: &ForgeConfig) -> Result<()> {
         fs::create_dir_all(&vscode_dir)?;
     }
 
-    // settings.json
-    let settings = r#"{
-    "evenBetterToml.schema.associations": {
+    // settings.json (con procesadores, las fuentes generadas también son código del proyecto)
+    let generated = generated_sources(config);
+    let source_paths = if generated.is_empty() {
+        String::new()
+    } else {
+        let paths: String = ["src/main/java".to_string(), "src/test/java".to_string()]
+            .into_iter()
+            .chain(generated.into_iter().map(|(dir, _)| dir))
+            .map(|dir| format!("\n        \"{}\"", dir))
+            .collect::<Vec<_>>()
+            .join(",");
+        format!(
+            r#",
+    "java.project.sourcePaths": [{paths}
+    ]"#
+        )
+    };
+    let settings = format!(
+        r#"{{
+    "evenBetterToml.schema.associations": {{
         "forge.toml": "https://raw.githubusercontent.com/enri312/forge/main/schemas/forge.schema.json",
         "tests/**/forge.toml": "https://raw.githubusercontent.com/enri312/forge/main/schemas/forge.schema.json"
-    }
-}"#;
+    }}{source_paths}
+}}"#
+    );
     fs::write(vscode_dir.join("settings.json"), settings)?;
 
     // tasks.json
@@ -134,8 +153,19 @@ fn generate_intellij(project_dir: &Path, config: &ForgeConfig) -> Result<()> {
 </project>"#);
     fs::write(idea_dir.join("modules.xml"), modules_xml)?;
 
+    // Fuentes de los procesadores de anotaciones, kapt y KSP, marcadas como generadas
+    let generated_folders: String = generated_sources(config)
+        .into_iter()
+        .map(|(dir, test)| {
+            format!(
+                r#"
+      <sourceFolder url="file://$MODULE_DIR$/../{dir}" isTestSource="{test}" generated="true" />"#
+            )
+        })
+        .collect();
+
     let iml_content = match config.project.lang.as_str() {
-        "java" | "kotlin" => r#"<?xml version="1.0" encoding="UTF-8"?>
+        "java" | "kotlin" => format!(r#"<?xml version="1.0" encoding="UTF-8"?>
 <module type="JAVA_MODULE" version="4">
   <component name="NewModuleRootManager" inherit-compiler-output="true">
     <exclude-output />
@@ -144,13 +174,15 @@ fn generate_intellij(project_dir: &Path, config: &ForgeConfig) -> Result<()> {
       <sourceFolder url="file://$MODULE_DIR$/../src/main/kotlin" isTestSource="false" />
       <sourceFolder url="file://$MODULE_DIR$/../src/test/java" isTestSource="true" />
       <sourceFolder url="file://$MODULE_DIR$/../src/test/kotlin" isTestSource="true" />
+      <sourceFolder url="file://$MODULE_DIR$/../src/main/resources" type="java-resource" />
+      <sourceFolder url="file://$MODULE_DIR$/../src/test/resources" type="java-test-resource" />{generated_folders}
       <excludeFolder url="file://$MODULE_DIR$/../.forge" />
       <excludeFolder url="file://$MODULE_DIR$/../build" />
     </content>
     <orderEntry type="inheritedJdk" />
     <orderEntry type="sourceFolder" forTests="false" />
   </component>
-</module>"#,
+</module>"#),
         "python" => r#"<?xml version="1.0" encoding="UTF-8"?>
 <module type="PYTHON_MODULE" version="4">
   <component name="NewModuleRootManager" inherit-compiler-output="true">
@@ -163,12 +195,181 @@ fn generate_intellij(project_dir: &Path, config: &ForgeConfig) -> Result<()> {
     <orderEntry type="inheritedJdk" />
     <orderEntry type="sourceFolder" forTests="false" />
   </component>
-</module>"#,
-        _ => r#"<?xml version="1.0" encoding="UTF-8"?><module type="WEB_MODULE" version="4"></module>"#,
+</module>"#.to_string(),
+        _ => r#"<?xml version="1.0" encoding="UTF-8"?><module type="WEB_MODULE" version="4"></module>"#.to_string(),
     };
 
     fs::write(idea_dir.join(format!("{}.iml", project_name)), iml_content)?;
 
+    if has_processors(config) {
+        fs::write(idea_dir.join("compiler.xml"), annotation_processing_xml(project_dir, config))?;
+    }
+
     println!("{}","✅ Archivos de configuración para IntelliJ generados en .idea/".green());
     Ok(())
 }
+
+/// ¿Hay procesadores de anotaciones que el IDE deba conocer?
+fn has_processors(config: &ForgeConfig) -> bool {
+    matches!(config.project.lang.as_str(), "java" | "kotlin") && !config.annotation_processors.is_empty()
+}
+
+/// Carpetas de fuentes generadas, relativas al proyecto, y si son de tests:
+/// las de javac con `[annotation-processors]`, y en Kotlin las de kapt y KSP
+/// (`build/generated/{kapt,ksp}/main`).
+fn generated_sources(config: &ForgeConfig) -> Vec<(String, bool)> {
+    let generated = format!("{}/generated", config.project.output_dir);
+    let mut dirs = Vec::new();
+    if has_processors(config) {
+        dirs.push((format!("{}/sources", generated), false));
+        dirs.push((format!("{}/test-sources", generated), true));
+    }
+    if config.project.lang == "kotlin" && !config.annotation_processors.is_empty() {
+        dirs.push((format!("{}/kapt/main", generated), false));
+    }
+    if config.ksp().is_some() {
+        dirs.push((format!("{}/ksp/main/kotlin", generated), false));
+        dirs.push((format!("{}/ksp/main/java", generated), false));
+    }
+    dirs
+}
+
+/// .idea/compiler.xml: procesamiento de anotaciones habilitado con los JARs que
+/// resolvió `forge deps` y las mismas carpetas de salida que usa `forge build`.
+fn annotation_processing_xml(project_dir: &Path, config: &ForgeConfig) -> String {
+    let classpath = project_dir.join(".forge").join("processors").join(CLASSPATH_FILE);
+    let jars: Vec<String> = fs::read_to_string(&classpath)
+        .map(|content| content.lines().map(str::trim).filter(|l| !l.is_empty()).map(String::from).collect())
+        .unwrap_or_default();
+    if jars.is_empty() {
+        println!(
+            "{}",
+            "⚠️  Procesadores de anotaciones sin resolver: ejecuta 'forge deps' y vuelve a generar la configuración.".yellow()
+        );
+    }
+
+    let entries: String = jars
+        .iter()
+        .map(|jar| format!("\n          <entry name=\"{}\" />", jar))
+        .collect();
+    let output_dir = &config.project.output_dir;
+    let project_name = &config.project.name;
+
+    format!(r#"<?xml version="1.0" encoding="UTF-8"?>
+<project version="4">
+  <component name="CompilerConfiguration">
+    <annotationProcessing>
+      <profile name="FORGE" enabled="true">
+        <sourceOutputDir name="{output_dir}/generated/sources" />
+        <sourceTestOutputDir name="{output_dir}/generated/test-sources" />
+        <outputRelativeToContentRoot value="true" />
+        <processorPath useClasspath="false">{entries}
+        </processorPath>
+        <module name="{project_name}" />
+      </profile>
+    </annotationProcessing>
+  </component>
+</project>"#)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Genera la configuración de ambos editores para un forge.toml y devuelve
+    /// `java.project.sourcePaths` de VS Code junto al .iml de IntelliJ.
+    async fn generate(name: &str, forge_toml: &str) -> (Vec<String>, String) {
+        let dir = std::env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("forge.toml"), forge_toml).unwrap();
+
+        cmd_ide(&dir, "vscode").await.unwrap();
+        cmd_ide(&dir, "intellij").await.unwrap();
+
+        let settings: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(dir.join(".vscode/settings.json")).unwrap()).unwrap();
+        let source_paths = settings["java.project.sourcePaths"]
+            .as_array()
+            .map(|paths| paths.iter().map(|p| p.as_str().unwrap().to_string()).collect())
+            .unwrap_or_default();
+        let iml = fs::read_to_string(dir.join(".idea/app.iml")).unwrap();
+
+        fs::remove_dir_all(&dir).unwrap();
+        (source_paths, iml)
+    }
+
+    #[tokio::test]
+    async fn test_java_project_marks_processor_output() {
+        let (source_paths, iml) = generate(
+            "forge_test_ide_java",
+            r#"
+[project]
+name = "app"
+lang = "java"
+
+[annotation-processors]
+"org.projectlombok:lombok" = "1.18.34"
+"#,
+        )
+        .await;
+
+        assert_eq!(
+            source_paths,
+            ["src/main/java", "src/test/java", "build/generated/sources", "build/generated/test-sources"]
+        );
+        assert!(iml.contains(r#"<sourceFolder url="file://$MODULE_DIR$/../build/generated/sources" isTestSource="false" generated="true" />"#));
+        assert!(iml.contains(r#"<sourceFolder url="file://$MODULE_DIR$/../build/generated/test-sources" isTestSource="true" generated="true" />"#));
+        assert!(!iml.contains("kapt"));
+    }
+
+    #[tokio::test]
+    async fn test_kotlin_project_marks_kapt_and_ksp_output() {
+        let (source_paths, iml) = generate(
+            "forge_test_ide_kotlin",
+            r#"
+[project]
+name = "app"
+lang = "kotlin"
+
+[annotation-processors]
+"com.google.dagger:dagger-compiler" = "2.52"
+
+[kotlin]
+version = "2.1.0"
+
+[kotlin.ksp]
+version = "2.1.0-1.0.29"
+processors = { "com.squareup.moshi:moshi-kotlin-codegen" = "1.15.1" }
+"#,
+        )
+        .await;
+
+        assert_eq!(
+            source_paths,
+            [
+                "src/main/java",
+                "src/test/java",
+                "build/generated/sources",
+                "build/generated/test-sources",
+                "build/generated/kapt/main",
+                "build/generated/ksp/main/kotlin",
+                "build/generated/ksp/main/java"
+            ]
+        );
+        for dir in ["kapt/main", "ksp/main/kotlin", "ksp/main/java"] {
+            assert!(iml.contains(&format!(
+                r#"<sourceFolder url="file://$MODULE_DIR$/../build/generated/{}" isTestSource="false" generated="true" />"#,
+                dir
+            )));
+        }
+        assert!(iml.contains(r#"<sourceFolder url="file://$MODULE_DIR$/../src/main/kotlin" isTestSource="false" />"#));
+    }
+
+    #[tokio::test]
+    async fn test_plain_project_has_no_generated_sources() {
+        let (source_paths, iml) = generate("forge_test_ide_plain", "[project]\nname = \"app\"\nlang = \"java\"\n").await;
+        assert!(source_paths.is_empty());
+        assert!(!iml.contains("generated"));
+    }
+}
@@ -12,9 +12,10 @@ mod lint;
 mod add;
 mod upgrade;
 mod tree;
+mod why;
 mod dashboard;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 use anyhow::Context;
@@ -23,7 +24,9 @@ use colored::Colorize;
 
 use cyrce_forge_core::cache::BuildCache;
 use cyrce_forge_core::config::ForgeConfig;
+use cyrce_forge_core::settings;
 
+use cyrce_forge_deps::lockfile::{self, Lockfile};
 use cyrce_forge_deps::maven::MavenResolver;
 use cyrce_forge_deps::pypi::PypiResolver;
 
@@ -53,6 +56,18 @@ struct Cli {
     /// Modo verboso (muestra más detalles)
     #[arg(short, long, global = true)]
     verbose: bool,
+
+    /// Fallar si la resolución de dependencias cambiaría forge.lock (también FORGE_LOCKED=1)
+    #[arg(long, global = true)]
+    locked: bool,
+
+    /// Trabajar sin red: usar solo las cachés locales (también FORGE_OFFLINE=1)
+    #[arg(long, global = true)]
+    offline: bool,
+
+    /// Compilar sin el daemon de compilación (también FORGE_NO_DAEMON=1)
+    #[arg(long, global = true)]
+    no_daemon: bool,
 }
 
 #[derive(Subcommand)]
@@ -95,7 +110,15 @@ enum Commands {
     Clean,
 
     /// 📦 Descargar y resolver dependencias
-    Deps,
+    Deps {
+        /// Re-verificar los checksums de los artefactos ya descargados
+        #[arg(long)]
+        verify: bool,
+
+        /// Re-resolver solo estas dependencias ignorando forge.lock (groupId:artifactId, artifactId o paquete PyPI)
+        #[arg(long, value_name = "NOMBRE")]
+        update: Vec<String>,
+    },
 
     /// ➕ Añadir una dependencia a forge.toml
     Add {
@@ -106,11 +129,37 @@ enum Commands {
         test: bool,
     },
 
-    /// ⬆️  Actualizar dependencias a versiones más recientes (beta/PyPI only por ahora)
-    Upgrade,
+    /// ⬆️  Actualizar dependencias a versiones más recientes de Maven / PyPI
+    Upgrade {
+        /// Dependencias a actualizar (groupId:artifactId, artifactId o paquete PyPI). Por defecto, todas
+        #[arg(value_name = "NOMBRE")]
+        deps: Vec<String>,
+
+        /// Permitir saltos de versión mayor
+        #[arg(long)]
+        major: bool,
+
+        /// Mostrar las actualizaciones disponibles sin modificar forge.toml
+        #[arg(long)]
+        dry_run: bool,
+    },
 
     /// 🌲 Visualizar el árbol de dependencias resueltas
-    Tree,
+    Tree {
+        /// Mostrar qué dependencias traen este artefacto (groupId:artifactId o artifactId)
+        #[arg(short, long, value_name = "ARTEFACTO")]
+        invert: Option<String>,
+
+        /// Imprimir el grafo en JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// 🔎 Explicar por qué un artefacto está en el classpath
+    Why {
+        /// Artefacto a explicar (groupId:artifactId o artifactId)
+        artifact: String,
+    },
 
     /// ℹ️  Mostrar información del proyecto
     Info,
@@ -131,6 +180,13 @@ enum Commands {
     /// 🩺 Diagnosticar el sistema (verificar herramientas instaladas)
     Doctor,
 
+    /// 🔌 Mostrar o detener los daemons de compilación
+    Daemon {
+        /// Detener todos los daemons en ejecución
+        #[arg(long)]
+        stop: bool,
+    },
+
     /// 📊 Mostrar estadísticas del proyecto (archivos, líneas, tamaño)
     Stats,
 
@@ -138,7 +194,17 @@ enum Commands {
     Bench,
 
     /// 📦 Empaquetar proyecto para distribución
-    Package,
+    Package {
+        /// Java/Kotlin: imagen autocontenida con un runtime mínimo (jlink)
+        #[arg(long)]
+        image: bool,
+        /// Java/Kotlin: instalador nativo con jpackage (ver [package.installer])
+        #[arg(long)]
+        installer: bool,
+        /// Java/Kotlin: distribución .zip y .tar.gz con lanzadores en bin/ y JARs en lib/
+        #[arg(long)]
+        dist: bool,
+    },
 
     /// 🐚 Generar autocompletado para tu shell
     Completions {
@@ -167,10 +233,10 @@ enum Commands {
     },
 }
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
-    // Inicializar logging
+fn main() -> anyhow::Result<()> {
+    // Inicializar logging (a stderr, para no mezclarse con salidas como `tree --json`)
     tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
         .with_env_filter(
             tracing_subscriber::EnvFilter::from_default_env()
                 .add_directive(tracing::Level::WARN.into()),
@@ -180,6 +246,24 @@ async fn main() -> anyhow::Result<()> {
 
     let cli = Cli::parse();
 
+    // --locked, --offline y --no-daemon se propagan por entorno para que apliquen a build/run/test
+    if cli.locked {
+        std::env::set_var(lockfile::LOCKED_ENV, "1");
+    }
+    if cli.offline {
+        std::env::set_var(settings::OFFLINE_ENV, "1");
+    }
+    if cli.no_daemon {
+        std::env::set_var(settings::NO_DAEMON_ENV, "1");
+    }
+
+    // El runtime se crea después de set_var: modificar el entorno con otros hilos
+    // ya corriendo (los workers de tokio) no es seguro
+    tokio::runtime::Runtime::new()?.block_on(run(cli))
+}
+
+async fn run(cli: Cli) -> anyhow::Result<()> {
+
     // Determinar directorio del proyecto (convertir a ruta absoluta)
     let project_dir = cli
         .project_dir
@@ -196,8 +280,11 @@ async fn main() -> anyhow::Result<()> {
             }
         });
 
-    // Banner
-    print_banner();
+    // Banner (no en salidas para máquinas como JSON)
+    let machine_output = matches!(cli.command, Commands::Tree { json: true, .. });
+    if !machine_output {
+        print_banner();
+    }
 
     // Ejecutar comando
     let start = Instant::now();
@@ -206,14 +293,14 @@ async fn main() -> anyhow::Result<()> {
         Commands::New { name, lang } => cmd_new(&project_dir, &name, &lang).await,
         Commands::Build { release, dashboard } => {
             if dashboard {
-                let p = project_dir.clone();
+                let p = project_dir.to_path_buf();
                 tokio::spawn(async move {
                     let _ = dashboard::cmd_dashboard(&p, 3000).await;
                 });
                 // Darle tiempo al servidor Axum para iniciar
                 tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
             }
-            let res = cmd_build(project_dir.clone(), cli.verbose, release).await;
+            let res = cmd_build(project_dir.to_path_buf(), cli.verbose, release).await;
             if dashboard {
                 println!("\n{} {}", "🚀".cyan(), "Dashboard corriendo en segundo plano.".bold());
                 println!("{}", "Presiona Ctrl+C para finalizar, o visita http://localhost:3000".dimmed());
@@ -225,17 +312,21 @@ async fn main() -> anyhow::Result<()> {
         Commands::Run => cmd_run(&project_dir, cli.verbose).await,
         Commands::Test => cmd_test(&project_dir, cli.verbose).await,
         Commands::Clean => cmd_clean(&project_dir).await,
-        Commands::Deps => cmd_deps(&project_dir).await,
+        Commands::Deps { verify, update } => cmd_deps(&project_dir, verify, &update).await,
         Commands::Add { dep, test } => add::cmd_add(&project_dir, &dep, test).await,
-        Commands::Upgrade => upgrade::cmd_upgrade(&project_dir).await,
-        Commands::Tree => tree::cmd_tree(&project_dir).await,
+        Commands::Upgrade { deps, major, dry_run } => {
+            upgrade::cmd_upgrade(&project_dir, &deps, major, dry_run).await
+        }
+        Commands::Tree { invert, json } => tree::cmd_tree(&project_dir, invert.as_deref(), json).await,
+        Commands::Why { artifact } => why::cmd_why(&project_dir, &artifact).await,
         Commands::Info => cmd_info(&project_dir).await,
         Commands::Watch { dashboard } => cmd_watch(&project_dir, dashboard).await,
         Commands::Task { name } => cmd_task(&project_dir, &name).await,
         Commands::Doctor => cmd_doctor().await,
+        Commands::Daemon { stop } => cmd_daemon(stop).await,
         Commands::Stats => cmd_stats(&project_dir).await,
         Commands::Bench => cmd_bench(&project_dir, cli.verbose).await,
-        Commands::Package => cmd_package(&project_dir).await,
+        Commands::Package { image, installer, dist } => cmd_package(&project_dir, image, installer, dist).await,
         Commands::Ide { target } => ide::cmd_ide(&project_dir, &target).await,
         Commands::Fmt => fmt::cmd_fmt(&project_dir).await,
         Commands::Lint => lint::cmd_lint(&project_dir).await,
@@ -264,7 +355,7 @@ async fn main() -> anyhow::Result<()> {
     }
 
     let elapsed = start.elapsed();
-    if elapsed.as_millis() > 100 {
+    if elapsed.as_millis() > 100 && !machine_output {
         println!(
             "{}",
             format!("⏱️  Completado en {:.2}s", elapsed.as_secs_f64()).dimmed()
@@ -292,7 +383,7 @@ fn print_banner() {
 }
 
 /// Comando: forge init <lang>
-async fn cmd_init(project_dir: &PathBuf, lang: &str) -> anyhow::Result<()> {
+async fn cmd_init(project_dir: &Path, lang: &str) -> anyhow::Result<()> {
     println!(
         "{}",
         format!("🆕 Inicializando proyecto {} en {:?}...", lang, project_dir).bold()
@@ -377,7 +468,7 @@ async fn cmd_init(project_dir: &PathBuf, lang: &str) -> anyhow::Result<()> {
 }
 
 /// Crea un archivo de ejemplo según el lenguaje.
-fn create_example_file(lang: &str, source_dir: &PathBuf) -> anyhow::Result<()> {
+fn create_example_file(lang: &str, source_dir: &Path) -> anyhow::Result<()> {
     match lang {
         "java" => {
             let file = source_dir.join("Main.java");
@@ -437,7 +528,7 @@ if __name__ == "__main__":
 }
 
 /// Crea un archivo de test de ejemplo según el lenguaje.
-fn create_test_file(lang: &str, test_dir: &PathBuf) -> anyhow::Result<()> {
+fn create_test_file(lang: &str, test_dir: &Path) -> anyhow::Result<()> {
     match lang {
         "java" => {
             let file = test_dir.join("MainTest.java");
@@ -612,7 +703,29 @@ async fn cmd_build(project_dir: PathBuf, _verbose: bool, release: bool) -> anyho
     let extensions = cyrce_forge_langs::extensions_for_lang(&config.project.lang);
     let mut cache = BuildCache::load(&project_dir)?;
 
-    if !cache.has_changes(&source_dir, extensions)? {
+    // Recursos JVM: cambiarlos (o cambiar la versión si se filtran) también recompila
+    let resources_dir = config.resources_dir(false).map(|dir| project_dir.join(dir));
+    let resource_filter = config
+        .filter_resources()
+        .then(|| cyrce_forge_langs::resources::filter_fingerprint(&config));
+    let resources_changed = match &resources_dir {
+        Some(dir) => cache.has_resource_changes(dir, resource_filter.as_deref())?,
+        None => false,
+    };
+
+    // Cambiar compiler-args, encoding, procesadores, Kotlin, plugins o KSP también invalida la caché
+    let compiler_options = config.build_fingerprint();
+    // Proyecto mixto Kotlin + Java: los fuentes de [java] también cuentan
+    let java_dir = config
+        .has_java_sources()
+        .then(|| project_dir.join(config.java_source_dir(false)));
+    let mixed_changed = match &java_dir {
+        Some(dir) => cache.has_mixed_source_changes(dir)?,
+        None => false,
+    };
+    let options_changed = cache.compiler_options != compiler_options;
+
+    if !cache.has_changes(&source_dir, extensions)? && !resources_changed && !options_changed && !mixed_changed {
         println!(
             "{}",
             "⚡ Sin cambios detectados — usando caché local".dimmed()
@@ -627,6 +740,13 @@ async fn cmd_build(project_dir: PathBuf, _verbose: bool, release: bool) -> anyho
     if let Some(remote_cfg) = &config.cache {
         // Intenta descargar el output compilado remotamente para este master_hash
         cache.update_hashes(&source_dir, extensions)?;
+        if let Some(dir) = &resources_dir {
+            cache.update_resource_hashes(dir, resource_filter.as_deref())?;
+        }
+        if let Some(dir) = &java_dir {
+            cache.update_mixed_source_hashes(dir)?;
+        }
+        cache.compiler_options = compiler_options.clone();
         if cache.download_from_remote(&project_dir, output_dir_name, remote_cfg).await? {
             used_remote = true;
             cache.save(&project_dir)?;
@@ -639,9 +759,9 @@ async fn cmd_build(project_dir: PathBuf, _verbose: bool, release: bool) -> anyho
     // 🪝 Hooks pre-build
     hooks::run_pre_build(&config.hooks, &project_dir).await?;
 
-    // Resolver dependencias si hay
-    if !config.dependencies.is_empty() {
-        resolve_dependencies(&config, &project_dir).await?;
+    // Resolver dependencias si hay (o el kotlin-stdlib de [kotlin] version)
+    if !config.dependencies.is_empty() || !config.annotation_processors.is_empty() || config.kotlin_version().is_some() || !config.kotlin_compiler_plugins().is_empty() {
+        resolve_dependencies(&config, &project_dir, &[]).await?;
     }
 
         // Compilar según el lenguaje
@@ -654,6 +774,13 @@ async fn cmd_build(project_dir: PathBuf, _verbose: bool, release: bool) -> anyho
 
         // Actualizar caché
         cache.update_hashes(&source_dir, extensions)?;
+        if let Some(dir) = &resources_dir {
+            cache.update_resource_hashes(dir, resource_filter.as_deref())?;
+        }
+        if let Some(dir) = &java_dir {
+            cache.update_mixed_source_hashes(dir)?;
+        }
+        cache.compiler_options = compiler_options.clone();
         cache.save(&project_dir)?;
 
         // Si la compilación fue local y tenemos push habilitado, subir artefactos
@@ -669,9 +796,9 @@ async fn cmd_build(project_dir: PathBuf, _verbose: bool, release: bool) -> anyho
 }
 
 /// Comando: forge run
-async fn cmd_run(project_dir: &PathBuf, verbose: bool) -> anyhow::Result<()> {
+async fn cmd_run(project_dir: &Path, verbose: bool) -> anyhow::Result<()> {
     // Primero compilar (en modo por defecto / no-release para run)
-    cmd_build(project_dir.clone(), verbose, false).await?;
+    cmd_build(project_dir.to_path_buf(), verbose, false).await?;
 
     let config = ForgeConfig::load(project_dir)?;
 
@@ -687,7 +814,7 @@ async fn cmd_run(project_dir: &PathBuf, verbose: bool) -> anyhow::Result<()> {
 }
 
 /// Comando: forge test
-async fn cmd_test(project_dir: &PathBuf, verbose: bool) -> anyhow::Result<()> {
+async fn cmd_test(project_dir: &Path, verbose: bool) -> anyhow::Result<()> {
     let config = ForgeConfig::load(project_dir)?;
 
     println!("{}", "🧪 Ejecutando tests...".bold());
@@ -697,11 +824,11 @@ async fn cmd_test(project_dir: &PathBuf, verbose: bool) -> anyhow::Result<()> {
 
     match config.project.lang.as_str() {
         "java" => {
-            cmd_build(project_dir.clone(), verbose, false).await?;
+            cmd_build(project_dir.to_path_buf(), verbose, false).await?;
             JavaModule::test(&config, project_dir).await?;
         }
         "kotlin" => {
-            cmd_build(project_dir.clone(), verbose, false).await?;
+            cmd_build(project_dir.to_path_buf(), verbose, false).await?;
             KotlinModule::test(&config, project_dir).await?;
         }
         "python" => PythonModule::test(&config, project_dir).await?,
@@ -715,7 +842,7 @@ async fn cmd_test(project_dir: &PathBuf, verbose: bool) -> anyhow::Result<()> {
 }
 
 /// Comando: forge clean
-async fn cmd_clean(project_dir: &PathBuf) -> anyhow::Result<()> {
+async fn cmd_clean(project_dir: &Path) -> anyhow::Result<()> {
     println!("{}", "🧹 Limpiando artefactos...".bold());
 
     let build_dir = project_dir.join("build");
@@ -732,48 +859,118 @@ async fn cmd_clean(project_dir: &PathBuf) -> anyhow::Result<()> {
 }
 
 /// Comando: forge deps
-async fn cmd_deps(project_dir: &PathBuf) -> anyhow::Result<()> {
+async fn cmd_deps(project_dir: &Path, verify: bool, update: &[String]) -> anyhow::Result<()> {
     let config = ForgeConfig::load(project_dir)?;
 
-    if config.dependencies.is_empty() {
+    if config.dependencies.is_empty() && config.annotation_processors.is_empty() && config.kotlin_version().is_none() && config.kotlin_compiler_plugins().is_empty() {
         println!("{}", "📦 No hay dependencias definidas en forge.toml".dimmed());
         return Ok(());
     }
 
-    resolve_dependencies(&config, project_dir).await
+    if verify && matches!(config.project.lang.as_str(), "java" | "kotlin") {
+        println!("{}", "🔐 Verificando checksums del almacén...".cyan());
+        let resolver = MavenResolver::new(&config, project_dir)?;
+        let (valid, missing, corrupted) = resolver.verify_cached()?;
+        println!(
+            "   {} válidos, {} sin checksum, {} corruptos",
+            valid.to_string().green(),
+            missing.to_string().yellow(),
+            corrupted.to_string().red()
+        );
+        if corrupted > 0 {
+            println!("{}", "   ⬇️  Re-descargando artefactos corruptos...".dimmed());
+        }
+    }
+
+    resolve_dependencies(&config, project_dir, update).await
 }
 
-/// Resuelve dependencias según el lenguaje, excluyendo las locales (path:).
-async fn resolve_dependencies(config: &ForgeConfig, project_dir: &PathBuf) -> anyhow::Result<()> {
+/// Resuelve dependencias según el lenguaje, excluyendo las locales (path:),
+/// y registra el resultado en forge.lock.
+async fn resolve_dependencies(
+    config: &ForgeConfig,
+    project_dir: &Path,
+    update: &[String],
+) -> anyhow::Result<()> {
+    let previous = Lockfile::load(project_dir)?;
+    let mut lock = Lockfile::default();
+
+    for name in update {
+        if !previous.as_ref().is_some_and(|l| l.contains(name)) {
+            println!(
+                "   {}",
+                format!("⚠️  '{}' no aparece en {}", name, lockfile::LOCKFILE).yellow()
+            );
+        }
+    }
+
     match config.project.lang.as_str() {
         "java" | "kotlin" => {
-            let mut resolver = cyrce_forge_deps::maven::MavenResolver::new(project_dir);
+            let mut resolver =
+                MavenResolver::new(config, project_dir)?.with_lock(previous.as_ref(), update);
             
-            let remote_deps: std::collections::HashMap<String, String> = config.dependencies.clone().into_iter().filter(|(_, v)| !v.starts_with("path:")).collect();
+            // [kotlin] version agrega kotlin-stdlib y kotlin-test implícitos
+            let remote_deps: std::collections::HashMap<String, String> = config.maven_dependencies(false).into_iter().filter(|(_, v)| !v.starts_with("path:")).collect();
             if !remote_deps.is_empty() {
                 resolver.resolve_all(&remote_deps).await?;
             }
             
-            let remote_test_deps: std::collections::HashMap<String, String> = config.test_dependencies.clone().into_iter().filter(|(_, v)| !v.starts_with("path:")).collect();
+            let remote_test_deps: std::collections::HashMap<String, String> = config.maven_dependencies(true).into_iter().filter(|(_, v)| !v.starts_with("path:")).collect();
             if !remote_test_deps.is_empty() {
                 resolver.resolve_test_deps(&remote_test_deps).await?;
             }
+
+            let remote_processors: std::collections::HashMap<String, String> = config.annotation_processors.clone().into_iter().filter(|(_, v)| !v.starts_with("path:")).collect();
+            if !remote_processors.is_empty() {
+                resolver.resolve_processors(&remote_processors).await?;
+            }
+
+            // [kotlin] embedded-compiler: kotlinc como JAR de Maven
+            let embedded = config.kotlin.as_ref().is_some_and(|k| k.embedded_compiler);
+            if let Some(version) = config.kotlin_version().filter(|_| embedded) {
+                resolver.resolve_kotlin_compiler(version).await?;
+            }
+
+            // Plugins del compilador de Kotlin, kapt y KSP
+            let plugins = config.kotlin_compiler_plugins();
+            if !plugins.is_empty() {
+                resolver.resolve_kotlin_plugins(&plugins).await?;
+            }
+            if let Some(version) = config.kotlin_version().filter(|_| config.project.lang == "kotlin" && !remote_processors.is_empty()) {
+                resolver.resolve_kapt(version).await?;
+            }
+            if let Some(ksp) = config.ksp() {
+                let processors: std::collections::HashMap<String, String> = ksp.processors.clone().into_iter().filter(|(_, v)| !v.starts_with("path:")).collect();
+                resolver.resolve_ksp(&ksp.version, &processors).await?;
+            }
+
+            lock.maven = resolver.lock_entries().to_vec();
         }
         "python" => {
-            let resolver = cyrce_forge_deps::pypi::PypiResolver::new();
+            let resolver = PypiResolver::new();
             let remote_deps: std::collections::HashMap<String, String> = config.dependencies.clone().into_iter().filter(|(_, v)| !v.starts_with("path:")).collect();
             if !remote_deps.is_empty() {
-                resolver.verify_all(&remote_deps).await?;
+                lock.pypi = resolver
+                    .resolve_all(&remote_deps, previous.as_ref(), update)
+                    .await?;
             }
         }
-        _ => {}
+        _ => return Ok(()),
+    }
+
+    let changes = lock.commit(previous.as_ref(), project_dir, lockfile::locked_mode())?;
+    if changes > 0 {
+        println!(
+            "   {}",
+            format!("🔒 {} actualizado: {} cambio(s)", lockfile::LOCKFILE, changes).dimmed()
+        );
     }
 
     Ok(())
 }
 
 /// Comando: forge info
-async fn cmd_info(project_dir: &PathBuf) -> anyhow::Result<()> {
+async fn cmd_info(project_dir: &Path) -> anyhow::Result<()> {
     let config = ForgeConfig::load(project_dir)
         .context("No se encontró forge.toml. ¿Estás en un proyecto FORGE?")?;
 
@@ -851,7 +1048,7 @@ fn print_tool_version(name: &str, cmd: &str, args: &[&str]) {
 }
 
 /// Comando: forge new <nombre>
-async fn cmd_new(parent_dir: &PathBuf, name: &str, lang: &str) -> anyhow::Result<()> {
+async fn cmd_new(parent_dir: &Path, name: &str, lang: &str) -> anyhow::Result<()> {
     let project_dir = parent_dir.join(name);
 
     if project_dir.exists() {
@@ -880,7 +1077,7 @@ async fn cmd_new(parent_dir: &PathBuf, name: &str, lang: &str) -> anyhow::Result
 }
 
 /// Comando: forge watch
-async fn cmd_watch(project_dir: &PathBuf, dashboard: bool) -> anyhow::Result<()> {
+async fn cmd_watch(project_dir: &Path, dashboard: bool) -> anyhow::Result<()> {
     use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher, Event, EventKind};
     use std::sync::mpsc;
 
@@ -895,7 +1092,7 @@ async fn cmd_watch(project_dir: &PathBuf, dashboard: bool) -> anyhow::Result<()>
     }
 
     if dashboard {
-        let p = project_dir.clone();
+        let p = project_dir.to_path_buf();
         tokio::spawn(async move {
             let _ = dashboard::cmd_dashboard(&p, 3000).await;
         });
@@ -914,7 +1111,7 @@ async fn cmd_watch(project_dir: &PathBuf, dashboard: bool) -> anyhow::Result<()>
 
     // Build inicial
     println!("{}", "\n── Build inicial ──".dimmed());
-    if let Err(e) = cmd_build(project_dir.clone(), false, false).await {
+    if let Err(e) = cmd_build(project_dir.to_path_buf(), false, false).await {
         eprintln!("   {} {}", "⚠️  Error en build:".yellow(), e);
     }
 
@@ -923,6 +1120,15 @@ async fn cmd_watch(project_dir: &PathBuf, dashboard: bool) -> anyhow::Result<()>
     let mut watcher = RecommendedWatcher::new(tx, Config::default())?;
     watcher.watch(&source_dir, RecursiveMode::Recursive)?;
 
+    // Proyecto mixto Kotlin + Java: también los fuentes Java
+    let java_dir = config
+        .has_java_sources()
+        .then(|| project_dir.join(config.java_source_dir(false)))
+        .filter(|dir| dir.exists());
+    if let Some(dir) = &java_dir {
+        watcher.watch(dir, RecursiveMode::Recursive)?;
+    }
+
     // Configurar Ctrl+C
     let running = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
     let r = running.clone();
@@ -935,7 +1141,10 @@ async fn cmd_watch(project_dir: &PathBuf, dashboard: bool) -> anyhow::Result<()>
         "✅ Watcher activo — editá tu código y FORGE recompilará automáticamente\n".green()
     );
 
-    let extensions = cyrce_forge_langs::extensions_for_lang(&config.project.lang);
+    let mut extensions = cyrce_forge_langs::extensions_for_lang(&config.project.lang).to_vec();
+    if java_dir.is_some() {
+        extensions.push("java");
+    }
 
     while running.load(std::sync::atomic::Ordering::SeqCst) {
         match rx.recv_timeout(std::time::Duration::from_millis(500)) {
@@ -968,7 +1177,7 @@ async fn cmd_watch(project_dir: &PathBuf, dashboard: bool) -> anyhow::Result<()>
                     );
 
                     let start = Instant::now();
-                    match cmd_build(project_dir.clone(), false, false).await {
+                    match cmd_build(project_dir.to_path_buf(), false, false).await {
                         Ok(_) => {
                             println!(
                                 "{}",
@@ -1001,7 +1210,7 @@ async fn cmd_watch(project_dir: &PathBuf, dashboard: bool) -> anyhow::Result<()>
 }
 
 /// Comando: forge task <nombre>
-async fn cmd_task(project_dir: &PathBuf, task_name: &str) -> anyhow::Result<()> {
+async fn cmd_task(project_dir: &Path, task_name: &str) -> anyhow::Result<()> {
     let config = ForgeConfig::load(project_dir)?;
 
     let task = config
@@ -1139,8 +1348,8 @@ async fn cmd_doctor() -> anyhow::Result<()> {
 
     // Verificar FORGE
     println!("\n{}", "📦 FORGE".bold());
-    println!("   {} {} — {}", "✅".green(), "Versión", env!("CARGO_PKG_VERSION").dimmed());
-    println!("   {} {} — {}", "✅".green(), "Ubicación", std::env::current_exe().unwrap_or_default().display().to_string().dimmed());
+    println!("   {} Versión — {}", "✅".green(), env!("CARGO_PKG_VERSION").dimmed());
+    println!("   {} Ubicación — {}", "✅".green(), std::env::current_exe().unwrap_or_default().display().to_string().dimmed());
 
     // Verificar caché global
     if let Some(home) = dirs::home_dir() {
@@ -1148,14 +1357,14 @@ async fn cmd_doctor() -> anyhow::Result<()> {
         let repo_cache = home.join(".forge").join("repository");
         if forge_cache.exists() {
             let size = dir_size(&forge_cache);
-            println!("   {} {} — {}", "✅".green(), "Caché global",
+            println!("   {} Caché global — {}", "✅".green(),
                 format!("{} ({})", forge_cache.display(), format_bytes(size)).dimmed());
         } else {
-            println!("   {} {} — {}", "ℹ️ ".cyan(), "Caché global", "No creada aún".dimmed());
+            println!("   {} Caché global — {}", "ℹ️ ".cyan(), "No creada aún".dimmed());
         }
         if repo_cache.exists() {
             let count = std::fs::read_dir(&repo_cache).map(|r| r.count()).unwrap_or(0);
-            println!("   {} {} — {}", "✅".green(), "Dependencias", format!("{} en caché", count).dimmed());
+            println!("   {} Dependencias — {}", "✅".green(), format!("{} en caché", count).dimmed());
         }
     }
 
@@ -1178,8 +1387,53 @@ async fn cmd_doctor() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Comando: forge daemon
+async fn cmd_daemon(stop: bool) -> anyhow::Result<()> {
+    if stop {
+        let stopped = cyrce_forge_langs::daemon::stop_all().await;
+        if stopped == 0 {
+            println!("{}", "🔌 No hay daemons de compilación en ejecución".dimmed());
+        } else {
+            println!("{}", format!("✅ {} daemon(s) de compilación detenido(s)", stopped).green().bold());
+        }
+        return Ok(());
+    }
+
+    let daemons = cyrce_forge_langs::daemon::status().await;
+    if daemons.is_empty() {
+        println!("{}", "🔌 No hay daemons de compilación en ejecución".dimmed());
+        println!("   {}", "Se inician solos en el próximo build de Java o Kotlin.".dimmed());
+        return Ok(());
+    }
+
+    println!("{}", "🔌 Daemons de compilación".bold());
+    println!("{}", "─".repeat(50).dimmed());
+    for daemon in &daemons {
+        println!(
+            "   {} pid {} — {} compilaciones, iniciado hace {}, inactivo {}",
+            "●".green(),
+            daemon.pid.to_string().cyan(),
+            daemon.compilations,
+            format_secs(daemon.uptime_secs),
+            format_secs(daemon.idle_secs)
+        );
+        println!("     {}", daemon.java_home.dimmed());
+    }
+    println!("\n   {}", "Detenerlos: forge daemon --stop".dimmed());
+    Ok(())
+}
+
+/// Duración legible: `45s`, `12m`, `3h 05m`.
+fn format_secs(secs: u64) -> String {
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m", secs / 60),
+        _ => format!("{}h {:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
 /// Comando: forge stats
-async fn cmd_stats(project_dir: &PathBuf) -> anyhow::Result<()> {
+async fn cmd_stats(project_dir: &Path) -> anyhow::Result<()> {
     let config = ForgeConfig::load(project_dir)
         .context("No se encontró forge.toml. ¿Estás en un proyecto FORGE?")?;
 
@@ -1237,7 +1491,7 @@ async fn cmd_stats(project_dir: &PathBuf) -> anyhow::Result<()> {
     if !files_by_ext.is_empty() {
         println!("\n   {}", "Por extensión:".cyan().bold());
         let mut sorted: Vec<_> = files_by_ext.into_iter().collect();
-        sorted.sort_by(|a, b| b.1.1.cmp(&a.1.1));
+        sorted.sort_by_key(|e| std::cmp::Reverse(e.1.1));
         for (ext, (count, lines)) in &sorted {
             println!("      .{:<8} {} archivos, {} líneas", ext, count, lines);
         }
@@ -1295,7 +1549,7 @@ fn format_bytes(bytes: u64) -> String {
 }
 
 /// Comando: forge bench
-async fn cmd_bench(project_dir: &PathBuf, verbose: bool) -> anyhow::Result<()> {
+async fn cmd_bench(project_dir: &Path, verbose: bool) -> anyhow::Result<()> {
     let config = ForgeConfig::load(project_dir)?;
 
     println!("{}", "⏱️  Benchmark de Compilación".bold());
@@ -1316,7 +1570,7 @@ async fn cmd_bench(project_dir: &PathBuf, verbose: bool) -> anyhow::Result<()> {
         );
 
         let start = Instant::now();
-        cmd_build(project_dir.clone(), verbose, false).await?;
+        cmd_build(project_dir.to_path_buf(), verbose, false).await?;
         let elapsed = start.elapsed().as_secs_f64();
         times.push(elapsed);
 
@@ -1355,7 +1609,7 @@ async fn cmd_bench(project_dir: &PathBuf, verbose: bool) -> anyhow::Result<()> {
 }
 
 /// Comando: forge package
-async fn cmd_package(project_dir: &PathBuf) -> anyhow::Result<()> {
+async fn cmd_package(project_dir: &Path, image: bool, installer: bool, dist: bool) -> anyhow::Result<()> {
     let config = ForgeConfig::load(project_dir)?;
 
     println!(
@@ -1364,7 +1618,7 @@ async fn cmd_package(project_dir: &PathBuf) -> anyhow::Result<()> {
     );
 
     // Compilar primero
-    cmd_build(project_dir.clone(), false, false).await?;
+    cmd_build(project_dir.to_path_buf(), false, false).await?;
 
     // Crear directorio dist
     let dist_dir = project_dir.join("dist");
@@ -1379,30 +1633,69 @@ async fn cmd_package(project_dir: &PathBuf) -> anyhow::Result<()> {
 
     match config.project.lang.as_str() {
         "java" | "kotlin" => {
-            // Para Java/Kotlin: el JAR ya está en build/
-            let build_dir = project_dir.join(&config.project.output_dir);
-            let jar_name = format!("{}.jar", config.project.name);
-            let jar_src = build_dir.join(&jar_name);
+            // Para Java/Kotlin: generar el JAR (o fat-jar) en build/ y copiarlo a dist/
+            let (jar_src, classpath) = if config.project.lang == "kotlin" {
+                (
+                    KotlinModule::package(&config, project_dir).await?,
+                    KotlinModule::runtime_classpath(&config, project_dir),
+                )
+            } else {
+                (
+                    JavaModule::package(&config, project_dir).await?,
+                    JavaModule::runtime_classpath(&config, project_dir),
+                )
+            };
             let jar_dst = dist_dir.join(format!("{}.jar", package_name));
 
-            if jar_src.exists() {
-                std::fs::copy(&jar_src, &jar_dst)?;
-                let size = std::fs::metadata(&jar_dst)?.len();
-                println!("   {} {} ({})", "✅ JAR:".green(), jar_dst.display(), format_bytes(size));
-            } else {
-                // Copiar archivos .class si no hay JAR
-                let classes_dir = build_dir.join("classes");
-                if classes_dir.exists() {
-                    let dest = dist_dir.join(format!("{}-classes", package_name));
-                    copy_dir_recursive(&classes_dir, &dest)?;
-                    println!("   {} {}", "✅ Classes:".green(), dest.display());
-                } else {
-                    println!("   {}", "⚠️  No se encontraron artefactos compilados".yellow());
-                    return Ok(());
+            std::fs::copy(&jar_src, &jar_dst)?;
+            let size = std::fs::metadata(&jar_dst)?.len();
+            println!("   {} {} ({})", "✅ JAR:".green(), jar_dst.display(), format_bytes(size));
+
+            if dist {
+                let archives = cyrce_forge_langs::dist::create_distribution(
+                    &config,
+                    project_dir,
+                    &jar_src,
+                    &classpath,
+                    &dist_dir,
+                    &package_name,
+                )?;
+                for archive in archives {
+                    let size = std::fs::metadata(&archive)?.len();
+                    println!("   {} {} ({})", "✅ Distribución:".green(), archive.display(), format_bytes(size));
+                }
+            }
+
+            if image {
+                let image_dir = dist_dir.join(format!("{}-image", package_name));
+                let launcher =
+                    cyrce_forge_langs::image::create_image(&config, project_dir, &jar_src, &classpath, &image_dir)
+                        .await?;
+                println!(
+                    "   {} {} ({})",
+                    "✅ Imagen:".green(),
+                    image_dir.display(),
+                    format_bytes(dir_size(&image_dir))
+                );
+                println!("   {}", format!("▶️  Ejecuta con {}", launcher.display()).dimmed());
+            }
+
+            if installer {
+                let created =
+                    cyrce_forge_langs::image::create_installer(&config, project_dir, &jar_src, &classpath, &dist_dir).await?;
+                for path in created {
+                    println!("   {} {}", "✅ Instalador:".green(), path.display());
                 }
             }
         }
         "python" => {
+            if image || installer || dist {
+                println!(
+                    "   {}",
+                    "⚠️  --image, --installer y --dist solo aplican a proyectos Java/Kotlin".yellow()
+                );
+            }
+
             // Para Python: copiar el source dir
             let source_dir = project_dir.join(config.source_dir());
             let dest = dist_dir.join(&package_name);
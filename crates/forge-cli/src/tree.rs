@@ -1,44 +1,235 @@
 // =============================================================================
 // 🔥 FORGE — Comando: tree
 // =============================================================================
-// Visualiza el árbol de dependencias del proyecto.
-// Muestra tanto las directas como un resumen de las test-dependencies.
+// Visualiza el árbol de dependencias resuelto: versiones finales, conflictos
+// mediados (`1.2 -> 1.5`), apariciones repetidas omitidas y el origen de
+// cada versión (forge.toml, forge.lock, rango). Con `--invert` muestra
+// quién trae un artefacto; con `--json`, el grafo completo.
 // =============================================================================
 
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::Path;
+
 use colored::Colorize;
+
 use cyrce_forge_core::config::ForgeConfig;
+use cyrce_forge_deps::graph::{DependencyGraph, GraphNode, Selection};
+use cyrce_forge_deps::lockfile::Lockfile;
+use cyrce_forge_deps::maven::MavenResolver;
 
-pub async fn cmd_tree(project_dir: &PathBuf) -> anyhow::Result<()> {
+pub async fn cmd_tree(project_dir: &Path, invert: Option<&str>, json: bool) -> anyhow::Result<()> {
     let config = ForgeConfig::load(project_dir)?;
-    
-    println!("{} {}", "🌲".green(), format!("Árbol de dependencias para '{}'", config.project.name).bold());
-    
-    if config.dependencies.is_empty() && config.test_dependencies.is_empty() {
-        println!("   {}", "No hay dependencias declaradas en este proyecto.".dimmed());
+
+    if !json {
+        println!("{} {}", "🌲".green(), format!("Árbol de dependencias para '{}'", config.project.name).bold());
+    }
+
+    if config.dependencies.is_empty()
+        && config.test_dependencies.is_empty()
+        && config.annotation_processors.is_empty()
+        && config.kotlin_version().is_none()
+    {
+        if json {
+            println!("{}", serde_json::json!({ "project": config.project.name, "nodes": [] }));
+        } else {
+            println!("   {}", "No hay dependencias declaradas en este proyecto.".dimmed());
+        }
         return Ok(());
     }
 
-    if !config.dependencies.is_empty() {
-        println!("\n   {}", "[dependencies]".cyan());
-        let count = config.dependencies.len();
-        for (i, (key, val)) in config.dependencies.iter().enumerate() {
-            let symbol = if i == count - 1 { "└──" } else { "├──" };
-            println!("   {} {} {}", symbol, key.bold(), val.dimmed());
+    if !json {
+        println!("   {}", "Resolviendo el grafo de dependencias...".dimmed());
+    }
+    let graph = resolve_graph(&config, project_dir).await?;
+
+    if let Some(name) = invert {
+        let matches = graph.find(name);
+        if matches.is_empty() {
+            return Err(anyhow::anyhow!("'{}' no aparece en el grafo de dependencias", name));
+        }
+        if json {
+            let paths: Vec<Vec<&GraphNode>> = matches
+                .iter()
+                .map(|&i| graph.path_to(i).into_iter().map(|n| &graph.nodes[n]).collect())
+                .collect();
+            let output = serde_json::json!({
+                "project": config.project.name,
+                "artifact": name,
+                "paths": paths,
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        } else {
+            print_inverted(&graph, &matches);
         }
+        return Ok(());
     }
 
-    if !config.test_dependencies.is_empty() {
-        println!("\n   {}", "[test-dependencies]".purple());
-        let count = config.test_dependencies.len();
-        for (i, (key, val)) in config.test_dependencies.iter().enumerate() {
-            let symbol = if i == count - 1 { "└──" } else { "├──" };
-            println!("   {} {} {}", symbol, key.bold(), val.dimmed());
+    if json {
+        let output = serde_json::json!({ "project": config.project.name, "nodes": graph.nodes });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    for scope in ["compile", "test", "processor"] {
+        let roots = graph.roots(scope);
+        if roots.is_empty() {
+            continue;
+        }
+        let section = scope_section(scope);
+        let title = if scope == "compile" { section.cyan() } else { section.purple() };
+        println!("\n   {}", title);
+        for (i, &root) in roots.iter().enumerate() {
+            print_node(&graph, root, "   ", i == roots.len() - 1);
+        }
+    }
+
+    let local: Vec<(&String, &String)> = config
+        .dependencies
+        .iter()
+        .chain(config.test_dependencies.iter())
+        .filter(|(_, v)| v.starts_with("path:"))
+        .collect();
+    if !local.is_empty() {
+        println!("\n   {}", "Módulos locales".cyan());
+        for (name, path) in local {
+            println!("   • {} {}", name.bold(), path.dimmed());
         }
     }
 
-    println!("\n   {}", "Nota: El árbol completo con sub-dependencias transitivas".dimmed());
-    println!("   {}", "      se visualiza resolviéndolas durante 'forge build'.".dimmed());
+    if graph.nodes.iter().any(|n| n.selection == Selection::Mediated) {
+        println!(
+            "\n   {}",
+            "(*) ya incluida por otro camino; no se expande. 'a -> b': se pidió a, se usa b.".dimmed()
+        );
+    }
 
     Ok(())
 }
+
+/// Resuelve las dependencias del proyecto (respetando forge.lock, sin modificarlo
+/// ni tocar los classpath de .forge/) y devuelve el grafo. Para Python, el grafo son las dependencias declaradas con
+/// su versión fijada: pip resuelve las transitivas.
+pub(crate) async fn resolve_graph(config: &ForgeConfig, project_dir: &Path) -> anyhow::Result<DependencyGraph> {
+    let lock = Lockfile::load(project_dir)?;
+    let remote = |deps: &HashMap<String, String>| -> HashMap<String, String> {
+        deps.iter()
+            .filter(|(_, v)| !v.starts_with("path:"))
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    };
+
+    match config.project.lang.as_str() {
+        "java" | "kotlin" => {
+            let mut resolver = MavenResolver::new(config, project_dir)?
+                .with_lock(lock.as_ref(), &[])
+                .graph_only()
+                .quiet();
+
+            // Incluye kotlin-stdlib y kotlin-test implícitos de [kotlin] version
+            let deps = remote(&config.maven_dependencies(false));
+            if !deps.is_empty() {
+                resolver.resolve_all(&deps).await?;
+            }
+            let test_deps = remote(&config.maven_dependencies(true));
+            if !test_deps.is_empty() {
+                resolver.resolve_test_deps(&test_deps).await?;
+            }
+            let processors = remote(&config.annotation_processors);
+            if !processors.is_empty() {
+                resolver.resolve_processors(&processors).await?;
+            }
+            Ok(resolver.graph().clone())
+        }
+        _ => {
+            let mut graph = DependencyGraph::default();
+            let mut deps: Vec<(String, String)> = remote(&config.dependencies).into_iter().collect();
+            deps.sort();
+            for (name, requested) in deps {
+                let pinned = lock.as_ref().and_then(|l| l.pypi_pin(&name, &[]));
+                let floating = requested == "*" || requested.is_empty();
+                let (version, selection) = match pinned {
+                    Some(pinned) if floating => (pinned.to_string(), Selection::Locked),
+                    _ => (requested.clone(), Selection::Declared),
+                };
+                graph.push(GraphNode {
+                    group: String::new(),
+                    artifact: name,
+                    requested,
+                    version,
+                    scope: "compile".to_string(),
+                    parent: None,
+                    selection,
+                    winner: None,
+                });
+            }
+            Ok(graph)
+        }
+    }
+}
+
+/// Sección de forge.toml de la que sale cada scope del grafo.
+pub(crate) fn scope_section(scope: &str) -> &'static str {
+    match scope {
+        "test" => "[test-dependencies]",
+        "processor" => "[annotation-processors]",
+        _ => "[dependencies]",
+    }
+}
+
+/// Texto de un nodo: coordenada, conflicto, omisión y origen de la versión.
+pub(crate) fn describe(node: &GraphNode) -> String {
+    let name = if node.group.is_empty() {
+        node.artifact.clone()
+    } else {
+        node.key()
+    };
+
+    let mut line = if node.is_conflict() {
+        format!(
+            "{} {}",
+            name.bold(),
+            format!("{} -> {}", node.requested, node.version).yellow()
+        )
+    } else if node.requested != node.version {
+        // Rango, LATEST o SNAPSHOT: se muestra lo pedido junto a lo elegido
+        format!("{}:{} {}", name.bold(), node.version, format!("({})", node.requested).dimmed())
+    } else {
+        format!("{}:{}", name.bold(), node.version)
+    };
+
+    match node.selection {
+        Selection::Mediated => line.push_str(&format!(" {}", "(*)".dimmed())),
+        Selection::Locked => line.push_str(&format!(" {}", "🔒 forge.lock".dimmed())),
+        Selection::Range => line.push_str(&format!(" {}", "(rango)".dimmed())),
+        Selection::Declared | Selection::Nearest => {}
+    }
+    line
+}
+
+fn print_node(graph: &DependencyGraph, index: usize, prefix: &str, last: bool) {
+    let symbol = if last { "└──" } else { "├──" };
+    println!("{}{} {}", prefix, symbol, describe(&graph.nodes[index]));
+
+    let children = graph.children(index);
+    let child_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+    for (i, &child) in children.iter().enumerate() {
+        print_node(graph, child, &child_prefix, i == children.len() - 1);
+    }
+}
+
+/// `--invert`: cada aparición del artefacto y, debajo, quién la pidió hasta llegar a forge.toml.
+fn print_inverted(graph: &DependencyGraph, matches: &[usize]) {
+    for &index in matches {
+        let node = &graph.nodes[index];
+        println!("\n   {} {}", describe(node), format!("[{}]", node.scope).dimmed());
+
+        let mut prefix = "   ".to_string();
+        let mut current = node.parent;
+        while let Some(parent) = current {
+            println!("{}└── {}", prefix, describe(&graph.nodes[parent]));
+            prefix.push_str("    ");
+            current = graph.nodes[parent].parent;
+        }
+        println!("{}└── {}", prefix, format!("forge.toml {}", scope_section(&node.scope)).cyan());
+    }
+}
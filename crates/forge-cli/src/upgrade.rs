@@ -2,18 +2,305 @@
 // 🔥 FORGE — Comando: upgrade
 // =============================================================================
 // Actualiza las dependencias declaradas en forge.toml a sus últimas versiones
-// estables comprobando en Maven Central o PyPI.
+// estables consultando maven-metadata.xml (en todos los repositorios
+// configurados) o la API JSON de PyPI. forge.toml se reescribe con toml_edit
+// para conservar comentarios, orden y formato.
 // =============================================================================
 
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::Context;
 use colored::Colorize;
 
-pub async fn cmd_upgrade(_project_dir: &PathBuf) -> anyhow::Result<()> {
-    println!("{} {}", "⚠️".yellow(), "forge upgrade".bold());
-    println!("   {}", "Esta función está parcialmente implementada (Fase 15).".dimmed());
-    println!("   En próximas versiones permitirá actualizar dinámicamente las versiones");
-    println!("   de las dependencias a las últimas disponibles en Maven Central / PyPI.");
-    
-    // WIP: Para resolver las versiones (necesita parsing del JSON de Maven Central Search API)
+use cyrce_forge_core::config::{ForgeConfig, UpdatePolicy};
+use cyrce_forge_deps::maven::{MavenCoordinate, MavenResolver};
+use cyrce_forge_deps::pypi::{self, PypiResolver};
+use cyrce_forge_deps::version::{Upgrades, VersionSpec};
+
+/// Una dependencia declarada con sus versiones más nuevas.
+struct Candidate {
+    section: &'static str,
+    key: String,
+    current: String,
+    upgrades: Upgrades,
+}
+
+pub async fn cmd_upgrade(
+    project_dir: &Path,
+    selected: &[String],
+    major: bool,
+    dry_run: bool,
+) -> anyhow::Result<()> {
+    let config = ForgeConfig::load(project_dir)?;
+    let maven = matches!(config.project.lang.as_str(), "java" | "kotlin");
+
+    println!(
+        "{} {}",
+        "⬆️".cyan(),
+        format!("Buscando actualizaciones para '{}'", config.project.name).bold()
+    );
+
+    let sections: [(&'static str, &HashMap<String, String>); 3] = [
+        ("dependencies", &config.dependencies),
+        ("test-dependencies", &config.test_dependencies),
+        ("annotation-processors", &config.annotation_processors),
+    ];
+
+    let mut declared: Vec<(&'static str, &String, &String)> = sections
+        .iter()
+        .flat_map(|(section, deps)| deps.iter().map(move |(k, v)| (*section, k, v)))
+        .filter(|(_, key, _)| selected.is_empty() || selected.iter().any(|name| matches(name, key)))
+        .collect();
+    declared.sort();
+
+    for name in selected {
+        if !declared.iter().any(|(_, key, _)| matches(name, key)) {
+            println!(
+                "   {}",
+                format!("⚠️  '{}' no está declarada en forge.toml", name).yellow()
+            );
+        }
+    }
+
+    // Siempre se consulta la metadata fresca, sin importar [resolver] update-policy
+    let resolver = if maven {
+        Some(MavenResolver::new(&config, project_dir)?.with_update_policy(UpdatePolicy::Always))
+    } else {
+        None
+    };
+    let pypi = PypiResolver::new();
+
+    let mut candidates = Vec::new();
+    for (section, key, current) in declared {
+        if current.starts_with("path:") {
+            continue;
+        }
+        if is_dynamic(current, maven) {
+            println!(
+                "   {}",
+                format!("⏭️  {} = \"{}\" (versión dinámica, se omite)", key, current).dimmed()
+            );
+            continue;
+        }
+
+        let available = match &resolver {
+            Some(resolver) => {
+                let coord = MavenCoordinate::parse(key, current)?;
+                resolver.available_versions(&coord).await
+            }
+            None => pypi.available_versions(key).await,
+        };
+
+        match available {
+            Ok(available) => candidates.push(Candidate {
+                section,
+                key: key.clone(),
+                current: current.clone(),
+                upgrades: if maven {
+                    Upgrades::find(current, &available)
+                } else {
+                    pypi::upgrades(current, &available)
+                },
+            }),
+            Err(e) => println!("   {}", format!("⚠️  {}: {}", key, e).yellow()),
+        }
+    }
+
+    if candidates.is_empty() {
+        println!("   {}", "No hay dependencias con versión fija para revisar.".dimmed());
+        return Ok(());
+    }
+
+    print_table(&candidates, major);
+
+    let changes: Vec<(&Candidate, &str)> = candidates
+        .iter()
+        .filter_map(|c| c.upgrades.target(major).map(|target| (c, target)))
+        .collect();
+
+    let major_only = candidates
+        .iter()
+        .filter(|c| !major && c.upgrades.minor.is_none() && c.upgrades.major.is_some())
+        .count();
+    if major_only > 0 {
+        println!(
+            "\n   {}",
+            format!(
+                "💡 {} dependencia(s) solo tienen una versión mayor nueva: usa 'forge upgrade --major'",
+                major_only
+            )
+            .dimmed()
+        );
+    }
+
+    if changes.is_empty() {
+        println!("\n   {} {}", "✅".green(), "Todas las dependencias están al día".bold());
+        return Ok(());
+    }
+
+    if dry_run {
+        println!(
+            "\n   {}",
+            format!("🔍 --dry-run: {} actualización(es) disponibles, forge.toml no se modificó", changes.len())
+                .yellow()
+        );
+        return Ok(());
+    }
+
+    let toml_path = project_dir.join("forge.toml");
+    let content = std::fs::read_to_string(&toml_path)?;
+    let mut doc: toml_edit::DocumentMut = content
+        .parse()
+        .with_context(|| format!("No se pudo editar {}", toml_path.display()))?;
+
+    println!();
+    for (candidate, target) in &changes {
+        if set_version(&mut doc, candidate.section, &candidate.key, target) {
+            println!(
+                "   {} {} {} → {}",
+                "✅".green(),
+                candidate.key.bold(),
+                candidate.current.dimmed(),
+                target.green()
+            );
+        }
+    }
+
+    std::fs::write(&toml_path, doc.to_string())?;
+    println!(
+        "\n   {}",
+        "forge.toml actualizado. Ejecuta 'forge deps' para resolver y actualizar forge.lock.".dimmed()
+    );
+
     Ok(())
 }
+
+/// `NOMBRE` en la línea de comandos: `groupId:artifactId`, solo `artifactId` o paquete PyPI.
+fn matches(name: &str, key: &str) -> bool {
+    key.eq_ignore_ascii_case(name)
+        || (!name.contains(':') && key.split_once(':').is_some_and(|(_, artifact)| artifact == name))
+}
+
+/// Rangos, LATEST/RELEASE y `*` no tienen una versión concreta que actualizar.
+fn is_dynamic(version: &str, maven: bool) -> bool {
+    if maven {
+        return VersionSpec::parse(version).map_or(true, |spec| spec.is_dynamic());
+    }
+    version.is_empty() || version == "*" || version.eq_ignore_ascii_case("latest")
+}
+
+/// Cambia el valor de `[section] key` conservando espacios y comentarios alrededor.
+fn set_version(doc: &mut toml_edit::DocumentMut, section: &str, key: &str, version: &str) -> bool {
+    let Some(value) = doc
+        .get_mut(section)
+        .and_then(|table| table.get_mut(key))
+        .and_then(|item| item.as_value_mut())
+    else {
+        return false;
+    };
+
+    let decor = value.decor().clone();
+    *value = toml_edit::Value::from(version);
+    *value.decor_mut() = decor;
+    true
+}
+
+/// Tabla: actual, último parche, último menor y último mayor. Se resalta la columna elegida.
+fn print_table(candidates: &[Candidate], major: bool) {
+    let none = "—".to_string();
+    let width = |f: &dyn Fn(&Candidate) -> usize, title: &str| {
+        candidates.iter().map(f).max().unwrap_or(0).max(title.chars().count())
+    };
+    let len = |v: &Option<String>| v.as_ref().map_or(1, |v| v.chars().count());
+
+    let w_key = width(&|c| c.key.chars().count(), "Dependencia");
+    let w_current = width(&|c| c.current.chars().count(), "Actual");
+    let w_patch = width(&|c| len(&c.upgrades.patch), "Parche");
+    let w_minor = width(&|c| len(&c.upgrades.minor), "Menor");
+
+    println!();
+    println!(
+        "   {}",
+        format!(
+            "{:<w_key$}  {:<w_current$}  {:<w_patch$}  {:<w_minor$}  {}",
+            "Dependencia", "Actual", "Parche", "Menor", "Mayor"
+        )
+        .bold()
+    );
+
+    for c in candidates {
+        let cell = |v: &Option<String>, w: usize, chosen: bool| {
+            let text = format!("{:<w$}", v.as_ref().unwrap_or(&none));
+            match v {
+                Some(_) if chosen => text.green().bold(),
+                Some(_) => text.yellow(),
+                None => text.dimmed(),
+            }
+        };
+        println!(
+            "   {:<w_key$}  {}  {}  {}  {}",
+            c.key,
+            format!("{:<w_current$}", c.current).dimmed(),
+            cell(&c.upgrades.patch, w_patch, false),
+            cell(&c.upgrades.minor, w_minor, !major),
+            cell(&c.upgrades.major, 0, major),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORGE_TOML: &str = r#"[project]
+name = "app"
+
+[dependencies]
+# Cliente HTTP
+"com.squareup.okhttp3:okhttp" = "4.11.0"   # fijada por compatibilidad
+"com.google.guava:guava" = "32.0.0-jre"
+
+[test-dependencies]
+"org.junit.jupiter:junit-jupiter" = "5.9.0" # última con Java 8
+"#;
+
+    #[test]
+    fn test_set_version_preserves_comments_in_both_sections() {
+        let mut doc: toml_edit::DocumentMut = FORGE_TOML.parse().unwrap();
+        assert!(set_version(&mut doc, "dependencies", "com.squareup.okhttp3:okhttp", "4.12.0"));
+        assert!(set_version(&mut doc, "test-dependencies", "org.junit.jupiter:junit-jupiter", "5.10.2"));
+        assert!(!set_version(&mut doc, "dependencies", "org.junit.jupiter:junit-jupiter", "5.10.2"));
+        assert!(!set_version(&mut doc, "dev-dependencies", "com.google.guava:guava", "33.0.0-jre"));
+
+        let expected = FORGE_TOML
+            .replace("\"4.11.0\"   #", "\"4.12.0\"   #")
+            .replace("\"5.9.0\" #", "\"5.10.2\" #");
+        assert_eq!(doc.to_string(), expected);
+    }
+
+    #[test]
+    fn test_matches() {
+        assert!(matches("com.google.guava:guava", "com.google.guava:guava"));
+        assert!(matches("COM.GOOGLE.GUAVA:GUAVA", "com.google.guava:guava"));
+        assert!(matches("guava", "com.google.guava:guava"));
+        assert!(!matches("google.guava:guava", "com.google.guava:guava"));
+        assert!(!matches("org.other:guava", "com.google.guava:guava"));
+        assert!(matches("Requests", "requests"));
+        assert!(!matches("request", "requests"));
+    }
+
+    #[test]
+    fn test_is_dynamic() {
+        assert!(!is_dynamic("32.0.0-jre", true));
+        assert!(!is_dynamic("1.0-SNAPSHOT", true));
+        assert!(is_dynamic("[1.0,2.0)", true));
+        assert!(is_dynamic("LATEST", true));
+        assert!(is_dynamic("RELEASE", true));
+
+        assert!(!is_dynamic("2.31.0", false));
+        assert!(is_dynamic("", false));
+        assert!(is_dynamic("*", false));
+        assert!(is_dynamic("Latest", false));
+    }
+}
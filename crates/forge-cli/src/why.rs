@@ -0,0 +1,164 @@
+// =============================================================================
+// 🔥 FORGE — Comando: why
+// =============================================================================
+// Explica por qué un artefacto está en el classpath: cada camino desde las
+// dependencias declaradas en forge.toml hasta él y la regla que eligió su
+// versión final. Usa el mismo grafo que el resolver (y que `forge tree`).
+// =============================================================================
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use colored::Colorize;
+
+use cyrce_forge_core::config::ForgeConfig;
+use cyrce_forge_deps::graph::{DependencyGraph, Selection};
+
+use crate::tree::{describe, resolve_graph, scope_section};
+
+pub async fn cmd_why(project_dir: &Path, artifact: &str) -> anyhow::Result<()> {
+    let config = ForgeConfig::load(project_dir)?;
+
+    println!("{} {}", "🔎".cyan(), format!("¿Por qué está '{}'?", artifact).bold());
+    println!("   {}", "Resolviendo el grafo de dependencias...".dimmed());
+
+    let graph = resolve_graph(&config, project_dir).await?;
+    for line in report(&graph, artifact)? {
+        println!("{}", line);
+    }
+
+    Ok(())
+}
+
+/// Líneas de la explicación: una sección por (artefacto, scope) con la versión
+/// elegida y todos los caminos que llegan a él.
+fn report(graph: &DependencyGraph, artifact: &str) -> anyhow::Result<Vec<String>> {
+    let matches = graph.find(artifact);
+    if matches.is_empty() {
+        return Err(anyhow::anyhow!(
+            "'{}' no es una dependencia del proyecto (ni directa ni transitiva)",
+            artifact
+        ));
+    }
+
+    // Cada scope se resuelve por separado: una explicación por (artefacto, scope).
+    // Las apariciones llegan en orden del grafo, intercaladas entre sí
+    let mut seen = HashSet::new();
+    let groups: Vec<(String, String)> = matches
+        .iter()
+        .map(|&i| (graph.nodes[i].key(), graph.nodes[i].scope.clone()))
+        .filter(|group| seen.insert(group.clone()))
+        .collect();
+
+    let mut lines = Vec::new();
+    for (key, scope) in groups {
+        let occurrences: Vec<usize> = matches
+            .iter()
+            .copied()
+            .filter(|&i| graph.nodes[i].key() == key && graph.nodes[i].scope == scope)
+            .collect();
+        let Some(&winner) = occurrences.iter().find(|&&i| graph.nodes[i].winner.is_none()) else {
+            continue;
+        };
+        let chosen = &graph.nodes[winner];
+
+        lines.push(format!(
+            "\n   {} {}",
+            format!("{}:{}", key, chosen.version).green().bold(),
+            format!("[{}]", scope).dimmed()
+        ));
+        lines.push(format!(
+            "   Versión elegida: {} — {}",
+            chosen.version.bold(),
+            explain(graph, winner)
+        ));
+
+        lines.push(format!("\n   Caminos ({}):", occurrences.len()));
+        for (n, &index) in occurrences.iter().enumerate() {
+            let node = &graph.nodes[index];
+            let mut steps = vec![format!("forge.toml {}", scope_section(&scope)).cyan().to_string()];
+            steps.extend(graph.path_to(index).into_iter().map(|i| describe(&graph.nodes[i])));
+
+            lines.push(format!("   {}. {}", n + 1, steps.join(" → ")));
+            if node.selection == Selection::Mediated {
+                lines.push(format!(
+                    "      {}",
+                    format!(
+                        "pide {}, se usa {}: ganó la aparición más cercana a forge.toml",
+                        node.requested, node.version
+                    )
+                    .dimmed()
+                ));
+            }
+        }
+    }
+
+    Ok(lines)
+}
+
+/// Regla que eligió la versión de la aparición ganadora.
+fn explain(graph: &DependencyGraph, winner: usize) -> String {
+    let node = &graph.nodes[winner];
+    match node.selection {
+        Selection::Nearest => {
+            let depth = graph.path_to(winner).len();
+            format!("la más cercana a forge.toml (nearest-wins, nivel {})", depth)
+        }
+        Selection::Range => format!("{} ({} → {})", node.selection.describe(), node.requested, node.version),
+        _ => node.selection.describe().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cyrce_forge_deps::graph::GraphNode;
+
+    fn node(artifact: &str, version: &str, parent: Option<usize>, selection: Selection) -> GraphNode {
+        GraphNode {
+            group: "com.acme".to_string(),
+            artifact: artifact.to_string(),
+            requested: version.to_string(),
+            version: version.to_string(),
+            scope: "compile".to_string(),
+            parent,
+            selection,
+            winner: None,
+        }
+    }
+
+    #[test]
+    fn test_report_explains_shared_artifact_once() {
+        colored::control::set_override(false);
+
+        // app y web dependen de util; entre sus dos apariciones en compile
+        // queda la de test, así que llegan intercaladas
+        let mut graph = DependencyGraph::default();
+        let app = graph.push(node("app", "1.0", None, Selection::Declared));
+        let web = graph.push(node("web", "2.0", None, Selection::Declared));
+        let util = graph.push(node("util", "1.5", Some(app), Selection::Nearest));
+        let junit = graph.push(GraphNode {
+            scope: "test".to_string(),
+            ..node("junit", "5.10", None, Selection::Declared)
+        });
+        graph.push(GraphNode {
+            scope: "test".to_string(),
+            ..node("util", "1.5", Some(junit), Selection::Nearest)
+        });
+        graph.push(GraphNode {
+            winner: Some(util),
+            ..node("util", "1.2", Some(web), Selection::Mediated)
+        });
+        graph.settle();
+
+        let lines = report(&graph, "util").unwrap();
+        let chosen: Vec<&String> = lines.iter().filter(|l| l.contains("Versión elegida")).collect();
+        assert_eq!(chosen.len(), 2); // una por scope, no una por aparición
+        assert!(lines.iter().any(|l| l.contains("com.acme:util:1.5 [compile]")));
+        assert!(lines.iter().any(|l| l.contains("com.acme:util:1.5 [test]")));
+        assert!(lines.iter().any(|l| l.contains("Caminos (2)")));
+        assert!(lines.iter().any(|l| l.contains("com.acme:web:2.0 → com.acme:util 1.2 -> 1.5")));
+
+        assert!(report(&graph, "ausente").is_err());
+    }
+}
@@ -21,6 +21,7 @@ use walkdir::WalkDir;
 
 use crate::config::RemoteCacheConfig;
 use crate::error::{ForgeError, ForgeResult};
+use crate::settings::offline_mode;
 
 /// Estado de caché del build, persiste entre ejecuciones.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -33,8 +34,23 @@ pub struct BuildCache {
 
     /// Timestamp de la última ejecución exitosa
     pub last_build_timestamp: Option<u64>,
+
+    /// Mapa de ruta de recurso (Java/Kotlin) -> hash SHA-256 del contenido
+    #[serde(default)]
+    pub resource_hashes: HashMap<String, String>,
+
+    /// Huella de las opciones del compilador con las que se hizo el último build
+    #[serde(default)]
+    pub compiler_options: String,
+
+    /// Fuentes Java de un proyecto mixto Kotlin + Java -> hash SHA-256 del contenido
+    #[serde(default)]
+    pub mixed_source_hashes: HashMap<String, String>,
 }
 
+/// Entrada de `resource_hashes` con la huella de los valores de filtrado.
+const RESOURCE_FILTER_KEY: &str = "<filter>";
+
 impl BuildCache {
     /// Carga la caché desde .forge/cache.json, o crea una nueva si no existe.
     pub fn load(project_dir: &Path) -> ForgeResult<Self> {
@@ -114,6 +130,29 @@ impl BuildCache {
         Ok(())
     }
 
+    /// Verifica si algún recurso cambió, o si cambiaron los valores con los que se
+    /// filtran (`filter`: `None` si el filtrado está desactivado).
+    pub fn has_resource_changes(&self, resources_dir: &Path, filter: Option<&str>) -> ForgeResult<bool> {
+        Ok(Self::resource_state(resources_dir, filter)? != self.resource_hashes)
+    }
+
+    /// Actualiza los hashes de los recursos con el estado actual.
+    pub fn update_resource_hashes(&mut self, resources_dir: &Path, filter: Option<&str>) -> ForgeResult<()> {
+        self.resource_hashes = Self::resource_state(resources_dir, filter)?;
+        Ok(())
+    }
+
+    /// Verifica si cambiaron los fuentes Java de un proyecto mixto.
+    pub fn has_mixed_source_changes(&self, java_dir: &Path) -> ForgeResult<bool> {
+        Ok(Self::compute_hashes(java_dir, &["java"])? != self.mixed_source_hashes)
+    }
+
+    /// Actualiza los hashes de los fuentes Java de un proyecto mixto.
+    pub fn update_mixed_source_hashes(&mut self, java_dir: &Path) -> ForgeResult<()> {
+        self.mixed_source_hashes = Self::compute_hashes(java_dir, &["java"])?;
+        Ok(())
+    }
+
     /// Devuelve los archivos que han cambiado desde el último build.
     pub fn changed_files(&self, source_dir: &Path, extensions: &[&str]) -> ForgeResult<Vec<PathBuf>> {
         let current_hashes = Self::compute_hashes(source_dir, extensions)?;
@@ -152,6 +191,11 @@ impl BuildCache {
             return Ok(());
         }
 
+        if offline_mode() {
+            println!("   {}", "📴 Modo offline: no se sube el caché remoto".dimmed());
+            return Ok(());
+        }
+
         // 1. Calcular el hash maestro (representando el estado global de dependencias/ficheros base del proyecto)
         let master_hash = self.compute_master_hash()?;
         let archive_name = format!("{}.tar.gz", master_hash);
@@ -223,6 +267,10 @@ impl BuildCache {
         output_dir_name: &str,
         remote_config: &RemoteCacheConfig,
     ) -> ForgeResult<bool> {
+        if offline_mode() {
+            return Ok(false);
+        }
+
         let master_hash = self.compute_master_hash()?;
         let archive_name = format!("{}.tar.gz", master_hash);
         let remote_url = format!("{}/cache/{}", remote_config.remote.trim_end_matches('/'), archive_name);
@@ -288,6 +336,26 @@ impl BuildCache {
             }
         }
 
+        hasher.update(b"compiler-options/");
+        hasher.update(self.compiler_options.as_bytes());
+
+        // Los recursos también terminan en el output compilado
+        let mut resource_keys: Vec<&String> = self.resource_hashes.keys().collect();
+        resource_keys.sort();
+        for key in resource_keys {
+            hasher.update(b"resources/");
+            hasher.update(key.as_bytes());
+            hasher.update(self.resource_hashes[key].as_bytes());
+        }
+
+        let mut mixed_keys: Vec<&String> = self.mixed_source_hashes.keys().collect();
+        mixed_keys.sort();
+        for key in mixed_keys {
+            hasher.update(b"java/");
+            hasher.update(key.as_bytes());
+            hasher.update(self.mixed_source_hashes[key].as_bytes());
+        }
+
         Ok(format!("{:x}", hasher.finalize()))
     }
 
@@ -296,6 +364,17 @@ impl BuildCache {
         project_dir.join(".forge").join("cache.json")
     }
 
+    /// Hashes de todos los recursos, más la huella del filtrado si está activo.
+    fn resource_state(resources_dir: &Path, filter: Option<&str>) -> ForgeResult<HashMap<String, String>> {
+        let mut hashes = Self::compute_hashes(resources_dir, &[])?;
+        if let Some(filter) = filter {
+            let mut hasher = Sha256::new();
+            hasher.update(filter.as_bytes());
+            hashes.insert(RESOURCE_FILTER_KEY.to_string(), format!("{:x}", hasher.finalize()));
+        }
+        Ok(hashes)
+    }
+
     /// Calcula hashes SHA-256 de todos los archivos con las extensiones dadas.
     fn compute_hashes(
         source_dir: &Path,
@@ -412,4 +491,30 @@ mod tests {
         // Limpiar
         let _ = fs::remove_dir_all(&temp_dir);
     }
+
+    #[test]
+    fn test_detect_resource_changes() {
+        let temp_dir = std::env::temp_dir().join("forge_test_resource_changes");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+        fs::write(temp_dir.join("app.properties"), "version=${project.version}").unwrap();
+
+        let mut cache = BuildCache::default();
+        assert!(cache.has_resource_changes(&temp_dir, None).unwrap());
+
+        cache.update_resource_hashes(&temp_dir, None).unwrap();
+        assert!(!cache.has_resource_changes(&temp_dir, None).unwrap());
+
+        // Activar el filtrado o cambiar sus valores invalida la caché
+        assert!(cache.has_resource_changes(&temp_dir, Some("project.version=1.0")).unwrap());
+        cache.update_resource_hashes(&temp_dir, Some("project.version=1.0")).unwrap();
+        assert!(!cache.has_resource_changes(&temp_dir, Some("project.version=1.0")).unwrap());
+        assert!(cache.has_resource_changes(&temp_dir, Some("project.version=1.1")).unwrap());
+
+        // Un recurso nuevo (de cualquier extensión) también
+        fs::write(temp_dir.join("logo.png"), [0u8, 1, 2]).unwrap();
+        assert!(cache.has_resource_changes(&temp_dir, Some("project.version=1.0")).unwrap());
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
 }
@@ -34,6 +34,10 @@ pub struct ForgeConfig {
     #[serde(default, rename = "test-dependencies")]
     pub test_dependencies: HashMap<String, String>,
 
+    /// Procesadores de anotaciones de javac (Lombok, MapStruct, Dagger...)
+    #[serde(default, rename = "annotation-processors")]
+    pub annotation_processors: HashMap<String, String>,
+
     /// Tareas personalizadas
     #[serde(default)]
     pub tasks: HashMap<String, TaskConfig>,
@@ -48,6 +52,239 @@ pub struct ForgeConfig {
 
     /// Configuración de caché distribuido (Fase 16)
     pub cache: Option<RemoteCacheConfig>,
+
+    /// Repositorios Maven, consultados en orden (Maven Central se agrega al final)
+    #[serde(default)]
+    pub repositories: Vec<RepositoryConfig>,
+
+    /// Opciones del resolver de dependencias
+    #[serde(default)]
+    pub resolver: ResolverConfig,
+
+    /// Empaquetado de proyectos JVM (JAR simple o fat-jar)
+    #[serde(default)]
+    pub package: PackageConfig,
+
+    /// Lanzador de tests JVM (versión de JUnit Platform y opciones)
+    #[serde(default)]
+    pub test: TestConfig,
+}
+
+/// Empaquetado de proyectos Java/Kotlin (`[package]`).
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PackageConfig {
+    /// Tipo de JAR a generar
+    #[serde(default)]
+    pub mode: PackageMode,
+
+    /// Paquetes de dependencias a reubicar dentro del fat-jar (shading)
+    #[serde(default)]
+    pub relocations: Vec<RelocationConfig>,
+
+    /// Agrega `Multi-Release: true` al manifiesto (clases en META-INF/versions/<N>/)
+    #[serde(default, rename = "multi-release")]
+    pub multi_release: bool,
+
+    /// Opciones de la JVM con las que arrancan la imagen y el instalador (ej: "-Xmx512m")
+    #[serde(default, rename = "jvm-options")]
+    pub jvm_options: Vec<String>,
+
+    /// Imagen de runtime con jlink (`forge package --image`)
+    #[serde(default)]
+    pub image: ImageConfig,
+
+    /// Instalador con jpackage (`forge package --installer`)
+    #[serde(default)]
+    pub installer: InstallerConfig,
+}
+
+/// Opciones de jlink para `forge package --image`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ImageConfig {
+    /// Módulos del JDK a incluir además de los que detecta jdeps (ej: "jdk.crypto.ec")
+    #[serde(default, rename = "add-modules")]
+    pub add_modules: Vec<String>,
+
+    /// Argumentos extra para jlink (ej: "--compress=zip-9")
+    #[serde(default, rename = "jlink-args")]
+    pub jlink_args: Vec<String>,
+}
+
+/// Opciones de jpackage para `forge package --installer`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct InstallerConfig {
+    /// Tipo de instalador: app-image, deb, rpm, dmg, pkg, exe o msi (por defecto, el de la plataforma)
+    #[serde(rename = "type")]
+    pub installer_type: Option<String>,
+
+    /// Fabricante que muestra el instalador
+    pub vendor: Option<String>,
+
+    /// Ícono de la aplicación (ruta relativa al proyecto)
+    pub icon: Option<String>,
+
+    /// Argumentos extra para jpackage (ej: "--linux-shortcut", "--win-menu")
+    #[serde(default, rename = "jpackage-args")]
+    pub jpackage_args: Vec<String>,
+}
+
+/// Tipo de JAR que produce `forge package`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum PackageMode {
+    /// Solo las clases del proyecto
+    #[default]
+    Jar,
+    /// Clases del proyecto + dependencias runtime (ejecutable con `java -jar`)
+    FatJar,
+}
+
+/// Regla de reubicación: `pattern = "com.google.gson"` → `shaded-pattern = "app.shaded.gson"`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelocationConfig {
+    /// Paquete original
+    pub pattern: String,
+
+    /// Paquete destino dentro del fat-jar
+    #[serde(rename = "shaded-pattern")]
+    pub shaded_pattern: String,
+}
+
+/// Opciones del resolver de dependencias (`[resolver]`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolverConfig {
+    /// Política de verificación de checksums (.sha256/.sha1) al descargar
+    #[serde(default)]
+    pub checksum: ChecksumPolicy,
+
+    /// Máximo de artefactos descargándose a la vez
+    #[serde(default = "default_parallel_downloads", rename = "parallel-downloads")]
+    pub parallel_downloads: usize,
+
+    /// Cada cuánto se vuelve a consultar maven-metadata.xml (rangos, latest y SNAPSHOT)
+    #[serde(default, rename = "update-policy")]
+    pub update_policy: UpdatePolicy,
+}
+
+impl Default for ResolverConfig {
+    fn default() -> Self {
+        Self {
+            checksum: ChecksumPolicy::default(),
+            parallel_downloads: default_parallel_downloads(),
+            update_policy: UpdatePolicy::default(),
+        }
+    }
+}
+
+/// Lanzador de tests de Java/Kotlin (`[test]`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestConfig {
+    /// Versión de org.junit.platform:junit-platform-console-standalone
+    #[serde(default = "default_junit_platform", rename = "junit-platform")]
+    pub junit_platform: String,
+
+    /// Argumentos extra del ConsoleLauncher (ej: "--include-tag=fast")
+    #[serde(default, rename = "launcher-args")]
+    pub launcher_args: Vec<String>,
+}
+
+impl Default for TestConfig {
+    fn default() -> Self {
+        Self {
+            junit_platform: default_junit_platform(),
+            launcher_args: Vec::new(),
+        }
+    }
+}
+
+fn default_junit_platform() -> String {
+    "1.12.0".to_string()
+}
+
+/// Política de actualización de metadata remota, como `updatePolicy` de Maven:
+/// `"always"`, `"daily"`, `"never"` o `"interval:<minutos>"`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+pub enum UpdatePolicy {
+    Always,
+    #[default]
+    Daily,
+    Never,
+    Interval(u64),
+}
+
+impl UpdatePolicy {
+    /// ¿Una copia local descargada hace `age` debe volver a consultarse?
+    pub fn is_stale(&self, age: std::time::Duration) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Daily => age.as_secs() >= 24 * 60 * 60,
+            Self::Never => false,
+            Self::Interval(minutes) => age.as_secs() >= minutes * 60,
+        }
+    }
+}
+
+impl TryFrom<String> for UpdatePolicy {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "always" => Ok(Self::Always),
+            "daily" => Ok(Self::Daily),
+            "never" => Ok(Self::Never),
+            other => other
+                .strip_prefix("interval:")
+                .and_then(|minutes| minutes.parse().ok())
+                .map(Self::Interval)
+                .ok_or_else(|| {
+                    format!(
+                        "update-policy inválida '{}': usa \"always\", \"daily\", \"never\" o \"interval:<minutos>\"",
+                        other
+                    )
+                }),
+        }
+    }
+}
+
+impl From<UpdatePolicy> for String {
+    fn from(policy: UpdatePolicy) -> Self {
+        match policy {
+            UpdatePolicy::Always => "always".to_string(),
+            UpdatePolicy::Daily => "daily".to_string(),
+            UpdatePolicy::Never => "never".to_string(),
+            UpdatePolicy::Interval(minutes) => format!("interval:{}", minutes),
+        }
+    }
+}
+
+/// Qué hacer cuando un checksum no coincide o el repositorio no lo publica.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChecksumPolicy {
+    /// Abortar la resolución
+    Strict,
+    /// Mostrar una advertencia y continuar
+    #[default]
+    Warn,
+    /// No descargar ni verificar checksums
+    Off,
+}
+
+/// Repositorio Maven declarado en `[[repositories]]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepositoryConfig {
+    /// Identificador del repositorio (clave para mirrors y credentials.toml)
+    pub name: String,
+
+    /// URL base en layout Maven (http(s):// o file://)
+    pub url: String,
+
+    /// Usuario para autenticación básica (admite `${VARIABLE}`)
+    pub username: Option<String>,
+
+    /// Contraseña para autenticación básica (admite `${VARIABLE}`)
+    pub password: Option<String>,
 }
 
 /// Configuración de servidor remoto de Caché (Distribución S3/HTTP)
@@ -78,6 +315,12 @@ pub struct ProjectConfig {
     #[serde(default = "default_lang")]
     pub lang: String,
 
+    /// Todos los lenguajes de código fuente del proyecto. Solo admite Kotlin + Java
+    /// (`lang = "kotlin"`, `langs = ["kotlin", "java"]`): kotlinc ve los fuentes Java
+    /// y luego javac compila contra las clases de Kotlin.
+    #[serde(default)]
+    pub langs: Vec<String>,
+
     /// Descripción breve del proyecto
     #[serde(default)]
     pub description: String,
@@ -87,6 +330,9 @@ pub struct ProjectConfig {
     pub output_dir: String,
 }
 
+/// groupId de los artefactos de Kotlin (stdlib, kotlin-test, compilador).
+pub const KOTLIN_GROUP: &str = "org.jetbrains.kotlin";
+
 /// Configuración para proyectos Java.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JavaConfig {
@@ -98,6 +344,18 @@ pub struct JavaConfig {
     #[serde(default = "default_java_test_source", rename = "test-source")]
     pub test_source: String,
 
+    /// Directorio de recursos (se copian tal cual a las clases compiladas)
+    #[serde(default = "default_resources")]
+    pub resources: String,
+
+    /// Directorio de recursos de tests (se copian a test-classes)
+    #[serde(default = "default_test_resources", rename = "test-resources")]
+    pub test_resources: String,
+
+    /// Reemplaza `${project.version}`, `${project.name}`, etc. en los recursos de texto
+    #[serde(default, rename = "filter-resources")]
+    pub filter_resources: bool,
+
     /// Versión objetivo del JDK (ej: "17", "21")
     #[serde(default = "default_java_target")]
     pub target: String,
@@ -105,6 +363,20 @@ pub struct JavaConfig {
     /// Clase principal con método main
     #[serde(rename = "main-class")]
     pub main_class: Option<String>,
+
+    /// Paquetes abiertos a reflexión al ejecutar y testear (`--add-opens`,
+    /// ej: "java.base/java.lang=ALL-UNNAMED")
+    #[serde(default, rename = "add-opens")]
+    pub add_opens: Vec<String>,
+
+    /// Paquetes exportados al compilar y ejecutar (`--add-exports`,
+    /// ej: "jdk.compiler/com.sun.tools.javac.api=com.acme.app")
+    #[serde(default, rename = "add-exports")]
+    pub add_exports: Vec<String>,
+
+    /// Opciones del compilador (`compiler-args`, `encoding`, `warnings-as-errors`, `preview`)
+    #[serde(flatten)]
+    pub compiler: CompilerOptions,
 }
 
 /// Configuración para proyectos Kotlin.
@@ -118,6 +390,18 @@ pub struct KotlinConfig {
     #[serde(default = "default_kotlin_test_source", rename = "test-source")]
     pub test_source: String,
 
+    /// Directorio de recursos (se copian tal cual a las clases compiladas)
+    #[serde(default = "default_resources")]
+    pub resources: String,
+
+    /// Directorio de recursos de tests (se copian a test-classes)
+    #[serde(default = "default_test_resources", rename = "test-resources")]
+    pub test_resources: String,
+
+    /// Reemplaza `${project.version}`, `${project.name}`, etc. en los recursos de texto
+    #[serde(default, rename = "filter-resources")]
+    pub filter_resources: bool,
+
     /// Versión objetivo de la JVM
     #[serde(default = "default_java_target")]
     pub jvm_target: String,
@@ -125,6 +409,79 @@ pub struct KotlinConfig {
     /// Clase principal con método main
     #[serde(rename = "main-class")]
     pub main_class: Option<String>,
+
+    /// Versión de Kotlin (ej: "2.1.0"): kotlin-stdlib y kotlin-test se resuelven desde
+    /// Maven con esta versión en lugar de tomarse de la instalación local de kotlinc
+    #[serde(default)]
+    pub version: Option<String>,
+
+    /// Descarga kotlin-compiler-embeddable (de `version`) y lo ejecuta con `java`:
+    /// no hace falta tener kotlinc instalado
+    #[serde(default, rename = "embedded-compiler")]
+    pub embedded_compiler: bool,
+
+    /// Plugins del compilador (groupId:artifactId = versión), cargados con `-Xplugin`
+    /// (ej: kotlin-serialization-compiler-plugin-embeddable)
+    #[serde(default, rename = "compiler-plugins")]
+    pub compiler_plugins: HashMap<String, String>,
+
+    /// Opciones de los plugins como `<id del plugin>:<clave>=<valor>`, pasadas con `-P plugin:...`
+    #[serde(default, rename = "plugin-options")]
+    pub plugin_options: Vec<String>,
+
+    /// Procesadores KSP (`[kotlin.ksp]`)
+    #[serde(default)]
+    pub ksp: Option<KspConfig>,
+
+    /// Opciones del compilador (`compiler-args`, `encoding`, `warnings-as-errors`, `preview`)
+    #[serde(flatten)]
+    pub compiler: CompilerOptions,
+}
+
+/// Kotlin Symbol Processing: se ejecuta antes de kotlinc y sus fuentes generadas
+/// se compilan junto con las del proyecto.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KspConfig {
+    /// Versión de KSP (ej: "2.1.0-1.0.29")
+    pub version: String,
+
+    /// Procesadores (groupId:artifactId = versión)
+    #[serde(default)]
+    pub processors: HashMap<String, String>,
+
+    /// Opciones para los procesadores (equivalente a `ksp { arg(...) }` de Gradle)
+    #[serde(default)]
+    pub options: HashMap<String, String>,
+}
+
+/// Opciones del compilador comunes a `[java]` y `[kotlin]`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct CompilerOptions {
+    /// Argumentos extra para javac/kotlinc (ej: `["-Xlint:all", "-parameters"]`)
+    #[serde(default, rename = "compiler-args")]
+    pub compiler_args: Vec<String>,
+
+    /// Codificación de los fuentes (javac `-encoding`)
+    #[serde(default)]
+    pub encoding: Option<String>,
+
+    /// Falla la compilación ante cualquier warning (`-Werror`)
+    #[serde(default, rename = "warnings-as-errors")]
+    pub warnings_as_errors: bool,
+
+    /// Habilita las características preview del JDK (`--enable-preview`)
+    #[serde(default)]
+    pub preview: bool,
+}
+
+impl CompilerOptions {
+    /// Huella de las opciones para la caché: si cambian, hay que recompilar.
+    pub fn fingerprint(&self) -> String {
+        format!(
+            "args={:?};encoding={:?};werror={};preview={}",
+            self.compiler_args, self.encoding, self.warnings_as_errors, self.preview
+        )
+    }
 }
 
 /// Configuración para proyectos Python.
@@ -207,6 +564,14 @@ fn default_kotlin_test_source() -> String {
     "src/test/kotlin".to_string()
 }
 
+fn default_resources() -> String {
+    "src/main/resources".to_string()
+}
+
+fn default_test_resources() -> String {
+    "src/test/resources".to_string()
+}
+
 fn default_python_source() -> String {
     "src".to_string()
 }
@@ -215,6 +580,10 @@ fn default_java_target() -> String {
     "17".to_string()
 }
 
+fn default_parallel_downloads() -> usize {
+    8
+}
+
 // ── Implementación ───────────────────────────────────────────────────────────
 
 impl ForgeConfig {
@@ -286,6 +655,30 @@ impl ForgeConfig {
             }
         }
 
+        // Proyectos mixtos: solo Kotlin como lenguaje principal con fuentes Java
+        for lang in &self.project.langs {
+            if !matches!(lang.as_str(), "java" | "kotlin" | "python") {
+                return Err(ForgeError::UnsupportedLanguage { lang: lang.clone() }.into());
+            }
+        }
+        let mixed = self.project.langs.iter().any(|lang| *lang != self.project.lang);
+        let jvm_mix = self.project.lang == "kotlin"
+            && self.project.langs.iter().all(|lang| lang == "kotlin" || lang == "java");
+        if mixed && !jvm_mix {
+            return Err(ForgeError::UnsupportedLanguageMix {
+                langs: format!("lang = \"{}\", langs = {:?}", self.project.lang, self.project.langs),
+            }
+            .into());
+        }
+
+        // KSP analiza los fuentes con la versión de Kotlin del proyecto
+        if self.kotlin.as_ref().is_some_and(|k| k.ksp.is_some()) && self.kotlin_version().is_none() {
+            return Err(ForgeError::ConfigMissingField {
+                field: "kotlin.version".to_string(),
+            }
+            .into());
+        }
+
         // Verificar coherencia: si lang=java, debe existir [java]
         if self.project.lang == "java" && self.java.is_none() {
             tracing::warn!("Lenguaje 'java' seleccionado pero no se definió [java] en forge.toml. Usando valores por defecto.");
@@ -340,6 +733,137 @@ impl ForgeConfig {
         }
     }
 
+    /// Devuelve el directorio de recursos (`test`: el de tests). `None` si el lenguaje
+    /// no tiene recursos separados del código (Python).
+    pub fn resources_dir(&self, test: bool) -> Option<String> {
+        let (main, tests) = match self.project.lang.as_str() {
+            "java" => self
+                .java
+                .as_ref()
+                .map(|j| (j.resources.clone(), j.test_resources.clone())),
+            "kotlin" => self
+                .kotlin
+                .as_ref()
+                .map(|k| (k.resources.clone(), k.test_resources.clone())),
+            _ => return None,
+        }
+        .unwrap_or_else(|| (default_resources(), default_test_resources()));
+        Some(if test { tests } else { main })
+    }
+
+    /// Versión de Kotlin de `[kotlin] version`, solo en proyectos Kotlin.
+    pub fn kotlin_version(&self) -> Option<&str> {
+        if self.project.lang != "kotlin" {
+            return None;
+        }
+        self.kotlin.as_ref().and_then(|k| k.version.as_deref())
+    }
+
+    /// Dependencias Maven a resolver: `[dependencies]` (o `[test-dependencies]`) más
+    /// kotlin-stdlib (o kotlin-test) con la versión de `[kotlin] version`, salvo que
+    /// ya estén declaradas.
+    pub fn maven_dependencies(&self, test: bool) -> HashMap<String, String> {
+        let mut dependencies = if test {
+            self.test_dependencies.clone()
+        } else {
+            self.dependencies.clone()
+        };
+        if let Some(version) = self.kotlin_version() {
+            let artifact = if test { "kotlin-test" } else { "kotlin-stdlib" };
+            dependencies
+                .entry(format!("{}:{}", KOTLIN_GROUP, artifact))
+                .or_insert_with(|| version.to_string());
+        }
+        dependencies
+    }
+
+    /// Plugins del compilador de Kotlin a resolver (vacío fuera de proyectos Kotlin).
+    pub fn kotlin_compiler_plugins(&self) -> HashMap<String, String> {
+        match &self.kotlin {
+            Some(kotlin) if self.project.lang == "kotlin" => kotlin.compiler_plugins.clone(),
+            _ => HashMap::new(),
+        }
+    }
+
+    /// Configuración de KSP (solo en proyectos Kotlin).
+    pub fn ksp(&self) -> Option<&KspConfig> {
+        if self.project.lang != "kotlin" {
+            return None;
+        }
+        self.kotlin.as_ref().and_then(|k| k.ksp.as_ref())
+    }
+
+    /// ¿Es un proyecto Kotlin con fuentes Java (`langs = ["kotlin", "java"]`)?
+    pub fn has_java_sources(&self) -> bool {
+        self.project.lang == "kotlin" && self.project.langs.iter().any(|lang| lang == "java")
+    }
+
+    /// Directorio de fuentes Java (o de tests Java) según [java], también en proyectos mixtos.
+    pub fn java_source_dir(&self, test: bool) -> String {
+        match (&self.java, test) {
+            (Some(java), false) => java.source.clone(),
+            (Some(java), true) => java.test_source.clone(),
+            (None, false) => default_java_source(),
+            (None, true) => default_java_test_source(),
+        }
+    }
+
+    /// Opciones del compilador del lenguaje del proyecto (vacías para Python).
+    pub fn compiler_options(&self) -> CompilerOptions {
+        match self.project.lang.as_str() {
+            "java" => self.java.as_ref().map(|j| j.compiler.clone()).unwrap_or_default(),
+            "kotlin" => self.kotlin.as_ref().map(|k| k.compiler.clone()).unwrap_or_default(),
+            _ => CompilerOptions::default(),
+        }
+    }
+
+    /// Huella de todo lo que cambia la salida de `forge build` además de los fuentes:
+    /// opciones del compilador, procesadores de anotaciones, versión de Kotlin (y
+    /// compilador embebido), plugins del compilador, KSP y, en proyectos mixtos,
+    /// las opciones de [java]. Si cambia, la caché local ya no sirve.
+    pub fn build_fingerprint(&self) -> String {
+        let mut fingerprint = self.compiler_options().fingerprint();
+        if !self.annotation_processors.is_empty() {
+            let mut processors: Vec<_> = self.annotation_processors.iter().collect();
+            processors.sort();
+            fingerprint.push_str(&format!(";processors={:?}", processors));
+        }
+        if let Some(version) = self.kotlin_version() {
+            let embedded = self.kotlin.as_ref().is_some_and(|k| k.embedded_compiler);
+            fingerprint.push_str(&format!(";kotlin={} embedded={}", version, embedded));
+        }
+        let mut plugins: Vec<_> = self.kotlin_compiler_plugins().into_iter().collect();
+        let options = match &self.kotlin {
+            Some(kotlin) if self.project.lang == "kotlin" => kotlin.plugin_options.clone(),
+            _ => Vec::new(),
+        };
+        if !plugins.is_empty() || !options.is_empty() {
+            plugins.sort();
+            fingerprint.push_str(&format!(";kotlin-plugins={:?} options={:?}", plugins, options));
+        }
+        if let Some(ksp) = self.ksp() {
+            let mut processors: Vec<_> = ksp.processors.iter().collect();
+            processors.sort();
+            let mut options: Vec<_> = ksp.options.iter().collect();
+            options.sort();
+            fingerprint.push_str(&format!(";ksp={} {:?} {:?}", ksp.version, processors, options));
+        }
+        if self.has_java_sources() {
+            let java_options = self.java.as_ref().map(|j| (j.target.as_str(), j.compiler.fingerprint()));
+            fingerprint.push_str(&format!(";java={:?}", java_options));
+        }
+        fingerprint
+    }
+
+    /// ¿Se reemplazan los `${...}` en los recursos?
+    pub fn filter_resources(&self) -> bool {
+        match self.project.lang.as_str() {
+            "java" => self.java.as_ref().map(|j| j.filter_resources).unwrap_or(false),
+            "kotlin" => self.kotlin.as_ref().map(|k| k.filter_resources).unwrap_or(false),
+            _ => false,
+        }
+    }
+
     /// Devuelve la clase/script principal.
     pub fn main_entry(&self) -> Option<String> {
         match self.project.lang.as_str() {
@@ -375,6 +899,9 @@ main-class = "com.ejemplo.Main"
 
 [test-dependencies]
 "org.junit.jupiter:junit-jupiter-api" = "5.10.1"
+
+[annotation-processors]
+"org.projectlombok:lombok" = "1.18.34"
 "#;
 
         let config: ForgeConfig = toml::from_str(toml_str).unwrap();
@@ -388,6 +915,7 @@ main-class = "com.ejemplo.Main"
         );
         assert!(config.dependencies.contains_key("com.google.guava:guava"));
         assert!(config.test_dependencies.contains_key("org.junit.jupiter:junit-jupiter-api"));
+        assert_eq!(config.annotation_processors["org.projectlombok:lombok"], "1.18.34");
     }
 
     #[test]
@@ -421,4 +949,240 @@ lang = "go"
         let config: ForgeConfig = toml::from_str(toml_str).unwrap();
         assert!(config.validate().is_err());
     }
+
+    #[test]
+    fn test_mixed_languages() {
+        let parse = |project: &str| -> ForgeConfig { toml::from_str(&format!("[project]\nname = \"mix\"\n{}", project)).unwrap() };
+
+        let kotlin = parse("lang = \"kotlin\"\nlangs = [\"kotlin\", \"java\"]");
+        assert!(kotlin.validate().is_ok());
+        assert!(kotlin.has_java_sources());
+        assert_eq!(kotlin.java_source_dir(true), "src/test/java");
+
+        assert!(parse("lang = \"java\"\nlangs = [\"java\", \"kotlin\"]").validate().is_err());
+        assert!(parse("lang = \"kotlin\"\nlangs = [\"python\"]").validate().is_err());
+        assert!(!parse("lang = \"java\"\nlangs = [\"java\"]").has_java_sources());
+    }
+
+    #[test]
+    fn test_parse_repositories() {
+        let toml_str = r#"
+[project]
+name = "con-nexus"
+
+[[repositories]]
+name = "nexus"
+url = "https://nexus.ejemplo.com/repository/maven-public"
+username = "${NEXUS_USER}"
+
+[[repositories]]
+name = "local"
+url = "file:///opt/maven-repo"
+"#;
+
+        let config: ForgeConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.repositories.len(), 2);
+        assert_eq!(config.repositories[0].name, "nexus");
+        assert_eq!(config.repositories[0].username.as_deref(), Some("${NEXUS_USER}"));
+        assert!(config.repositories[0].password.is_none());
+        assert_eq!(config.repositories[1].url, "file:///opt/maven-repo");
+        assert_eq!(config.resolver.checksum, ChecksumPolicy::Warn);
+        assert_eq!(config.resolver.parallel_downloads, 8);
+        assert_eq!(config.resolver.update_policy, UpdatePolicy::Daily);
+    }
+
+    #[test]
+    fn test_parse_update_policy() {
+        let toml_str = r#"
+[project]
+name = "snapshots"
+
+[resolver]
+update-policy = "interval:30"
+"#;
+
+        let config: ForgeConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.resolver.update_policy, UpdatePolicy::Interval(30));
+        assert!(config.resolver.update_policy.is_stale(std::time::Duration::from_secs(31 * 60)));
+        assert!(!config.resolver.update_policy.is_stale(std::time::Duration::from_secs(60)));
+
+        let invalid = "[project]\nname = \"x\"\n[resolver]\nupdate-policy = \"weekly\"\n";
+        assert!(toml::from_str::<ForgeConfig>(invalid).is_err());
+    }
+
+    #[test]
+    fn test_parse_compiler_options() {
+        let toml_str = r#"
+[project]
+name = "strict"
+lang = "java"
+
+[java]
+compiler-args = ["-Xlint:all", "-parameters"]
+encoding = "UTF-8"
+warnings-as-errors = true
+add-opens = ["java.base/java.lang=ALL-UNNAMED"]
+"#;
+
+        let config: ForgeConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.java.as_ref().unwrap().add_opens, vec!["java.base/java.lang=ALL-UNNAMED"]);
+        let options = config.compiler_options();
+        assert_eq!(options.compiler_args, vec!["-Xlint:all", "-parameters"]);
+        assert_eq!(options.encoding.as_deref(), Some("UTF-8"));
+        assert!(options.warnings_as_errors);
+        assert!(!options.preview);
+        assert_ne!(options.fingerprint(), CompilerOptions::default().fingerprint());
+    }
+
+    #[test]
+    fn test_kotlin_maven_dependencies() {
+        let toml_str = r#"
+[project]
+name = "kt"
+lang = "kotlin"
+
+[kotlin]
+version = "2.1.0"
+embedded-compiler = true
+
+[test-dependencies]
+"org.jetbrains.kotlin:kotlin-test" = "2.0.0"
+"#;
+
+        let config: ForgeConfig = toml::from_str(toml_str).unwrap();
+        assert!(config.kotlin.as_ref().unwrap().embedded_compiler);
+        assert_eq!(config.kotlin_version(), Some("2.1.0"));
+        assert_eq!(
+            config.maven_dependencies(false).get("org.jetbrains.kotlin:kotlin-stdlib").map(String::as_str),
+            Some("2.1.0")
+        );
+        // Lo declarado en forge.toml manda sobre la versión de [kotlin]
+        assert_eq!(
+            config.maven_dependencies(true).get("org.jetbrains.kotlin:kotlin-test").map(String::as_str),
+            Some("2.0.0")
+        );
+    }
+
+    #[test]
+    fn test_parse_kotlin_plugins_and_ksp() {
+        let toml_str = r#"
+[project]
+name = "kt"
+lang = "kotlin"
+
+[kotlin]
+version = "2.1.0"
+compiler-plugins = { "org.jetbrains.kotlin:kotlin-allopen-compiler-plugin-embeddable" = "2.1.0" }
+plugin-options = ["org.jetbrains.kotlin.allopen:annotation=com.acme.Open"]
+
+[kotlin.ksp]
+version = "2.1.0-1.0.29"
+processors = { "com.squareup.moshi:moshi-kotlin-codegen" = "1.15.1" }
+options = { "moshi.generated" = "javax.annotation.Generated" }
+"#;
+
+        let config: ForgeConfig = toml::from_str(toml_str).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.kotlin_compiler_plugins().len(), 1);
+        let ksp = config.ksp().unwrap();
+        assert_eq!(ksp.version, "2.1.0-1.0.29");
+        assert_eq!(ksp.options["moshi.generated"], "javax.annotation.Generated");
+
+        // KSP necesita [kotlin] version
+        let without_version: ForgeConfig = toml::from_str(&toml_str.replace("version = \"2.1.0\"\n", "")).unwrap();
+        assert!(without_version.validate().is_err());
+    }
+
+    #[test]
+    fn test_build_fingerprint_tracks_compiler_inputs() {
+        let base = r#"
+[project]
+name = "kt"
+lang = "kotlin"
+langs = ["kotlin", "java"]
+
+[annotation-processors]
+"com.google.dagger:dagger-compiler" = "2.52"
+"org.mapstruct:mapstruct-processor" = "1.6.2"
+
+[kotlin]
+version = "2.1.0"
+compiler-args = ["-Xjsr305=strict"]
+compiler-plugins = { "org.jetbrains.kotlin:kotlin-allopen-compiler-plugin-embeddable" = "2.1.0" }
+plugin-options = ["org.jetbrains.kotlin.allopen:annotation=com.acme.Open"]
+
+[kotlin.ksp]
+version = "2.1.0-1.0.29"
+processors = { "com.squareup.moshi:moshi-kotlin-codegen" = "1.15.1" }
+options = { "moshi.generated" = "javax.annotation.Generated" }
+
+[java]
+target = "17"
+"#;
+        let fingerprint = |toml_str: &str| toml::from_str::<ForgeConfig>(toml_str).unwrap().build_fingerprint();
+        let original = fingerprint(base);
+
+        // Mismo forge.toml, misma huella (sin depender del orden de los HashMap)
+        assert_eq!(fingerprint(base), original);
+
+        for (from, to) in [
+            ("-Xjsr305=strict", "-Xjsr305=warn"),
+            ("dagger-compiler\" = \"2.52", "dagger-compiler\" = \"2.53"),
+            ("version = \"2.1.0\"\ncompiler-args", "version = \"2.1.20\"\ncompiler-args"),
+            ("[kotlin]\n", "[kotlin]\nembedded-compiler = true\n"),
+            ("plugin-embeddable\" = \"2.1.0", "plugin-embeddable\" = \"2.1.20"),
+            ("annotation=com.acme.Open", "annotation=com.acme.Abierta"),
+            ("version = \"2.1.0-1.0.29\"", "version = \"2.1.0-1.0.28\""),
+            ("moshi-kotlin-codegen\" = \"1.15.1", "moshi-kotlin-codegen\" = \"1.15.2"),
+            ("javax.annotation.Generated", "javax.annotation.processing.Generated"),
+            ("target = \"17\"", "target = \"21\""),
+            ("target = \"17\"", "target = \"17\"\nencoding = \"UTF-8\""),
+        ] {
+            assert!(base.contains(from), "{}", from);
+            assert_ne!(fingerprint(&base.replacen(from, to, 1)), original, "{} -> {}", from, to);
+        }
+
+        // Sin fuentes Java, [java] no afecta a un proyecto Kotlin
+        let kotlin_only = base.replace("langs = [\"kotlin\", \"java\"]\n", "");
+        assert_eq!(
+            fingerprint(&kotlin_only),
+            fingerprint(&kotlin_only.replace("target = \"17\"", "target = \"21\""))
+        );
+    }
+
+    #[test]
+    fn test_parse_package_config() {
+        let toml_str = r#"
+[project]
+name = "uber"
+
+[package]
+mode = "fat-jar"
+multi-release = true
+jvm-options = ["-Xmx256m"]
+
+[[package.relocations]]
+pattern = "com.google.gson"
+shaded-pattern = "uber.shaded.gson"
+
+[package.image]
+add-modules = ["jdk.crypto.ec"]
+
+[package.installer]
+type = "deb"
+vendor = "ACME"
+"#;
+
+        let config: ForgeConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.package.mode, PackageMode::FatJar);
+        assert_eq!(config.package.relocations[0].shaded_pattern, "uber.shaded.gson");
+        assert!(config.package.multi_release);
+        assert_eq!(config.package.jvm_options, vec!["-Xmx256m"]);
+        assert_eq!(config.package.image.add_modules, vec!["jdk.crypto.ec"]);
+        assert_eq!(config.package.installer.installer_type.as_deref(), Some("deb"));
+
+        let default: ForgeConfig = toml::from_str("[project]\nname = \"x\"\n").unwrap();
+        assert_eq!(default.package.mode, PackageMode::Jar);
+        assert!(!default.package.multi_release);
+    }
 }
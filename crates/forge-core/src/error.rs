@@ -25,6 +25,12 @@ pub enum ForgeError {
     #[error("Lenguaje no soportado: '{lang}'. Usa: java, kotlin, python")]
     UnsupportedLanguage { lang: String },
 
+    #[error("Combinación de lenguajes no soportada: {langs}")]
+    UnsupportedLanguageMix { langs: String },
+
+    #[error("Variable de entorno '{var}' no definida (referenciada como '${{{var}}}')")]
+    EnvVarNotSet { var: String },
+
     // ── Grafo de Tareas (DAG) ────────────────────────────────────────────
     #[error("Dependencia circular detectada: {cycle}")]
     CyclicDependency { cycle: String },
@@ -49,6 +55,19 @@ pub enum ForgeError {
     #[error("Error al descargar '{url}': {message}")]
     DownloadError { url: String, message: String },
 
+    #[error("Checksum inválido para '{artifact}': esperado {expected}, obtenido {actual}")]
+    ChecksumMismatch {
+        artifact: String,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("Modo offline: '{artifact}' no está en la caché local")]
+    OfflineArtifactMissing { artifact: String },
+
+    #[error("forge.lock no está al día con la resolución actual:\n{changes}")]
+    LockfileOutdated { changes: String },
+
     // ── Sistema de Archivos ──────────────────────────────────────────────
     #[error("Error de E/S en '{path}': {message}")]
     IoError { path: PathBuf, message: String },
@@ -78,6 +97,12 @@ impl ForgeError {
             Self::UnsupportedLanguage { .. } => {
                 "💡 FORGE soporta: java, kotlin, python. Verifica el campo 'lang' en [project]"
             }
+            Self::UnsupportedLanguageMix { .. } => {
+                "💡 Solo se pueden mezclar Kotlin y Java: usa lang = \"kotlin\" y langs = [\"kotlin\", \"java\"] en [project]"
+            }
+            Self::EnvVarNotSet { .. } => {
+                "💡 Exporta la variable antes de ejecutar forge o revisa las referencias ${VAR} en forge.toml, ~/.forge/settings.toml y ~/.forge/credentials.toml"
+            }
             Self::CyclicDependency { .. } => {
                 "💡 Revisa las secciones [tasks.*.depends-on] en tu forge.toml para romper el ciclo"
             }
@@ -105,6 +130,15 @@ impl ForgeError {
             Self::DownloadError { .. } => {
                 "💡 Verifica tu conexión a internet y que la dependencia exista en Maven Central / PyPI"
             }
+            Self::ChecksumMismatch { .. } => {
+                "💡 El artefacto descargado está corrupto o fue alterado. Reintenta con 'forge deps --verify' o revisa [resolver] checksum en forge.toml"
+            }
+            Self::OfflineArtifactMissing { .. } => {
+                "💡 Ejecuta 'forge deps' con conexión (sin --offline ni FORGE_OFFLINE) para poblar ~/.forge/repository antes de trabajar sin red"
+            }
+            Self::LockfileOutdated { .. } => {
+                "💡 Ejecuta 'forge deps' sin --locked (ni FORGE_LOCKED) para regenerar forge.lock y súbelo al repositorio"
+            }
             Self::IoError { .. } => {
                 "💡 Verifica permisos de escritura en el directorio del proyecto y espacio disponible en disco"
             }
@@ -9,4 +9,5 @@ pub mod config;
 pub mod dag;
 pub mod error;
 pub mod executor;
+pub mod settings;
 pub mod telemetry;
@@ -0,0 +1,313 @@
+// =============================================================================
+// 🔥 FORGE — Motor Core: Configuración de Usuario (~/.forge)
+// =============================================================================
+// Ajustes globales del desarrollador que no pertenecen a ningún proyecto:
+// mirrors de repositorios, almacén de artefactos y daemon de compilación
+// (settings.toml) y credenciales (credentials.toml).
+// =============================================================================
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::error::{ForgeError, ForgeResult};
+
+/// Configuración global del usuario, mapeada desde ~/.forge/settings.toml.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct UserSettings {
+    /// Mirrors que reemplazan la URL de uno o varios repositorios
+    #[serde(default)]
+    pub mirrors: Vec<MirrorConfig>,
+
+    /// Almacén global de artefactos compartido entre proyectos
+    #[serde(default)]
+    pub store: StoreSettings,
+
+    /// Daemon de compilación (javac/kotlinc en una JVM reutilizada entre builds)
+    #[serde(default)]
+    pub daemon: DaemonSettings,
+}
+
+/// Daemon de compilación compartido por todos los proyectos del usuario.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonSettings {
+    /// Compilar en el daemon (default: true). `--no-daemon` lo desactiva por comando
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Minutos sin compilar tras los que el daemon termina (default: 180)
+    #[serde(default = "default_idle_timeout", rename = "idle-timeout")]
+    pub idle_timeout: u64,
+}
+
+impl Default for DaemonSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            idle_timeout: default_idle_timeout(),
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_idle_timeout() -> u64 {
+    180
+}
+
+/// Ubicación del almacén global de artefactos (layout Maven).
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct StoreSettings {
+    /// Ruta del almacén (default: ~/.forge/repository)
+    pub path: Option<String>,
+
+    /// Reutilizar ~/.m2/repository como fuente de solo lectura
+    #[serde(default, rename = "use-m2")]
+    pub use_m2: bool,
+}
+
+/// Mirror de un repositorio Maven (ej: un Nexus corporativo delante de Central).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MirrorConfig {
+    /// Identificador del mirror (también clave en credentials.toml)
+    pub name: String,
+
+    /// Repositorio reemplazado: nombre exacto o "*" para todos
+    #[serde(rename = "mirror-of")]
+    pub mirror_of: String,
+
+    /// URL del mirror (http(s):// o file://)
+    pub url: String,
+}
+
+/// Usuario y contraseña para un repositorio autenticado.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Credentials {
+    pub username: String,
+    pub password: String,
+}
+
+impl UserSettings {
+    /// Directorio global de FORGE (~/.forge). `FORGE_HOME` permite sobrescribirlo.
+    pub fn forge_home() -> PathBuf {
+        if let Ok(home) = std::env::var("FORGE_HOME") {
+            return PathBuf::from(home);
+        }
+        dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(".forge")
+    }
+
+    /// Raíz del almacén global de artefactos.
+    pub fn store_dir(&self) -> ForgeResult<PathBuf> {
+        Ok(match &self.store.path {
+            Some(path) => PathBuf::from(expand_env(path)?),
+            None => Self::forge_home().join("repository"),
+        })
+    }
+
+    /// Repositorio local de Maven (~/.m2/repository) si está habilitado y existe.
+    pub fn m2_dir(&self) -> Option<PathBuf> {
+        if !self.store.use_m2 {
+            return None;
+        }
+        dirs::home_dir()
+            .map(|home| home.join(".m2").join("repository"))
+            .filter(|dir| dir.exists())
+    }
+
+    /// Carga ~/.forge/settings.toml. Si no existe, devuelve la configuración por defecto.
+    pub fn load() -> ForgeResult<Self> {
+        Self::load_from(&Self::forge_home().join("settings.toml"))
+    }
+
+    /// Carga la configuración de usuario desde una ruta específica.
+    pub fn load_from(path: &Path) -> ForgeResult<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(path).map_err(|e| ForgeError::IoError {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })?;
+
+        toml::from_str(&content).map_err(|e| {
+            ForgeError::ConfigParseError {
+                message: format!("{}: {}", path.display(), e),
+            }
+            .into()
+        })
+    }
+
+    /// Devuelve el mirror que aplica a un repositorio, si existe.
+    /// Un mirror con nombre exacto tiene prioridad sobre uno comodín ("*").
+    pub fn mirror_for(&self, repository: &str) -> Option<&MirrorConfig> {
+        self.mirrors
+            .iter()
+            .find(|m| m.mirror_of == repository)
+            .or_else(|| self.mirrors.iter().find(|m| m.mirror_of == "*"))
+    }
+
+    /// Busca credenciales para un repositorio en ~/.forge/credentials.toml.
+    ///
+    /// ```toml
+    /// [nexus]
+    /// username = "ci-bot"
+    /// password = "${NEXUS_PASSWORD}"
+    /// ```
+    pub fn credentials_for(repository: &str) -> ForgeResult<Option<Credentials>> {
+        let path = Self::forge_home().join("credentials.toml");
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(&path).map_err(|e| ForgeError::IoError {
+            path: path.clone(),
+            message: e.to_string(),
+        })?;
+
+        let mut all: HashMap<String, Credentials> =
+            toml::from_str(&content).map_err(|e| ForgeError::ConfigParseError {
+                message: format!("{}: {}", path.display(), e),
+            })?;
+
+        all.remove(repository)
+            .map(|c| {
+                Ok(Credentials {
+                    username: expand_env(&c.username)?,
+                    password: expand_env(&c.password)?,
+                })
+            })
+            .transpose()
+    }
+}
+
+/// Variable de entorno equivalente a `--offline`.
+pub const OFFLINE_ENV: &str = "FORGE_OFFLINE";
+
+/// ¿Está activo el modo offline (`--offline` o `FORGE_OFFLINE`)?
+/// En este modo ningún componente accede a la red: solo se usan las cachés locales.
+pub fn offline_mode() -> bool {
+    env_flag(OFFLINE_ENV)
+}
+
+/// Variable de entorno equivalente a `--no-daemon`.
+pub const NO_DAEMON_ENV: &str = "FORGE_NO_DAEMON";
+
+/// Interpreta una variable de entorno booleana (`1`, `true`, ...). Vacía, `0` o `false` = apagada.
+pub fn env_flag(name: &str) -> bool {
+    std::env::var(name)
+        .map(|v| !v.is_empty() && v != "0" && !v.eq_ignore_ascii_case("false"))
+        .unwrap_or(false)
+}
+
+/// Expande referencias `${VAR}` a variables de entorno. Una variable sin definir es un
+/// error: una contraseña vacía solo se notaría después como un 401 del repositorio.
+pub fn expand_env(value: &str) -> ForgeResult<String> {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        match rest[start + 2..].find('}') {
+            Some(end) => {
+                let var = &rest[start + 2..start + 2 + end];
+                let expanded = std::env::var(var).map_err(|_| ForgeError::EnvVarNotSet { var: var.to_string() })?;
+                result.push_str(&expanded);
+                rest = &rest[start + 2 + end + 1..];
+            }
+            None => {
+                result.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+
+    result.push_str(rest);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_env() {
+        std::env::set_var("FORGE_TEST_EXPAND_USER", "ci-bot");
+        assert_eq!(expand_env("${FORGE_TEST_EXPAND_USER}@corp").unwrap(), "ci-bot@corp");
+        assert_eq!(expand_env("sin-variables").unwrap(), "sin-variables");
+        assert_eq!(expand_env("abierta ${FORGE_TEST_EXPAND_USER").unwrap(), "abierta ${FORGE_TEST_EXPAND_USER");
+
+        let err = expand_env("${FORGE_TEST_EXPAND_MISSING}").unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ForgeError>(),
+            Some(ForgeError::EnvVarNotSet { var }) if var == "FORGE_TEST_EXPAND_MISSING"
+        ));
+        std::env::remove_var("FORGE_TEST_EXPAND_USER");
+    }
+
+    #[test]
+    fn test_credentials_for_reads_forge_home() {
+        let home = std::env::temp_dir().join("forge_test_settings_credentials");
+        let _ = std::fs::remove_dir_all(&home);
+        std::fs::create_dir_all(&home).unwrap();
+        // Es el único test del crate que depende de FORGE_HOME
+        std::env::set_var("FORGE_HOME", &home);
+        std::env::set_var("FORGE_TEST_CREDENTIALS_PASSWORD", "s3cr3t");
+
+        let missing_file = UserSettings::credentials_for("nexus");
+
+        std::fs::write(
+            home.join("credentials.toml"),
+            r#"[nexus]
+username = "ci-bot"
+password = "${FORGE_TEST_CREDENTIALS_PASSWORD}"
+
+[roto]
+username = "ci-bot"
+password = "${FORGE_TEST_CREDENTIALS_MISSING}"
+"#,
+        )
+        .unwrap();
+        let nexus = UserSettings::credentials_for("nexus");
+        let unknown = UserSettings::credentials_for("otro");
+        let broken = UserSettings::credentials_for("roto");
+
+        std::env::remove_var("FORGE_HOME");
+        std::env::remove_var("FORGE_TEST_CREDENTIALS_PASSWORD");
+        std::fs::remove_dir_all(&home).unwrap();
+
+        assert!(missing_file.unwrap().is_none());
+        let nexus = nexus.unwrap().unwrap();
+        assert_eq!((nexus.username.as_str(), nexus.password.as_str()), ("ci-bot", "s3cr3t"));
+        assert!(unknown.unwrap().is_none());
+        assert!(matches!(
+            broken.unwrap_err().downcast_ref::<ForgeError>(),
+            Some(ForgeError::EnvVarNotSet { var }) if var == "FORGE_TEST_CREDENTIALS_MISSING"
+        ));
+    }
+
+    #[test]
+    fn test_mirror_for_prefers_exact_name_over_wildcard() {
+        let settings: UserSettings = toml::from_str(
+            r#"[[mirrors]]
+name = "corp"
+mirror-of = "*"
+url = "https://nexus.corp/maven-public"
+
+[[mirrors]]
+name = "interno"
+mirror-of = "snapshots"
+url = "https://nexus.corp/maven-snapshots"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(settings.mirror_for("snapshots").unwrap().name, "interno");
+        assert_eq!(settings.mirror_for("central").unwrap().name, "corp");
+        assert!(UserSettings::default().mirror_for("central").is_none());
+    }
+}
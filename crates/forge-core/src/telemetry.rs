@@ -21,6 +21,20 @@ pub enum ForgeEvent {
         level: String,
         text: String,
     },
+    DependencyResolved {
+        name: String,
+        version: String,
+        cached: bool,
+        time_ms: u64,
+    },
+    TestFinished {
+        suite: String,
+        class_name: String,
+        name: String,
+        status: String,
+        time_ms: u64,
+        message: Option<String>,
+    },
 }
 
 #[derive(Clone)]
@@ -45,6 +59,12 @@ impl EventBus {
     }
 }
 
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 use std::sync::OnceLock;
 
 // Inicialización de un bus global usando OnceLock (Standard Library >= 1.70)
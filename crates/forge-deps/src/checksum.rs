@@ -0,0 +1,161 @@
+// =============================================================================
+// 🔥 FORGE — Resolución de Dependencias: Verificación de Checksums
+// =============================================================================
+// Los repositorios Maven publican archivos `.sha256` / `.sha1` junto a cada
+// artefacto. FORGE los descarga, compara el hash de lo recibido y guarda el
+// checksum verificado en el almacén para poder re-verificar más tarde.
+// =============================================================================
+
+use std::path::Path;
+
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+use cyrce_forge_core::error::{ForgeError, ForgeResult};
+
+/// Algoritmos soportados, en orden de preferencia.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumAlgorithm {
+    Sha256,
+    Sha1,
+}
+
+/// Todos los algoritmos, del más fuerte al más débil.
+pub const ALGORITHMS: [ChecksumAlgorithm; 2] = [ChecksumAlgorithm::Sha256, ChecksumAlgorithm::Sha1];
+
+impl ChecksumAlgorithm {
+    /// Extensión del archivo sidecar (sin punto).
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Sha256 => "sha256",
+            Self::Sha1 => "sha1",
+        }
+    }
+
+    /// Hash hexadecimal en minúsculas del contenido.
+    pub fn digest(&self, bytes: &[u8]) -> String {
+        match self {
+            Self::Sha256 => format!("{:x}", Sha256::digest(bytes)),
+            Self::Sha1 => format!("{:x}", Sha1::digest(bytes)),
+        }
+    }
+}
+
+/// Resultado de verificar un archivo ya presente en disco.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verification {
+    /// El hash coincide con el sidecar
+    Valid,
+    /// No hay sidecar con el que comparar
+    Missing,
+    /// El hash no coincide: el archivo está corrupto
+    Mismatch { expected: String, actual: String },
+}
+
+/// Extrae el hash de un sidecar. Algunos repositorios agregan el nombre del
+/// archivo después del hash (`<hash>  archivo.jar`).
+pub fn parse_sidecar(content: &str) -> Option<String> {
+    content
+        .split_whitespace()
+        .next()
+        .filter(|hash| hash.chars().all(|c| c.is_ascii_hexdigit()))
+        .map(|hash| hash.to_ascii_lowercase())
+}
+
+/// Verifica un archivo local contra el primer sidecar disponible a su lado.
+pub fn verify_file(path: &Path) -> ForgeResult<Verification> {
+    for algorithm in ALGORITHMS {
+        let sidecar = path.with_file_name(format!(
+            "{}.{}",
+            path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            algorithm.extension()
+        ));
+
+        let Ok(content) = std::fs::read_to_string(&sidecar) else {
+            continue;
+        };
+        let Some(expected) = parse_sidecar(&content) else {
+            continue;
+        };
+
+        let bytes = std::fs::read(path).map_err(|e| ForgeError::IoError {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })?;
+        let actual = algorithm.digest(&bytes);
+
+        return Ok(if actual == expected {
+            Verification::Valid
+        } else {
+            Verification::Mismatch { expected, actual }
+        });
+    }
+
+    Ok(Verification::Missing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sidecar_formats() {
+        let hash = "aaf4c61ddcc5e8a2dabede0f3b482cd9aea9434d";
+        assert_eq!(parse_sidecar(hash), Some(hash.to_string()));
+        assert_eq!(parse_sidecar(&format!("{}\n", hash)), Some(hash.to_string()));
+        assert_eq!(parse_sidecar(&format!("{}  hello-1.0.jar\n", hash)), Some(hash.to_string()));
+        assert_eq!(parse_sidecar(&hash.to_ascii_uppercase()), Some(hash.to_string()));
+        assert_eq!(parse_sidecar("<html>404</html>"), None);
+        assert_eq!(parse_sidecar(""), None);
+    }
+
+    #[test]
+    fn test_digest_known_values() {
+        assert_eq!(
+            ChecksumAlgorithm::Sha256.digest(b"hello"),
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+        assert_eq!(
+            ChecksumAlgorithm::Sha1.digest(b"hello"),
+            "aaf4c61ddcc5e8a2dabede0f3b482cd9aea9434d"
+        );
+    }
+
+    #[test]
+    fn test_verify_file_against_sidecars() {
+        let dir = std::env::temp_dir().join("forge_test_checksum_verify");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let jar = dir.join("hello-1.0.jar");
+        std::fs::write(&jar, b"hello").unwrap();
+
+        assert_eq!(verify_file(&jar).unwrap(), Verification::Missing);
+
+        // Solo .sha1, con el nombre del archivo detrás del hash
+        let sha1 = dir.join("hello-1.0.jar.sha1");
+        std::fs::write(&sha1, "aaf4c61ddcc5e8a2dabede0f3b482cd9aea9434d  hello-1.0.jar").unwrap();
+        assert_eq!(verify_file(&jar).unwrap(), Verification::Valid);
+
+        // .sha256 tiene prioridad sobre .sha1
+        std::fs::write(&sha1, "0000000000000000000000000000000000000000").unwrap();
+        std::fs::write(
+            dir.join("hello-1.0.jar.sha256"),
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824",
+        )
+        .unwrap();
+        assert_eq!(verify_file(&jar).unwrap(), Verification::Valid);
+
+        std::fs::write(&jar, b"corrupto").unwrap();
+        assert_eq!(
+            verify_file(&jar).unwrap(),
+            Verification::Mismatch {
+                expected: "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824".into(),
+                actual: ChecksumAlgorithm::Sha256.digest(b"corrupto"),
+            }
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
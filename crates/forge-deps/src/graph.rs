@@ -0,0 +1,219 @@
+// =============================================================================
+// 🔥 FORGE — Resolución de Dependencias: Grafo resuelto
+// =============================================================================
+// Registro de cada aparición de una dependencia durante la resolución Maven:
+// quién la pidió, qué versión pidió, cuál terminó en el classpath y qué
+// regla la eligió. Lo usan `forge tree` y `forge why`.
+// =============================================================================
+
+use serde::Serialize;
+
+use crate::version::{self, VersionSpec};
+
+/// Regla que decidió la versión de un nodo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Selection {
+    /// Versión exacta declarada en forge.toml
+    Declared,
+    /// Fijada por forge.lock
+    Locked,
+    /// Rango o latest/release resuelto con maven-metadata.xml
+    Range,
+    /// Transitiva: la aparición más cercana a forge.toml
+    Nearest,
+    /// Repetida: ganó otra aparición más cercana (o anterior en el mismo nivel)
+    Mediated,
+}
+
+impl Selection {
+    /// Explicación legible de la regla.
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Self::Declared => "declarada en forge.toml",
+            Self::Locked => "fijada por forge.lock",
+            Self::Range => "rango resuelto con maven-metadata.xml",
+            Self::Nearest => "la más cercana a forge.toml (nearest-wins)",
+            Self::Mediated => "omitida: ganó otra aparición más cercana",
+        }
+    }
+}
+
+/// Una aparición de un artefacto en el árbol de dependencias.
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphNode {
+    pub group: String,
+    pub artifact: String,
+
+    /// Versión pedida por el padre (o por forge.toml)
+    pub requested: String,
+
+    /// Versión que terminó en el classpath
+    pub version: String,
+
+    /// "compile" o "test"
+    pub scope: String,
+
+    /// Índice del nodo que la pidió (`None` = dependencia directa)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<usize>,
+
+    pub selection: Selection,
+
+    /// Para nodos `Mediated`: índice de la aparición que ganó
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub winner: Option<usize>,
+}
+
+impl GraphNode {
+    /// `groupId:artifactId`
+    pub fn key(&self) -> String {
+        format!("{}:{}", self.group, self.artifact)
+    }
+
+    /// ¿Otra aparición impuso una versión que no cumple lo pedido aquí? (`1.2 -> 1.5`)
+    /// Un rango, LATEST/RELEASE o SNAPSHOT resuelto a una versión concreta no es conflicto.
+    pub fn is_conflict(&self) -> bool {
+        self.winner.is_some() && !satisfies(&self.requested, &self.version)
+    }
+}
+
+/// ¿`version` cumple lo pedido? Una SNAPSHOT acepta sus versiones con timestamp.
+fn satisfies(requested: &str, version: &str) -> bool {
+    if version::is_snapshot(requested) {
+        return version.starts_with(requested.trim_end_matches("SNAPSHOT"));
+    }
+    VersionSpec::parse(requested).is_ok_and(|spec| spec.matches(version))
+}
+
+/// Grafo de la última resolución, en orden de resolución (padres antes que hijos).
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DependencyGraph {
+    pub nodes: Vec<GraphNode>,
+}
+
+impl DependencyGraph {
+    /// Agrega un nodo y devuelve su índice.
+    pub fn push(&mut self, node: GraphNode) -> usize {
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    /// Completa la versión de los nodos repetidos con la de la aparición ganadora.
+    pub fn settle(&mut self) {
+        for index in 0..self.nodes.len() {
+            if let Some(winner) = self.nodes[index].winner {
+                self.nodes[index].version = self.nodes[winner].version.clone();
+            }
+        }
+    }
+
+    /// Dependencias directas de un scope.
+    pub fn roots(&self, scope: &str) -> Vec<usize> {
+        (0..self.nodes.len())
+            .filter(|&i| self.nodes[i].parent.is_none() && self.nodes[i].scope == scope)
+            .collect()
+    }
+
+    /// Hijos directos de un nodo.
+    pub fn children(&self, index: usize) -> Vec<usize> {
+        (0..self.nodes.len())
+            .filter(|&i| self.nodes[i].parent == Some(index))
+            .collect()
+    }
+
+    /// Nodos que corresponden a `groupId:artifactId` o solo `artifactId`.
+    pub fn find(&self, name: &str) -> Vec<usize> {
+        (0..self.nodes.len())
+            .filter(|&i| {
+                let node = &self.nodes[i];
+                match name.split_once(':') {
+                    Some((group, artifact)) => node.group == group && node.artifact == artifact,
+                    None => node.artifact == name,
+                }
+            })
+            .collect()
+    }
+
+    /// Camino desde la dependencia directa hasta el nodo (ambos incluidos).
+    pub fn path_to(&self, index: usize) -> Vec<usize> {
+        let mut path = vec![index];
+        let mut current = index;
+        while let Some(parent) = self.nodes[current].parent {
+            path.push(parent);
+            current = parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(artifact: &str, requested: &str, parent: Option<usize>, selection: Selection) -> GraphNode {
+        GraphNode {
+            group: "com.acme".to_string(),
+            artifact: artifact.to_string(),
+            requested: requested.to_string(),
+            version: requested.to_string(),
+            scope: "compile".to_string(),
+            parent,
+            selection,
+            winner: None,
+        }
+    }
+
+    #[test]
+    fn test_paths_and_mediation() {
+        let mut graph = DependencyGraph::default();
+        let app = graph.push(node("app", "1.0", None, Selection::Declared));
+        let util = graph.push(node("util", "1.5", None, Selection::Declared));
+        let dup = graph.push(GraphNode {
+            winner: Some(util),
+            ..node("util", "1.2", Some(app), Selection::Mediated)
+        });
+        graph.settle();
+
+        assert_eq!(graph.roots("compile"), vec![app, util]);
+        assert_eq!(graph.children(app), vec![dup]);
+        assert_eq!(graph.find("util"), vec![util, dup]);
+        assert_eq!(graph.find("com.acme:app"), vec![app]);
+        assert_eq!(graph.path_to(dup), vec![app, dup]);
+        assert!(graph.nodes[dup].is_conflict());
+        assert_eq!(graph.nodes[dup].version, "1.5");
+    }
+
+    #[test]
+    fn test_ranges_and_snapshots_are_not_conflicts() {
+        let mut graph = DependencyGraph::default();
+        let ranged = graph.push(GraphNode {
+            version: "1.5".to_string(),
+            ..node("util", "[1.2,2.0)", None, Selection::Range)
+        });
+        let latest = graph.push(GraphNode {
+            version: "3.1".to_string(),
+            ..node("core", "LATEST", None, Selection::Range)
+        });
+        let snapshot = graph.push(GraphNode {
+            version: "2.0-20240101.120000-3".to_string(),
+            ..node("beta", "2.0-SNAPSHOT", None, Selection::Declared)
+        });
+        // Otra aparición pide un rango que la ganadora cumple, y otra uno que no
+        let compatible = graph.push(GraphNode {
+            winner: Some(ranged),
+            ..node("util", "[1.0,)", Some(latest), Selection::Mediated)
+        });
+        let outside = graph.push(GraphNode {
+            winner: Some(ranged),
+            ..node("util", "[1.0,1.4]", Some(latest), Selection::Mediated)
+        });
+        graph.settle();
+
+        for index in [ranged, latest, snapshot, compatible] {
+            assert!(!graph.nodes[index].is_conflict(), "{:?}", graph.nodes[index]);
+        }
+        assert!(graph.nodes[outside].is_conflict());
+    }
+}
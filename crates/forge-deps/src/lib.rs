@@ -2,5 +2,12 @@
 // 🔥 FORGE — Resolución de Dependencias: Punto de Entrada
 // =============================================================================
 
+pub mod checksum;
+pub mod graph;
+pub mod lockfile;
 pub mod maven;
+pub mod metadata;
 pub mod pypi;
+pub mod repository;
+pub mod store;
+pub mod version;
@@ -0,0 +1,389 @@
+// =============================================================================
+// 🔥 FORGE — Resolución de Dependencias: Lockfile (forge.lock)
+// =============================================================================
+// Registra exactamente qué eligió el resolver: coordenadas, checksum y el
+// camino por el que se llegó a cada artefacto. Con el lockfile presente, las
+// versiones transitivas y los paquetes Python con "*" quedan fijados hasta
+// que se actualicen explícitamente con `forge deps --update <nombre>`.
+// =============================================================================
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use cyrce_forge_core::error::{ForgeError, ForgeResult};
+use cyrce_forge_core::settings;
+
+/// Nombre del lockfile en la raíz del proyecto.
+pub const LOCKFILE: &str = "forge.lock";
+
+/// Variable de entorno equivalente a `--locked`.
+pub const LOCKED_ENV: &str = "FORGE_LOCKED";
+
+/// Versión del formato del lockfile.
+const LOCKFILE_VERSION: u32 = 1;
+
+/// Cabecera escrita al inicio de forge.lock.
+const HEADER: &str = "# Generado por FORGE — no editar a mano.\n# Actualízalo con 'forge deps' o 'forge deps --update <nombre>'.\n\n";
+
+/// Contenido de forge.lock.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Lockfile {
+    /// Versión del formato
+    pub version: u32,
+
+    /// Artefactos Maven resueltos (directos y transitivos)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub maven: Vec<LockedMaven>,
+
+    /// Paquetes PyPI resueltos
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pypi: Vec<LockedPypi>,
+}
+
+/// Un artefacto Maven fijado en el lockfile.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LockedMaven {
+    pub group: String,
+    pub artifact: String,
+    pub version: String,
+
+    /// "compile" o "test"
+    pub scope: String,
+
+    /// Hash del JAR (`sha256:<hex>`)
+    pub checksum: String,
+
+    /// Camino desde forge.toml hasta el artefacto (vacío = dependencia directa)
+    #[serde(default)]
+    pub path: Vec<String>,
+}
+
+/// Un paquete PyPI fijado en el lockfile.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LockedPypi {
+    pub name: String,
+    pub version: String,
+
+    /// Hash de la distribución publicada en PyPI (`sha256:<hex>`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+
+    /// Camino desde forge.toml hasta el paquete (vacío = dependencia directa)
+    #[serde(default)]
+    pub path: Vec<String>,
+}
+
+impl LockedMaven {
+    /// `groupId:artifactId`
+    pub fn key(&self) -> String {
+        format!("{}:{}", self.group, self.artifact)
+    }
+}
+
+impl Default for Lockfile {
+    fn default() -> Self {
+        Self {
+            version: LOCKFILE_VERSION,
+            maven: Vec::new(),
+            pypi: Vec::new(),
+        }
+    }
+}
+
+impl Lockfile {
+    /// Carga forge.lock del proyecto, si existe.
+    pub fn load(project_dir: &Path) -> ForgeResult<Option<Self>> {
+        let path = project_dir.join(LOCKFILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(&path).map_err(|e| ForgeError::IoError {
+            path: path.clone(),
+            message: e.to_string(),
+        })?;
+
+        toml::from_str(&content).map(Some).map_err(|e| {
+            ForgeError::ConfigParseError {
+                message: format!("{}: {}", path.display(), e),
+            }
+            .into()
+        })
+    }
+
+    /// Escribe forge.lock en el proyecto.
+    pub fn save(&self, project_dir: &Path) -> ForgeResult<()> {
+        let path = project_dir.join(LOCKFILE);
+        let body = toml::to_string(self).map_err(|e| ForgeError::ConfigParseError {
+            message: format!("No se pudo serializar {}: {}", LOCKFILE, e),
+        })?;
+
+        std::fs::write(&path, format!("{}{}", HEADER, body)).map_err(|e| {
+            ForgeError::IoError {
+                path,
+                message: e.to_string(),
+            }
+            .into()
+        })
+    }
+
+    /// Ordena las entradas para que el archivo sea estable entre ejecuciones.
+    pub fn sort(&mut self) {
+        self.maven.sort_by(|a, b| {
+            (&a.scope, &a.group, &a.artifact).cmp(&(&b.scope, &b.group, &b.artifact))
+        });
+        self.pypi.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    /// Versiones Maven fijadas para un scope (`groupId:artifactId` → versión).
+    /// Las entradas afectadas por `update` (el artefacto o cualquier cosa que
+    /// cuelgue de él) se omiten para que se resuelvan de nuevo.
+    pub fn maven_pins(&self, scope: &str, update: &[String]) -> HashMap<String, String> {
+        self.maven
+            .iter()
+            .filter(|entry| entry.scope == scope)
+            .filter(|entry| {
+                !update.iter().any(|name| {
+                    matches_maven(name, &entry.key())
+                        || entry.path.iter().any(|parent| matches_maven(name, parent))
+                })
+            })
+            .map(|entry| (entry.key(), entry.version.clone()))
+            .collect()
+    }
+
+    /// Versión fijada de un paquete PyPI, salvo que se esté actualizando.
+    pub fn pypi_pin(&self, name: &str, update: &[String]) -> Option<&str> {
+        if update.iter().any(|u| u.eq_ignore_ascii_case(name)) {
+            return None;
+        }
+        self.pypi
+            .iter()
+            .find(|entry| entry.name.eq_ignore_ascii_case(name))
+            .map(|entry| entry.version.as_str())
+    }
+
+    /// ¿Algún nombre de `--update` corresponde a una entrada del lockfile?
+    pub fn contains(&self, name: &str) -> bool {
+        self.maven.iter().any(|entry| matches_maven(name, &entry.key()))
+            || self.pypi.iter().any(|entry| entry.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Cambios legibles entre este lockfile (anterior) y `next`.
+    pub fn diff(&self, next: &Lockfile) -> Vec<String> {
+        let mut changes = Vec::new();
+
+        let index = |lock: &Lockfile| -> BTreeMap<String, (String, String)> {
+            let maven = lock.maven.iter().map(|e| {
+                (
+                    format!("{} [{}]", e.key(), e.scope),
+                    (e.version.clone(), e.checksum.clone()),
+                )
+            });
+            let pypi = lock.pypi.iter().map(|e| {
+                (
+                    format!("{} [pypi]", e.name),
+                    (e.version.clone(), e.checksum.clone().unwrap_or_default()),
+                )
+            });
+            maven.chain(pypi).collect()
+        };
+
+        let before = index(self);
+        let after = index(next);
+
+        for (key, (version, checksum)) in &before {
+            match after.get(key) {
+                None => changes.push(format!("- {} {}", key, version)),
+                Some((new_version, _)) if new_version != version => {
+                    changes.push(format!("~ {} {} → {}", key, version, new_version))
+                }
+                Some((_, new_checksum)) if new_checksum != checksum => {
+                    changes.push(format!("! {} {} (checksum distinto)", key, version))
+                }
+                _ => {}
+            }
+        }
+        for (key, (version, _)) in &after {
+            if !before.contains_key(key) {
+                changes.push(format!("+ {} {}", key, version));
+            }
+        }
+
+        changes
+    }
+
+    /// Compara la resolución nueva con el lockfile anterior y la guarda.
+    /// En modo `--locked` cualquier diferencia (o la ausencia de forge.lock) es un error.
+    /// Devuelve la cantidad de cambios escritos.
+    pub fn commit(
+        self,
+        previous: Option<&Lockfile>,
+        project_dir: &Path,
+        locked: bool,
+    ) -> ForgeResult<usize> {
+        let mut next = self;
+        next.sort();
+
+        let changes = match previous {
+            Some(previous) => previous.diff(&next),
+            None => vec![format!("+ {} (nuevo)", LOCKFILE)],
+        };
+
+        if changes.is_empty() {
+            return Ok(0);
+        }
+
+        if locked {
+            return Err(ForgeError::LockfileOutdated {
+                changes: changes
+                    .iter()
+                    .map(|c| format!("   {}", c))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            }
+            .into());
+        }
+
+        next.save(project_dir)?;
+        Ok(changes.len())
+    }
+}
+
+/// ¿Está activo el modo `--locked` (o `FORGE_LOCKED`)?
+pub fn locked_mode() -> bool {
+    settings::env_flag(LOCKED_ENV)
+}
+
+/// `--update` acepta `groupId:artifactId` o solo `artifactId`.
+/// `key` es `groupId:artifactId` o, dentro de un camino, `groupId:artifactId:versión`.
+fn matches_maven(name: &str, key: &str) -> bool {
+    let mut parts = key.split(':');
+    let group = parts.next().unwrap_or_default();
+    let artifact = parts.next().unwrap_or_default();
+
+    match name.split_once(':') {
+        Some((g, a)) => g == group && a == artifact,
+        None => name == artifact,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn maven(key: &str, version: &str, scope: &str, path: &[&str]) -> LockedMaven {
+        let (group, artifact) = key.split_once(':').unwrap();
+        LockedMaven {
+            group: group.to_string(),
+            artifact: artifact.to_string(),
+            version: version.to_string(),
+            scope: scope.to_string(),
+            checksum: format!("sha256:{}", version),
+            path: path.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    fn lock(maven: Vec<LockedMaven>) -> Lockfile {
+        Lockfile {
+            maven,
+            ..Lockfile::default()
+        }
+    }
+
+    #[test]
+    fn test_matches_maven() {
+        assert!(matches_maven("com.google.guava:guava", "com.google.guava:guava"));
+        assert!(matches_maven("guava", "com.google.guava:guava:33.0.0-jre"));
+        assert!(!matches_maven("org.acme:guava", "com.google.guava:guava"));
+        assert!(!matches_maven("failureaccess", "com.google.guava:guava"));
+    }
+
+    #[test]
+    fn test_maven_pins_update_unpins_only_that_artifact() {
+        let lock = lock(vec![
+            maven("com.google.guava:guava", "33.0.0-jre", "compile", &[]),
+            maven(
+                "com.google.guava:failureaccess",
+                "1.0.2",
+                "compile",
+                &["com.google.guava:guava:33.0.0-jre"],
+            ),
+            maven("org.slf4j:slf4j-api", "2.0.9", "compile", &[]),
+            maven("org.junit.jupiter:junit-jupiter-api", "5.10.1", "test", &[]),
+        ]);
+
+        let pins = lock.maven_pins("compile", &[]);
+        assert_eq!(pins.len(), 3);
+        assert_eq!(pins["org.slf4j:slf4j-api"], "2.0.9");
+
+        // --update guava libera guava y lo que cuelga de él, nada más
+        let pins = lock.maven_pins("compile", &["com.google.guava:guava".to_string()]);
+        assert_eq!(pins.len(), 1);
+        assert!(pins.contains_key("org.slf4j:slf4j-api"));
+
+        let pins = lock.maven_pins("test", &["guava".to_string()]);
+        assert_eq!(pins.keys().collect::<Vec<_>>(), ["org.junit.jupiter:junit-jupiter-api"]);
+    }
+
+    #[test]
+    fn test_diff_reports_added_removed_and_changed() {
+        let before = lock(vec![
+            maven("org.acme:kept", "1.0", "compile", &[]),
+            maven("org.acme:removed", "1.0", "compile", &[]),
+            maven("org.acme:bumped", "1.0", "compile", &[]),
+            maven("org.acme:tampered", "1.0", "compile", &[]),
+        ]);
+        let mut tampered = maven("org.acme:tampered", "1.0", "compile", &[]);
+        tampered.checksum = "sha256:otro".to_string();
+        let after = lock(vec![
+            maven("org.acme:kept", "1.0", "compile", &[]),
+            maven("org.acme:bumped", "2.0", "compile", &[]),
+            tampered,
+            maven("org.acme:added", "3.0", "test", &[]),
+        ]);
+
+        let mut changes = before.diff(&after);
+        changes.sort();
+        assert_eq!(
+            changes,
+            [
+                "! org.acme:tampered [compile] 1.0 (checksum distinto)",
+                "+ org.acme:added [test] 3.0",
+                "- org.acme:removed [compile] 1.0",
+                "~ org.acme:bumped [compile] 1.0 → 2.0",
+            ]
+        );
+        assert!(before.diff(&before).is_empty());
+    }
+
+    #[test]
+    fn test_commit_locked_fails_on_drift() {
+        let dir = std::env::temp_dir().join("forge_test_lockfile_commit");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let previous = lock(vec![maven("org.acme:lib", "1.0", "compile", &[])]);
+        let drifted = lock(vec![maven("org.acme:lib", "1.1", "compile", &[])]);
+
+        // --locked: sin cambios pasa, con cambios falla y no escribe nada
+        assert_eq!(previous.clone().commit(Some(&previous), &dir, true).unwrap(), 0);
+        let err = drifted.clone().commit(Some(&previous), &dir, true).unwrap_err();
+        match err.downcast_ref::<ForgeError>() {
+            Some(ForgeError::LockfileOutdated { changes }) => {
+                assert!(changes.contains("org.acme:lib [compile] 1.0 → 1.1"))
+            }
+            other => panic!("se esperaba LockfileOutdated, llegó {:?}", other),
+        }
+        assert!(drifted.clone().commit(None, &dir, true).is_err());
+        assert!(!dir.join(LOCKFILE).exists());
+
+        // Sin --locked se guarda y se vuelve a leer igual
+        assert_eq!(drifted.clone().commit(Some(&previous), &dir, false).unwrap(), 1);
+        assert_eq!(Lockfile::load(&dir).unwrap(), Some(drifted));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
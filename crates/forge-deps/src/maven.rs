@@ -1,18 +1,45 @@
 // =============================================================================
 // 🔥 FORGE — Resolución de Dependencias: Maven Central
 // =============================================================================
-// Descarga JARs y resuelve dependencias transitivas desde Maven Central.
+// Descarga JARs y resuelve dependencias transitivas desde los repositorios
+// configurados (por defecto, Maven Central).
 // =============================================================================
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
 
-use colored::Colorize;
+use colored::{ColoredString, Colorize};
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 
+use cyrce_forge_core::config::{ChecksumPolicy, ForgeConfig, UpdatePolicy, KOTLIN_GROUP};
 use cyrce_forge_core::error::{ForgeError, ForgeResult};
+use cyrce_forge_core::settings::{offline_mode, UserSettings};
+use cyrce_forge_core::telemetry::{global_event_bus, ForgeEvent};
 
-/// URL base de Maven Central.
-const MAVEN_CENTRAL_URL: &str = "https://repo1.maven.org/maven2";
+use crate::checksum::{self, ChecksumAlgorithm, Verification, ALGORITHMS};
+use crate::graph::{DependencyGraph, GraphNode, Selection};
+use crate::lockfile::{LockedMaven, Lockfile};
+use crate::metadata::MavenMetadata;
+use crate::repository::MavenRepository;
+use crate::store::{ArtifactStore, StoreLock};
+use crate::version::{self, VersionSpec};
+
+/// Archivo con el classpath resuelto (una ruta absoluta por línea) dentro de
+/// .forge/deps/, .forge/test-deps/ y .forge/processors/.
+pub const CLASSPATH_FILE: &str = "classpath.txt";
+
+/// Profundidad máxima de dependencias transitivas (evita ciclos infinitos).
+const MAX_DEPTH: usize = 5;
+
+/// groupId de los artefactos de KSP.
+const KSP_GROUP: &str = "com.google.devtools.ksp";
+
+/// Consola de JUnit Platform: un único JAR con el lanzador y los motores.
+const JUNIT_CONSOLE: &str = "org.junit.platform:junit-platform-console-standalone";
 
 /// Coordenadas Maven (groupId:artifactId:version).
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
@@ -43,32 +70,42 @@ impl MavenCoordinate {
         })
     }
 
-    /// Genera la URL del JAR en Maven Central.
-    pub fn jar_url(&self) -> String {
-        format!(
-            "{}/{}/{}/{}/{}-{}.jar",
-            MAVEN_CENTRAL_URL,
-            self.group_id.replace('.', "/"),
-            self.artifact_id,
-            self.version,
-            self.artifact_id,
-            self.version
-        )
+    /// Ruta del JAR relativa a la raíz de un repositorio (layout Maven).
+    pub fn jar_path(&self) -> String {
+        self.artifact_path("jar")
+    }
+
+    /// Ruta del POM relativa a la raíz de un repositorio (layout Maven).
+    pub fn pom_path(&self) -> String {
+        self.artifact_path("pom")
     }
 
-    /// Genera la URL del POM en Maven Central.
-    pub fn pom_url(&self) -> String {
+    /// Ruta de un artefacto con la extensión dada: `g/r/o/u/p/artifact/version/artifact-version.ext`.
+    fn artifact_path(&self, extension: &str) -> String {
         format!(
-            "{}/{}/{}/{}/{}-{}.pom",
-            MAVEN_CENTRAL_URL,
-            self.group_id.replace('.', "/"),
+            "{}/{}-{}.{}",
+            self.version_dir(),
             self.artifact_id,
             self.version,
-            self.artifact_id,
-            self.version
+            extension
         )
     }
 
+    /// Directorio del artefacto (todas las versiones): `g/r/o/u/p/artifact`.
+    pub fn artifact_dir(&self) -> String {
+        format!("{}/{}", self.group_id.replace('.', "/"), self.artifact_id)
+    }
+
+    /// Directorio de esta versión: `g/r/o/u/p/artifact/version`.
+    pub fn version_dir(&self) -> String {
+        format!("{}/{}", self.artifact_dir(), self.version)
+    }
+
+    /// `groupId:artifactId`, sin versión. Identifica el artefacto al mediar conflictos.
+    pub fn key(&self) -> String {
+        format!("{}:{}", self.group_id, self.artifact_id)
+    }
+
     /// Nombre del archivo JAR.
     pub fn jar_filename(&self) -> String {
         format!("{}-{}.jar", self.artifact_id, self.version)
@@ -82,23 +119,188 @@ impl MavenCoordinate {
 
 /// Resuelve y descarga dependencias Maven.
 pub struct MavenResolver {
-    /// Cliente HTTP reutilizable
-    client: reqwest::Client,
-    /// Directorio donde se cachean JARs
+    /// Estado compartido por las descargas concurrentes
+    fetcher: ArtifactFetcher,
+    /// Máximo de artefactos descargándose a la vez
+    parallel_downloads: usize,
+    /// Directorio del proyecto con el classpath resuelto
     cache_dir: PathBuf,
-    /// Dependencias ya resueltas (evitar ciclos)
-    resolved: HashSet<String>,
+    /// forge.lock anterior, cuyas versiones transitivas se respetan
+    lock: Option<Lockfile>,
+    /// Artefactos a re-resolver ignorando el lockfile (`--update`)
+    update: Vec<String>,
+    /// Entradas del lockfile producidas por esta resolución
+    locked: Vec<LockedMaven>,
+    /// Cada aparición de cada dependencia, para `forge tree` y `forge why`
+    graph: DependencyGraph,
+    /// Solo construir el grafo: no tocar los classpath.txt del proyecto
+    graph_only: bool,
+}
+
+/// Todo lo necesario para descargar un artefacto. Es barato de clonar para
+/// moverlo a cada tarea de descarga.
+#[derive(Clone)]
+struct ArtifactFetcher {
+    /// Cliente HTTP compartido (pool de conexiones común)
+    client: reqwest::Client,
+    /// Repositorios consultados en orden
+    repositories: Arc<Vec<MavenRepository>>,
+    /// Almacén global donde se guardan JARs y POMs
+    store: ArtifactStore,
+    /// Política de verificación de checksums
+    checksum_policy: ChecksumPolicy,
+    /// Cada cuánto se refresca maven-metadata.xml
+    update_policy: UpdatePolicy,
+    /// Barras de progreso de las descargas en curso
+    progress: MultiProgress,
+    /// Sin salida por consola (p. ej. `forge tree --json`)
+    quiet: bool,
+}
+
+/// Una dependencia pendiente de resolver en el nivel actual del árbol.
+struct Pending {
+    coord: MavenCoordinate,
+    /// Versión tal como la pidió el padre (o forge.toml)
+    requested: String,
+    /// Camino de coordenadas desde forge.toml (para forge.lock)
+    path: Vec<String>,
+    /// Nodo del grafo que la pidió
+    parent: Option<usize>,
+    selection: Selection,
+}
+
+/// Resultado de resolver un artefacto: su JAR y sus dependencias directas.
+struct ResolvedArtifact {
+    /// Coordenada con la versión concreta elegida (rangos y latest ya resueltos)
+    coordinate: MavenCoordinate,
+    jar: PathBuf,
+    checksum: String,
+    dependencies: Vec<MavenCoordinate>,
 }
 
 impl MavenResolver {
-    /// Crea un nuevo resolver.
-    pub fn new(project_dir: &Path) -> Self {
+    /// Crea un nuevo resolver con los repositorios de forge.toml y los mirrors del usuario.
+    pub fn new(config: &ForgeConfig, project_dir: &Path) -> ForgeResult<Self> {
+        Self::with_settings(config, project_dir, &UserSettings::load()?)
+    }
+
+    fn with_settings(config: &ForgeConfig, project_dir: &Path, settings: &UserSettings) -> ForgeResult<Self> {
         let cache_dir = project_dir.join(".forge").join("deps");
-        Self {
-            client: reqwest::Client::new(),
+        Ok(Self {
+            fetcher: ArtifactFetcher {
+                client: reqwest::Client::new(),
+                repositories: Arc::new(MavenRepository::from_config(config, settings)?),
+                store: ArtifactStore::new(settings)?,
+                checksum_policy: config.resolver.checksum,
+                update_policy: config.resolver.update_policy,
+                progress: MultiProgress::new(),
+                quiet: false,
+            },
+            parallel_downloads: config.resolver.parallel_downloads.max(1),
             cache_dir,
-            resolved: HashSet::new(),
+            lock: None,
+            update: Vec::new(),
+            locked: Vec::new(),
+            graph: DependencyGraph::default(),
+            graph_only: false,
+        })
+    }
+
+    /// Respeta las versiones de un forge.lock previo, salvo las indicadas en `update`.
+    pub fn with_lock(mut self, lock: Option<&Lockfile>, update: &[String]) -> Self {
+        self.lock = lock.cloned();
+        self.update = update.to_vec();
+        self
+    }
+
+    /// Cambia cada cuánto se re-consulta maven-metadata.xml (`forge upgrade` usa `Always`).
+    pub fn with_update_policy(mut self, policy: UpdatePolicy) -> Self {
+        self.fetcher.update_policy = policy;
+        self
+    }
+
+    /// Versiones publicadas de un artefacto en todos los repositorios configurados.
+    pub async fn available_versions(&self, coord: &MavenCoordinate) -> ForgeResult<Vec<String>> {
+        Ok(self.fetcher.artifact_metadata(coord).await?.versions)
+    }
+
+    /// Resuelve sin imprimir nada ni mostrar barras de progreso.
+    pub fn quiet(mut self) -> Self {
+        self.fetcher.quiet = true;
+        self.fetcher.progress.set_draw_target(ProgressDrawTarget::hidden());
+        self
+    }
+
+    /// Resuelve solo para obtener el grafo (`forge tree`, `forge why`): los
+    /// artefactos se descargan al almacén, pero .forge/deps y demás quedan intactos.
+    pub fn graph_only(mut self) -> Self {
+        self.graph_only = true;
+        self
+    }
+
+    /// Grafo de todo lo resuelto hasta ahora.
+    pub fn graph(&self) -> &DependencyGraph {
+        &self.graph
+    }
+
+    /// Entradas de forge.lock para todo lo resuelto hasta ahora.
+    pub fn lock_entries(&self) -> &[LockedMaven] {
+        &self.locked
+    }
+
+    /// Repositorios configurados, en orden de consulta.
+    pub fn repositories(&self) -> &[MavenRepository] {
+        &self.fetcher.repositories
+    }
+    /// Re-verifica los artefactos del classpath del proyecto contra sus checksums guardados.
+    /// Los archivos corruptos se eliminan del almacén para que se vuelvan a descargar.
+    /// Devuelve (válidos, sin checksum, corruptos).
+    pub fn verify_cached(&self) -> ForgeResult<(usize, usize, usize)> {
+        let (mut valid, mut missing, mut corrupted) = (0, 0, 0);
+
+        for dir in [
+            self.cache_dir.clone(),
+            self.test_cache_dir(),
+            self.processor_cache_dir(),
+            self.kotlin_compiler_cache_dir(),
+            self.kotlin_plugin_cache_dir(),
+            self.kapt_cache_dir(),
+            self.ksp_cache_dir(),
+            self.ksp_processor_cache_dir(),
+            self.junit_cache_dir(),
+        ] {
+            let Ok(content) = std::fs::read_to_string(dir.join(CLASSPATH_FILE)) else {
+                continue;
+            };
+
+            for line in content.lines().map(str::trim).filter(|l| !l.is_empty()) {
+                let jar = PathBuf::from(line);
+                if !jar.exists() {
+                    continue;
+                }
+
+                match checksum::verify_file(&jar)? {
+                    Verification::Valid => valid += 1,
+                    Verification::Missing => missing += 1,
+                    Verification::Mismatch { expected, actual } => {
+                        corrupted += 1;
+                        println!(
+                            "   {}",
+                            format!(
+                                "❌ {} — esperado {}, obtenido {}",
+                                jar.display(),
+                                expected,
+                                actual
+                            )
+                            .red()
+                        );
+                        self.fetcher.store.evict(&jar);
+                    }
+                }
+            }
         }
+
+        Ok((valid, missing, corrupted))
     }
 
     /// Carga el directorio en caché para dependencias de prueba
@@ -106,127 +308,346 @@ impl MavenResolver {
         self.cache_dir.parent().unwrap().join("test-deps")
     }
 
-    /// Resuelve y descarga todas las dependencias runtime a .forge/deps/.
+    /// Directorio del classpath de los procesadores de anotaciones
+    pub fn processor_cache_dir(&self) -> PathBuf {
+        self.cache_dir.parent().unwrap().join("processors")
+    }
+
+    /// Directorio del classpath del compilador de Kotlin embebido
+    pub fn kotlin_compiler_cache_dir(&self) -> PathBuf {
+        self.cache_dir.parent().unwrap().join("kotlin-compiler")
+    }
+
+    /// Directorio de los plugins del compilador de Kotlin (`-Xplugin`)
+    pub fn kotlin_plugin_cache_dir(&self) -> PathBuf {
+        self.cache_dir.parent().unwrap().join("kotlin-plugins")
+    }
+
+    /// Directorio del plugin kapt (procesadores de anotaciones sobre fuentes Kotlin)
+    pub fn kapt_cache_dir(&self) -> PathBuf {
+        self.cache_dir.parent().unwrap().join("kapt")
+    }
+
+    /// Directorio del classpath de KSP
+    pub fn ksp_cache_dir(&self) -> PathBuf {
+        self.cache_dir.parent().unwrap().join("ksp")
+    }
+
+    /// Directorio del classpath de los procesadores KSP
+    pub fn ksp_processor_cache_dir(&self) -> PathBuf {
+        self.cache_dir.parent().unwrap().join("ksp-processors")
+    }
+
+    /// Directorio del classpath de la consola de JUnit Platform
+    pub fn junit_cache_dir(&self) -> PathBuf {
+        self.cache_dir.parent().unwrap().join("junit")
+    }
+
+    /// Resuelve las dependencias runtime y escribe su classpath en .forge/deps/.
     pub async fn resolve_all(
         &mut self,
         dependencies: &std::collections::HashMap<String, String>,
     ) -> ForgeResult<Vec<PathBuf>> {
-        self.resolve_internal(dependencies, &self.cache_dir.clone()).await
+        self.resolve_internal(dependencies, &self.cache_dir.clone(), "compile").await
     }
 
-    /// Resuelve y descarga dependencias de prueba a .forge/test-deps/.
+    /// Resuelve las dependencias de prueba y escribe su classpath en .forge/test-deps/.
     pub async fn resolve_test_deps(
         &mut self,
         dependencies: &std::collections::HashMap<String, String>,
     ) -> ForgeResult<Vec<PathBuf>> {
-        self.resolve_internal(dependencies, &self.test_cache_dir()).await
+        self.resolve_internal(dependencies, &self.test_cache_dir(), "test").await
     }
 
-    /// Implementación interna de resolución a un directorio específico.
+    /// Resuelve los procesadores de anotaciones y escribe su classpath (el
+    /// `-processorpath` de javac) en .forge/processors/.
+    pub async fn resolve_processors(
+        &mut self,
+        dependencies: &std::collections::HashMap<String, String>,
+    ) -> ForgeResult<Vec<PathBuf>> {
+        self.resolve_internal(dependencies, &self.processor_cache_dir(), "processor").await
+    }
+
+    /// Resuelve kotlin-compiler-embeddable de una versión de Kotlin y escribe su
+    /// classpath en .forge/kotlin-compiler/.
+    pub async fn resolve_kotlin_compiler(&mut self, version: &str) -> ForgeResult<Vec<PathBuf>> {
+        let compiler = std::collections::HashMap::from([(
+            format!("{}:kotlin-compiler-embeddable", KOTLIN_GROUP),
+            version.to_string(),
+        )]);
+        self.resolve_internal(&compiler, &self.kotlin_compiler_cache_dir(), "kotlin-compiler").await
+    }
+
+    /// Resuelve los plugins del compilador de Kotlin (`[kotlin] compiler-plugins`) y
+    /// escribe su classpath en .forge/kotlin-plugins/.
+    pub async fn resolve_kotlin_plugins(
+        &mut self,
+        plugins: &std::collections::HashMap<String, String>,
+    ) -> ForgeResult<Vec<PathBuf>> {
+        self.resolve_internal(plugins, &self.kotlin_plugin_cache_dir(), "kotlin-plugin").await
+    }
+
+    /// Resuelve el plugin kapt de una versión de Kotlin en .forge/kapt/.
+    pub async fn resolve_kapt(&mut self, version: &str) -> ForgeResult<Vec<PathBuf>> {
+        let kapt = std::collections::HashMap::from([(
+            format!("{}:kotlin-annotation-processing-embeddable", KOTLIN_GROUP),
+            version.to_string(),
+        )]);
+        self.resolve_internal(&kapt, &self.kapt_cache_dir(), "kapt").await
+    }
+
+    /// Resuelve KSP (la versión standalone, KSP2) en .forge/ksp/ y sus procesadores
+    /// en .forge/ksp-processors/.
+    pub async fn resolve_ksp(
+        &mut self,
+        version: &str,
+        processors: &std::collections::HashMap<String, String>,
+    ) -> ForgeResult<Vec<PathBuf>> {
+        let ksp: std::collections::HashMap<String, String> = [
+            "symbol-processing-aa-embeddable",
+            "symbol-processing-common-deps",
+            "symbol-processing-api",
+        ]
+        .iter()
+        .map(|artifact| (format!("{}:{}", KSP_GROUP, artifact), version.to_string()))
+        .collect();
+        let mut jars = self.resolve_internal(&ksp, &self.ksp_cache_dir(), "ksp").await?;
+        jars.extend(
+            self.resolve_internal(processors, &self.ksp_processor_cache_dir(), "ksp-processor")
+                .await?,
+        );
+        Ok(jars)
+    }
+
+    /// Resuelve junit-platform-console-standalone (`[test] junit-platform`) en .forge/junit/.
+    pub async fn resolve_junit_platform(&mut self, version: &str) -> ForgeResult<Vec<PathBuf>> {
+        let console = std::collections::HashMap::from([(JUNIT_CONSOLE.to_string(), version.to_string())]);
+        self.resolve_internal(&console, &self.junit_cache_dir(), "junit-platform").await
+    }
+
+    /// Implementación interna de resolución hacia un directorio de classpath.
     /// Soporta resolución TRANSITIVA: descarga cada JAR, lee su POM y resuelve sub-dependencias.
+    /// Cada nivel del árbol se descarga en paralelo (hasta `[resolver] parallel-downloads`
+    /// artefactos a la vez). Los JARs viven en el almacén global; el proyecto solo guarda las rutas.
+    ///
+    /// Si un mismo `groupId:artifactId` aparece con varias versiones gana la más cercana a
+    /// forge.toml (como Maven). Las versiones transitivas fijadas en forge.lock se respetan.
     async fn resolve_internal(
         &mut self,
         dependencies: &std::collections::HashMap<String, String>,
         target_dir: &Path,
+        scope: &str,
     ) -> ForgeResult<Vec<PathBuf>> {
-        std::fs::create_dir_all(target_dir).map_err(|e| ForgeError::IoError {
-            path: target_dir.to_path_buf(),
-            message: e.to_string(),
-        })?;
-
-        let mut downloaded = Vec::new();
+        if !self.graph_only {
+            std::fs::create_dir_all(target_dir).map_err(|e| ForgeError::IoError {
+                path: target_dir.to_path_buf(),
+                message: e.to_string(),
+            })?;
+        }
 
-        println!(
-            "   {}",
+        self.fetcher.report(
             format!(
-                "📦 Resolviendo {} dependencias en Maven Central...",
-                dependencies.len()
+                "📦 Resolviendo {} dependencias en {} repositorio(s)...",
+                dependencies.len(),
+                self.fetcher.repositories.len()
             )
-            .cyan()
+            .cyan(),
         );
 
-        for (key, version) in dependencies {
-            let coord = MavenCoordinate::parse(key, version)?;
-            self.resolve_recursive(&coord, target_dir, &mut downloaded, 0).await?;
-        }
+        // Orden estable: el mismo forge.toml produce siempre el mismo classpath
+        let mut direct: Vec<(&String, &String)> = dependencies.iter().collect();
+        direct.sort();
+        let pins = self
+            .lock
+            .as_ref()
+            .map(|lock| lock.maven_pins(scope, &self.update))
+            .unwrap_or_default();
 
-        println!(
-            "   {}",
-            format!("✅ {} dependencias resueltas (incluyendo transitivas)", downloaded.len()).green()
+        // Las versiones directas de forge.toml mandan; solo un rango o latest/release
+        // se fija a lo que diga forge.lock (si sigue cumpliendo lo pedido)
+        let mut level = direct
+            .into_iter()
+            .map(|(key, version)| {
+                let mut coord = MavenCoordinate::parse(key, version)?;
+                let spec = VersionSpec::parse(&coord.version)?;
+                let mut selection = if spec.is_dynamic() {
+                    Selection::Range
+                } else {
+                    Selection::Declared
+                };
+                if let Some(pinned) = pins.get(&coord.key()) {
+                    if spec.is_dynamic() && spec.matches(pinned) {
+                        coord.version = pinned.clone();
+                        selection = Selection::Locked;
+                    }
+                }
+                Ok(Pending {
+                    requested: version.clone(),
+                    coord,
+                    path: Vec::new(),
+                    parent: None,
+                    selection,
+                })
+            })
+            .collect::<ForgeResult<Vec<Pending>>>()?;
+
+        let overall = self.fetcher.progress.add(ProgressBar::new(0));
+        overall.set_style(
+            ProgressStyle::default_bar()
+                .template("   {spinner:.cyan} [{bar:30.cyan/blue}] {pos}/{len} dependencias")
+                .unwrap()
+                .progress_chars("=> "),
         );
 
-        Ok(downloaded)
-    }
+        let semaphore = Arc::new(Semaphore::new(self.parallel_downloads));
+        let mut winners: HashMap<String, usize> = HashMap::new();
+        let mut downloaded = Vec::new();
 
-    /// Resolución recursiva: descarga JAR + lee POM + resuelve sub-dependencias.
-    /// `depth` limita la profundidad para evitar ciclos infinitos.
-    fn resolve_recursive<'a>(
-        &'a mut self,
-        coord: &'a MavenCoordinate,
-        target_dir: &'a Path,
-        downloaded: &'a mut Vec<PathBuf>,
-        depth: usize,
-    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = ForgeResult<()>> + Send + 'a>> {
-        Box::pin(async move {
-            // Límite de profundidad para evitar ciclos
-            if depth > 5 {
-                return Ok(());
+        for _ in 0..=MAX_DEPTH {
+            // Las apariciones repetidas quedan en el grafo como mediadas, sin expandirse
+            let mut fresh = Vec::with_capacity(level.len());
+            for pending in level.drain(..) {
+                let node = self.graph.push(GraphNode {
+                    group: pending.coord.group_id.clone(),
+                    artifact: pending.coord.artifact_id.clone(),
+                    requested: pending.requested.clone(),
+                    version: pending.coord.version.clone(),
+                    scope: scope.to_string(),
+                    parent: pending.parent,
+                    selection: pending.selection,
+                    winner: None,
+                });
+                match winners.get(&pending.coord.key()) {
+                    Some(&winner) => {
+                        self.graph.nodes[node].selection = Selection::Mediated;
+                        self.graph.nodes[node].winner = Some(winner);
+                    }
+                    None => {
+                        winners.insert(pending.coord.key(), node);
+                        fresh.push((node, pending));
+                    }
+                }
             }
+            if fresh.is_empty() {
+                break;
+            }
+            overall.inc_length(fresh.len() as u64);
 
-            let key = format!("{}:{}", target_dir.to_string_lossy(), coord.display());
+            let mut tasks = JoinSet::new();
+            let mut paths = Vec::with_capacity(fresh.len());
+            for (index, (node, pending)) in fresh.into_iter().enumerate() {
+                let fetcher = self.fetcher.clone();
+                let semaphore = semaphore.clone();
+                let coord = pending.coord;
+                paths.push((node, pending.path));
+                tasks.spawn(async move {
+                    let _permit = semaphore.acquire_owned().await;
+                    (index, fetcher.resolve_artifact(&coord).await)
+                });
+            }
 
-            // Evitar resolver la misma dependencia dos veces
-            if self.resolved.contains(&key) {
-                return Ok(());
+            // Los resultados llegan en cualquier orden; se reordenan para un classpath estable
+            let mut results = Vec::new();
+            while let Some(joined) = tasks.join_next().await {
+                let (index, result) = joined.map_err(|e| ForgeError::DependencyResolutionFailed {
+                    dependency: format!("tarea de descarga abortada: {}", e),
+                })?;
+                let artifact = match result {
+                    Ok(artifact) => artifact,
+                    Err(e) => {
+                        overall.finish_and_clear();
+                        return Err(e);
+                    }
+                };
+                overall.inc(1);
+                results.push((index, artifact));
             }
+            results.sort_by_key(|(index, _)| *index);
 
-            // Descargar el JAR principal
-            let jar_path = self.download_dependency(coord, target_dir).await?;
-            downloaded.push(jar_path);
+            for (index, artifact) in results {
+                let (node, path) = &paths[index];
+                let coord = &artifact.coordinate;
+                self.graph.nodes[*node].version = coord.version.clone();
+                downloaded.push(artifact.jar);
 
-            // Intentar leer el POM para dependencias transitivas
-            if let Ok(transitive_deps) = self.fetch_transitive_deps(coord).await {
-                for dep_coord in transitive_deps {
-                    self.resolve_recursive(&dep_coord, target_dir, downloaded, depth + 1).await?;
+                let mut child_path = path.clone();
+                child_path.push(coord.display());
+                for mut dep in artifact.dependencies {
+                    let requested = dep.version.clone();
+                    let dynamic = VersionSpec::parse(&dep.version).map(|spec| spec.is_dynamic());
+                    let mut selection = match dynamic {
+                        Ok(true) => Selection::Range,
+                        _ => Selection::Nearest,
+                    };
+                    if let Some(pinned) = pins.get(&dep.key()) {
+                        // Un rango del POM solo se fija si la versión bloqueada aún lo cumple
+                        let applies = VersionSpec::parse(&dep.version)
+                            .map(|spec| !spec.is_dynamic() || spec.matches(pinned))
+                            .unwrap_or(true);
+                        if applies {
+                            dep.version = pinned.clone();
+                            selection = Selection::Locked;
+                        }
+                    }
+                    level.push(Pending {
+                        coord: dep,
+                        requested,
+                        path: child_path.clone(),
+                        parent: Some(*node),
+                        selection,
+                    });
                 }
+
+                self.locked.push(LockedMaven {
+                    group: coord.group_id.clone(),
+                    artifact: coord.artifact_id.clone(),
+                    version: coord.version.clone(),
+                    scope: scope.to_string(),
+                    checksum: artifact.checksum,
+                    path: path.clone(),
+                });
             }
+        }
 
-            Ok(())
-        })
-    }
+        overall.finish_and_clear();
+        self.graph.settle();
+        if !self.graph_only {
+            Self::write_classpath(target_dir, &downloaded)?;
+        }
 
-    /// Descarga y parsea el POM de una coordenada Maven para extraer dependencias transitivas.
-    /// Solo extrae dependencias con scope "compile" o sin scope (default=compile).
-    /// Ignora dependencias con scope "test", "provided" o "system".
-    async fn fetch_transitive_deps(
-        &self,
-        coord: &MavenCoordinate,
-    ) -> ForgeResult<Vec<MavenCoordinate>> {
-        let pom_url = coord.pom_url();
+        self.fetcher.report(
+            format!("✅ {} dependencias resueltas (incluyendo transitivas)", downloaded.len()).green(),
+        );
 
-        let response = self
-            .client
-            .get(&pom_url)
-            .send()
-            .await
-            .map_err(|e| ForgeError::DownloadError {
-                url: pom_url.clone(),
-                message: e.to_string(),
-            })?;
+        Ok(downloaded)
+    }
 
-        if !response.status().is_success() {
-            return Ok(Vec::new()); // POM no encontrado, no es error fatal
+    /// Escribe el classpath resuelto y elimina JARs sueltos de versiones anteriores de FORGE,
+    /// que copiaban cada dependencia dentro del proyecto.
+    fn write_classpath(target_dir: &Path, jars: &[PathBuf]) -> ForgeResult<()> {
+        if let Ok(entries) = std::fs::read_dir(target_dir) {
+            for entry in entries.flatten() {
+                if entry.path().extension().map(|e| e == "jar").unwrap_or(false) {
+                    let _ = std::fs::remove_file(entry.path());
+                }
+            }
         }
 
-        let pom_text = response
-            .text()
-            .await
-            .map_err(|e| ForgeError::DownloadError {
-                url: pom_url.clone(),
-                message: e.to_string(),
-            })?;
+        let mut seen = HashSet::new();
+        let content: Vec<String> = jars
+            .iter()
+            .filter(|jar| seen.insert(jar.as_path()))
+            .map(|jar| jar.to_string_lossy().to_string())
+            .collect();
 
-        Ok(Self::parse_pom_dependencies(&pom_text))
+        let path = target_dir.join(CLASSPATH_FILE);
+        std::fs::write(&path, content.join("\n")).map_err(|e| {
+            ForgeError::IoError {
+                path,
+                message: e.to_string(),
+            }
+            .into()
+        })
     }
 
     /// Parsea un POM XML y extrae las dependencias con scope compile.
@@ -295,16 +716,14 @@ impl MavenResolver {
                         }
                     }
                 }
-                Ok(quick_xml::events::Event::Text(ref e)) => {
-                    if in_dependency {
-                        let text = e.unescape().unwrap_or_default().to_string();
-                        match current_tag.as_str() {
-                            "groupId" => current_group = text,
-                            "artifactId" => current_artifact = text,
-                            "version" => current_version = text,
-                            "scope" => current_scope = text,
-                            _ => {}
-                        }
+                Ok(quick_xml::events::Event::Text(ref e)) if in_dependency => {
+                    let text = e.unescape().unwrap_or_default().to_string();
+                    match current_tag.as_str() {
+                        "groupId" => current_group = text,
+                        "artifactId" => current_artifact = text,
+                        "version" => current_version = text,
+                        "scope" => current_scope = text,
+                        _ => {}
                     }
                 }
                 Ok(quick_xml::events::Event::Eof) => break,
@@ -316,70 +735,641 @@ impl MavenResolver {
 
         deps
     }
+}
 
-    /// Descarga un JAR individual si no está en caché.
-    async fn download_dependency(
-        &mut self,
-        coord: &MavenCoordinate,
-        target_dir: &Path,
+impl ArtifactFetcher {
+    /// Imprime una línea sin romper las barras de progreso (nada en modo silencioso).
+    fn report(&self, line: ColoredString) {
+        if !self.quiet {
+            self.progress.suspend(|| println!("   {}", line));
+        }
+    }
+
+    /// Busca un archivo en los repositorios en orden y devuelve el primero encontrado.
+    async fn fetch_from_repositories(
+        &self,
+        path: &str,
+    ) -> ForgeResult<Option<(Vec<u8>, &MavenRepository)>> {
+        for repo in self.repositories.iter() {
+            if let Some(bytes) = repo.fetch(&self.client, path).await? {
+                return Ok(Some((bytes, repo)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Devuelve un archivo del almacén global, descargándolo si aún no está.
+    /// `None` si ningún repositorio lo tiene.
+    async fn fetch_into_store(&self, relative: &str) -> ForgeResult<Option<PathBuf>> {
+        if let Some(path) = self.store.find(relative) {
+            return Ok(Some(path));
+        }
+
+        let lock = self.store.lock(relative).await?;
+
+        // Otro proceso pudo haberlo descargado mientras esperábamos el lock
+        if let Some(path) = self.store.find(relative) {
+            return Ok(Some(path));
+        }
+
+        match self.fetch_from_repositories(relative).await? {
+            Some((bytes, repo)) => {
+                tracing::debug!("{} descargado desde '{}'", relative, repo.name);
+                let path = self
+                    .store_download(&lock, relative, relative, &bytes, repo)
+                    .await?;
+                Ok(Some(path))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Verifica lo descargado desde `remote` y lo guarda en el almacén como `relative`,
+    /// junto con el checksum verificado (o sin sidecar si no había checksum).
+    async fn store_download(
+        &self,
+        lock: &StoreLock,
+        relative: &str,
+        remote: &str,
+        bytes: &[u8],
+        repo: &MavenRepository,
     ) -> ForgeResult<PathBuf> {
-        let key = format!("{}:{}", target_dir.to_string_lossy(), coord.display());
+        let verified = self.verify_download(remote, bytes, repo).await?;
+        let path = self.store.write(lock, relative, bytes)?;
+
+        for algorithm in ALGORITHMS {
+            let sidecar = format!("{}.{}", relative, algorithm.extension());
+            match &verified {
+                Some((verified_with, hash)) if *verified_with == algorithm => {
+                    self.store.write(lock, &sidecar, hash.as_bytes())?;
+                }
+                // Un sidecar de una versión anterior (SNAPSHOT) ya no corresponde
+                _ => {
+                    let _ = std::fs::remove_file(self.store.path_for(&sidecar));
+                }
+            }
+        }
+
+        Ok(path)
+    }
+
+    /// Elige la versión concreta para un rango, `latest` o `release` consultando
+    /// maven-metadata.xml. Las versiones exactas se devuelven sin tocar la red.
+    async fn resolve_version(&self, coord: &MavenCoordinate) -> ForgeResult<MavenCoordinate> {
+        let spec = VersionSpec::parse(&coord.version)?;
+        if !spec.is_dynamic() {
+            return Ok(coord.clone());
+        }
+
+        let metadata = self.artifact_metadata(coord).await?;
+        let version = spec
+            .select(&metadata)
+            .ok_or_else(|| ForgeError::DependencyResolutionFailed {
+                dependency: format!(
+                    "{} {} — Ninguna versión publicada cumple lo pedido (disponibles: {})",
+                    coord.key(),
+                    spec,
+                    if metadata.versions.is_empty() {
+                        "ninguna".to_string()
+                    } else {
+                        metadata.versions.join(", ")
+                    }
+                ),
+            })?;
+
+        tracing::debug!("{} {} → {}", coord.key(), spec, version);
+        Ok(MavenCoordinate {
+            version,
+            ..coord.clone()
+        })
+    }
+
+    /// maven-metadata.xml de un artefacto combinando todos los repositorios.
+    /// Cada copia se guarda en el almacén (`maven-metadata-<repo>.xml`) y solo se vuelve
+    /// a pedir cuando vence `[resolver] update-policy`.
+    async fn artifact_metadata(&self, coord: &MavenCoordinate) -> ForgeResult<MavenMetadata> {
+        let remote = format!("{}/maven-metadata.xml", coord.artifact_dir());
+        let mut merged = MavenMetadata::default();
+        let mut found = false;
+        let mut last_error = None;
+
+        for repo in self.repositories.iter() {
+            let cached = format!("{}/maven-metadata-{}.xml", coord.artifact_dir(), repo.name);
+            let cached_path = self.store.path_for(&cached);
+
+            let fetched = if !offline_mode() && self.is_stale(&cached_path) {
+                match repo.fetch(&self.client, &remote).await {
+                    Ok(fetched) => fetched,
+                    // Un repositorio caído no impide usar lo que publican los demás
+                    Err(e) => {
+                        tracing::debug!("maven-metadata.xml de '{}' no disponible: {}", repo.name, e);
+                        last_error = Some(e);
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+
+            let content = match fetched {
+                Some(bytes) => {
+                    let lock = self.store.lock(&cached).await?;
+                    self.store.write(&lock, &cached, &bytes)?;
+                    Some(String::from_utf8_lossy(&bytes).to_string())
+                }
+                None => std::fs::read_to_string(&cached_path).ok(),
+            };
+
+            if let Some(content) = content {
+                merged.merge(MavenMetadata::parse(&content));
+                found = true;
+            }
+        }
+
+        if !found {
+            if offline_mode() {
+                return Err(ForgeError::OfflineArtifactMissing {
+                    artifact: format!("{} (maven-metadata.xml)", coord.key()),
+                }
+                .into());
+            }
+            if let Some(e) = last_error {
+                return Err(e);
+            }
+        }
+
+        Ok(merged)
+    }
+
+    /// ¿Hay que volver a consultar un archivo de metadata guardado localmente?
+    fn is_stale(&self, path: &Path) -> bool {
+        let age = std::fs::metadata(path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok());
 
-        // Evitar resolver la misma dependencia dos veces
-        if self.resolved.contains(&key) {
-            return Ok(target_dir.join(coord.jar_filename()));
+        match age {
+            Some(age) => self.update_policy.is_stale(age),
+            None => true,
         }
+    }
 
-        let jar_path = target_dir.join(coord.jar_filename());
+    /// Obtiene un JAR o POM `-SNAPSHOT`. El almacén guarda siempre el último build con
+    /// nombre estable (`lib-1.0-SNAPSHOT.jar`) y, al lado, un `.snapshot` con el build con
+    /// timestamp al que corresponde. Se re-consulta según `[resolver] update-policy`.
+    /// Devuelve la ruta y si vino de la caché.
+    async fn fetch_snapshot(
+        &self,
+        coord: &MavenCoordinate,
+        extension: &str,
+    ) -> ForgeResult<Option<(PathBuf, bool)>> {
+        let relative = coord.artifact_path(extension);
+        let local = self.store.path_for(&relative);
+        let marker = format!("{}.snapshot", relative);
+        let marker_path = self.store.path_for(&marker);
 
-        // Si ya existe en caché, no descargar
-        if jar_path.exists() {
-            self.resolved.insert(key);
-            println!(
-                "   {}",
-                format!("   ⚡ {} (caché)", coord.display()).dimmed()
+        if local.exists() && (offline_mode() || !self.is_stale(&marker_path)) {
+            return Ok(Some((local, true)));
+        }
+        if offline_mode() {
+            return Ok(None);
+        }
+
+        let remote_metadata = format!("{}/maven-metadata.xml", coord.version_dir());
+        for repo in self.repositories.iter() {
+            let Some(bytes) = repo.fetch(&self.client, &remote_metadata).await? else {
+                continue;
+            };
+            let metadata = MavenMetadata::parse(&String::from_utf8_lossy(&bytes));
+            let value = metadata
+                .snapshot_value(&coord.version, extension)
+                .unwrap_or_else(|| coord.version.clone());
+
+            let lock = self.store.lock(&relative).await?;
+            let previous = std::fs::read_to_string(&marker_path).ok();
+
+            if local.exists() && previous.as_deref() == Some(value.as_str()) {
+                // Mismo build: solo se renueva la marca de tiempo
+                self.store.write(&lock, &marker, value.as_bytes())?;
+                return Ok(Some((local, true)));
+            }
+
+            let remote = format!(
+                "{}/{}-{}.{}",
+                coord.version_dir(),
+                coord.artifact_id,
+                value,
+                extension
             );
-            return Ok(jar_path);
+            let Some(bytes) = repo.fetch(&self.client, &remote).await? else {
+                continue;
+            };
+
+            tracing::debug!("{} → {} desde '{}'", coord.display(), value, repo.name);
+            let path = self
+                .store_download(&lock, &relative, &remote, &bytes, repo)
+                .await?;
+            self.store.write(&lock, &marker, value.as_bytes())?;
+            return Ok(Some((path, false)));
         }
 
-        println!(
-            "   {}",
-            format!("   ⬇️  Descargando {}...", coord.display()).dimmed()
-        );
+        // Repositorios sin metadata de SNAPSHOT (p. ej. file:// sencillos): nombre literal
+        Ok(self.fetch_into_store(&relative).await?.map(|path| (path, false)))
+    }
 
-        let url = coord.jar_url();
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| ForgeError::DownloadError {
-                url: url.clone(),
-                message: e.to_string(),
-            })?;
+    /// Compara lo descargado con el checksum publicado por el mismo repositorio.
+    /// Devuelve el checksum verificado para guardarlo junto al artefacto.
+    async fn verify_download(
+        &self,
+        relative: &str,
+        bytes: &[u8],
+        repo: &MavenRepository,
+    ) -> ForgeResult<Option<(ChecksumAlgorithm, String)>> {
+        if self.checksum_policy == ChecksumPolicy::Off {
+            return Ok(None);
+        }
+
+        for algorithm in ALGORITHMS {
+            let sidecar_path = format!("{}.{}", relative, algorithm.extension());
+            let Some(sidecar) = repo.fetch(&self.client, &sidecar_path).await? else {
+                continue;
+            };
+            let Some(expected) = checksum::parse_sidecar(&String::from_utf8_lossy(&sidecar)) else {
+                continue;
+            };
 
-        if !response.status().is_success() {
-            return Err(ForgeError::DownloadError {
-                url,
-                message: format!("HTTP {}", response.status()),
+            let actual = algorithm.digest(bytes);
+            if actual == expected {
+                return Ok(Some((algorithm, actual)));
+            }
+
+            let error = ForgeError::ChecksumMismatch {
+                artifact: repo.url_for(relative),
+                expected,
+                actual,
+            };
+            if self.checksum_policy == ChecksumPolicy::Strict {
+                return Err(error.into());
+            }
+            self.report(format!("⚠️  {}", error).yellow());
+            return Ok(None);
+        }
+
+        if self.checksum_policy == ChecksumPolicy::Strict {
+            return Err(ForgeError::DependencyResolutionFailed {
+                dependency: format!(
+                    "{} — '{}' no publica checksums (.sha256/.sha1) y [resolver] checksum = \"strict\"",
+                    relative, repo.name
+                ),
             }
             .into());
         }
 
-        let bytes = response
-            .bytes()
-            .await
-            .map_err(|e| ForgeError::DownloadError {
-                url: url.clone(),
-                message: e.to_string(),
-            })?;
+        self.report(
+            format!("⚠️  Sin checksum publicado para {} en '{}'", relative, repo.name).yellow(),
+        );
+        Ok(None)
+    }
+
+    /// Obtiene el JAR de una coordenada (descargándolo si hace falta) y lee su POM
+    /// para conocer sus dependencias transitivas.
+    async fn resolve_artifact(&self, coord: &MavenCoordinate) -> ForgeResult<ResolvedArtifact> {
+        let start = Instant::now();
+        let coord = &self.resolve_version(coord).await?;
+        let relative = coord.jar_path();
+        let snapshot = version::is_snapshot(&coord.version);
 
-        std::fs::write(&jar_path, &bytes).map_err(|e| ForgeError::IoError {
-            path: jar_path.clone(),
+        // Si ya existe en el almacén (o en ~/.m2), no descargar. Los SNAPSHOT
+        // se re-consultan según update-policy.
+        let found = if snapshot { None } else { self.store.find(&relative) };
+        let (jar, cached) = match found {
+            Some(jar) => {
+                self.report(format!("⚡ {} (caché)", coord.display()).dimmed());
+                (jar, true)
+            }
+            None => {
+                let pb = self.progress.add(ProgressBar::new_spinner());
+                pb.set_style(
+                    ProgressStyle::default_spinner()
+                        .template("   {spinner:.cyan} {msg}")
+                        .unwrap(),
+                );
+                pb.set_message(format!("Descargando {}...", coord.display()));
+                pb.enable_steady_tick(std::time::Duration::from_millis(100));
+
+                let jar = if snapshot {
+                    self.fetch_snapshot(coord, "jar").await
+                } else {
+                    self.fetch_into_store(&relative)
+                        .await
+                        .map(|jar| jar.map(|jar| (jar, false)))
+                };
+                pb.finish_and_clear();
+
+                let (jar, cached) = jar?.ok_or_else(|| {
+                    if offline_mode() {
+                        return ForgeError::OfflineArtifactMissing {
+                            artifact: coord.display(),
+                        };
+                    }
+                    ForgeError::DependencyResolutionFailed {
+                        dependency: format!(
+                            "{} — No encontrado en: {}",
+                            coord.display(),
+                            self.repositories
+                                .iter()
+                                .map(|r| r.name.as_str())
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ),
+                    }
+                })?;
+                if cached {
+                    self.report(format!("⚡ {} (caché)", coord.display()).dimmed());
+                } else {
+                    self.report(format!("⬇️  {}", coord.display()).dimmed());
+                }
+                (jar, cached)
+            }
+        };
+
+        // Un POM ausente o ilegible no es fatal: el artefacto simplemente no aporta transitivas.
+        // Sin red sí lo es, porque no se puede saber si el POM existe.
+        let dependencies = match self.fetch_transitive_deps(coord).await {
+            Ok(dependencies) => dependencies,
+            Err(e) if offline_mode() => return Err(e),
+            Err(_) => Vec::new(),
+        };
+
+        global_event_bus().send(ForgeEvent::DependencyResolved {
+            name: format!("{}:{}", coord.group_id, coord.artifact_id),
+            version: coord.version.clone(),
+            cached,
+            time_ms: start.elapsed().as_millis() as u64,
+        });
+
+        let bytes = std::fs::read(&jar).map_err(|e| ForgeError::IoError {
+            path: jar.clone(),
+            message: e.to_string(),
+        })?;
+        let checksum = format!("sha256:{}", ChecksumAlgorithm::Sha256.digest(&bytes));
+
+        Ok(ResolvedArtifact {
+            coordinate: coord.clone(),
+            jar,
+            checksum,
+            dependencies,
+        })
+    }
+
+    /// Descarga y parsea el POM de una coordenada Maven para extraer dependencias transitivas.
+    /// Solo extrae dependencias con scope "compile" o sin scope (default=compile).
+    /// Ignora dependencias con scope "test", "provided" o "system".
+    async fn fetch_transitive_deps(
+        &self,
+        coord: &MavenCoordinate,
+    ) -> ForgeResult<Vec<MavenCoordinate>> {
+        let pom = if version::is_snapshot(&coord.version) {
+            self.fetch_snapshot(coord, "pom").await?.map(|(path, _)| path)
+        } else {
+            self.fetch_into_store(&coord.pom_path()).await?
+        };
+        let pom_path = match pom {
+            Some(path) => path,
+            None if offline_mode() => {
+                return Err(ForgeError::OfflineArtifactMissing {
+                    artifact: format!("{} (POM)", coord.display()),
+                }
+                .into())
+            }
+            None => return Ok(Vec::new()), // POM no encontrado, no es error fatal
+        };
+
+        let pom_text = std::fs::read_to_string(&pom_path).map_err(|e| ForgeError::IoError {
+            path: pom_path.clone(),
             message: e.to_string(),
         })?;
+        Ok(MavenResolver::parse_pom_dependencies(&pom_text))
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cyrce_forge_core::config::RepositoryConfig;
+
+    /// Repositorio file:// y almacén temporales bajo `root`.
+    struct TestRepo {
+        root: PathBuf,
+    }
+
+    impl TestRepo {
+        fn new(name: &str) -> Self {
+            let root = std::env::temp_dir().join(name);
+            let _ = std::fs::remove_dir_all(&root);
+            std::fs::create_dir_all(root.join("project")).unwrap();
+            Self { root }
+        }
+
+        /// Publica `group:artifact:version` con sus dependencias compile.
+        fn publish(&self, coord: &str, dependencies: &[&str]) {
+            let parts: Vec<&str> = coord.split(':').collect();
+            let (group, artifact, version) = (parts[0], parts[1], parts[2]);
+            let dir = self
+                .root
+                .join("repo")
+                .join(group.replace('.', "/"))
+                .join(artifact)
+                .join(version);
+            std::fs::create_dir_all(&dir).unwrap();
+            let deps: String = dependencies
+                .iter()
+                .map(|dep| {
+                    let d: Vec<&str> = dep.split(':').collect();
+                    format!(
+                        "<dependency><groupId>{}</groupId><artifactId>{}</artifactId><version>{}</version></dependency>",
+                        d[0], d[1], d[2]
+                    )
+                })
+                .collect();
+            let pom = format!(
+                "<project><groupId>{}</groupId><artifactId>{}</artifactId><version>{}</version><dependencies>{}</dependencies></project>",
+                group, artifact, version, deps
+            );
+            std::fs::write(dir.join(format!("{}-{}.pom", artifact, version)), pom).unwrap();
+            std::fs::write(dir.join(format!("{}-{}.jar", artifact, version)), coord).unwrap();
+        }
+
+        /// Publica el build `timestamp-build` de `org.acme:util:1.0-SNAPSHOT` y su metadata.
+        fn publish_snapshot(&self, timestamp: &str, build: u32) {
+            let dir = self.root.join("repo/org/acme/util/1.0-SNAPSHOT");
+            std::fs::create_dir_all(&dir).unwrap();
+            let value = format!("1.0-{}-{}", timestamp, build);
+            let metadata = format!(
+                "<metadata><versioning><snapshot><timestamp>{ts}</timestamp><buildNumber>{b}</buildNumber></snapshot>\
+                 <snapshotVersions>\
+                 <snapshotVersion><extension>jar</extension><value>{v}</value></snapshotVersion>\
+                 <snapshotVersion><extension>pom</extension><value>{v}</value></snapshotVersion>\
+                 </snapshotVersions></versioning></metadata>",
+                ts = timestamp,
+                b = build,
+                v = value
+            );
+            std::fs::write(dir.join("maven-metadata.xml"), metadata).unwrap();
+            std::fs::write(
+                dir.join(format!("util-{}.pom", value)),
+                "<project><groupId>org.acme</groupId><artifactId>util</artifactId><version>1.0-SNAPSHOT</version></project>",
+            )
+            .unwrap();
+            std::fs::write(dir.join(format!("util-{}.jar", value)), format!("build-{}", build)).unwrap();
+        }
+
+        fn project_dir(&self) -> PathBuf {
+            self.root.join("project")
+        }
+
+        fn resolver(&self) -> MavenResolver {
+            self.resolver_with(ChecksumPolicy::Off)
+        }
+
+        fn resolver_with(&self, checksum: ChecksumPolicy) -> MavenResolver {
+            let mut config = ForgeConfig::default();
+            config.resolver.checksum = checksum;
+            config.repositories.push(RepositoryConfig {
+                name: "test".to_string(),
+                url: format!("file://{}", self.root.join("repo").display()),
+                username: None,
+                password: None,
+            });
+            let mut settings = UserSettings::default();
+            settings.store.path = Some(self.root.join("store").to_string_lossy().to_string());
+            MavenResolver::with_settings(&config, &self.project_dir(), &settings)
+                .unwrap()
+                .quiet()
+        }
+    }
+
+    fn file_names(jars: &[PathBuf]) -> Vec<String> {
+        jars.iter()
+            .map(|jar| jar.file_name().unwrap().to_string_lossy().to_string())
+            .collect()
+    }
+
+    impl Drop for TestRepo {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.root);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_graph_only_leaves_classpath_untouched() {
+        let repo = TestRepo::new("forge_test_maven_graph_only");
+        repo.publish("org.acme:app:1.0", &["org.acme:util:2.0"]);
+        repo.publish("org.acme:util:2.0", &[]);
+
+        let deps_dir = repo.project_dir().join(".forge").join("deps");
+        std::fs::create_dir_all(&deps_dir).unwrap();
+        std::fs::write(deps_dir.join(CLASSPATH_FILE), "/libs/local.jar").unwrap();
+        std::fs::write(deps_dir.join("suelto.jar"), "jar").unwrap();
+
+        let deps = HashMap::from([("org.acme:app".to_string(), "1.0".to_string())]);
+        let mut resolver = repo.resolver().graph_only();
+        resolver.resolve_all(&deps).await.unwrap();
+        resolver.resolve_test_deps(&deps).await.unwrap();
+
+        assert_eq!(resolver.graph().nodes.len(), 4);
+        assert_eq!(std::fs::read_to_string(deps_dir.join(CLASSPATH_FILE)).unwrap(), "/libs/local.jar");
+        assert!(deps_dir.join("suelto.jar").exists());
+        assert!(!repo.project_dir().join(".forge").join("test-deps").exists());
+    }
+
+    #[tokio::test]
+    async fn test_strict_checksum_mismatch_fails_without_storing() {
+        let repo = TestRepo::new("forge_test_maven_checksum_mismatch");
+        repo.publish("org.acme:util:2.0", &[]);
+        let jar = repo.root.join("repo/org/acme/util/2.0/util-2.0.jar");
+        std::fs::write(
+            jar.with_file_name("util-2.0.jar.sha1"),
+            "0000000000000000000000000000000000000000  util-2.0.jar",
+        )
+        .unwrap();
+
+        let deps = HashMap::from([("org.acme:util".to_string(), "2.0".to_string())]);
+        let err = repo
+            .resolver_with(ChecksumPolicy::Strict)
+            .resolve_all(&deps)
+            .await
+            .unwrap_err();
+        match err.downcast_ref::<ForgeError>() {
+            Some(ForgeError::ChecksumMismatch { expected, .. }) => {
+                assert_eq!(expected, "0000000000000000000000000000000000000000")
+            }
+            other => panic!("se esperaba ChecksumMismatch, no {:?}", other),
+        }
+        assert!(!repo.root.join("store/org/acme/util/2.0/util-2.0.jar").exists());
+
+        // En modo warn el mismo artefacto se acepta con un aviso
+        let mut resolver = repo.resolver_with(ChecksumPolicy::Warn);
+        assert_eq!(resolver.resolve_all(&deps).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_parallel_resolution_is_ordered_and_deduplicated() {
+        let repo = TestRepo::new("forge_test_maven_parallel");
+        repo.publish("org.acme:zeta:1.0", &["org.acme:shared:1.0"]);
+        repo.publish("org.acme:alpha:1.0", &["org.acme:shared:1.0", "org.acme:other:1.0"]);
+        repo.publish("org.acme:shared:1.0", &[]);
+        repo.publish("org.acme:other:1.0", &[]);
+
+        let deps = HashMap::from([
+            ("org.acme:zeta".to_string(), "1.0".to_string()),
+            ("org.acme:alpha".to_string(), "1.0".to_string()),
+        ]);
+        // Las descargas terminan en cualquier orden; el classpath no debe depender de eso
+        for _ in 0..5 {
+            let mut resolver = repo.resolver();
+            let jars = resolver.resolve_all(&deps).await.unwrap();
+            assert_eq!(
+                file_names(&jars),
+                ["alpha-1.0.jar", "zeta-1.0.jar", "shared-1.0.jar", "other-1.0.jar"]
+            );
+
+            let classpath =
+                std::fs::read_to_string(repo.project_dir().join(".forge/deps").join(CLASSPATH_FILE)).unwrap();
+            assert_eq!(classpath.lines().count(), 4);
+
+            // La aparición compartida bajo zeta queda mediada, sin volver a descargarse
+            let shared: Vec<&GraphNode> =
+                resolver.graph().nodes.iter().filter(|node| node.artifact == "shared").collect();
+            assert_eq!(shared.len(), 2);
+            assert_eq!(shared.iter().filter(|node| node.selection == Selection::Mediated).count(), 1);
+            assert_eq!(resolver.lock_entries().len(), 4);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_timestamped_snapshot_follows_update_policy() {
+        let repo = TestRepo::new("forge_test_maven_snapshot");
+        repo.publish_snapshot("20240315.101500", 7);
+        let deps = HashMap::from([("org.acme:util".to_string(), "1.0-SNAPSHOT".to_string())]);
+        let stored = repo.root.join("store/org/acme/util/1.0-SNAPSHOT/util-1.0-SNAPSHOT.jar");
+        let marker = stored.with_file_name("util-1.0-SNAPSHOT.jar.snapshot");
+
+        let jars = repo.resolver().resolve_all(&deps).await.unwrap();
+        assert_eq!(jars, std::slice::from_ref(&stored));
+        assert_eq!(std::fs::read_to_string(&stored).unwrap(), "build-7");
+        assert_eq!(std::fs::read_to_string(&marker).unwrap(), "1.0-20240315.101500-7");
+
+        // Un build nuevo no se ve mientras la copia local no haya vencido
+        repo.publish_snapshot("20240316.080000", 8);
+        let mut never = repo.resolver().with_update_policy(UpdatePolicy::Never);
+        never.resolve_all(&deps).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&stored).unwrap(), "build-7");
 
-        self.resolved.insert(key);
-        Ok(jar_path)
+        let mut always = repo.resolver().with_update_policy(UpdatePolicy::Always);
+        always.resolve_all(&deps).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&stored).unwrap(), "build-8");
+        assert_eq!(std::fs::read_to_string(&marker).unwrap(), "1.0-20240316.080000-8");
     }
 }
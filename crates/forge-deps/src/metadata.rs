@@ -0,0 +1,201 @@
+// =============================================================================
+// 🔥 FORGE — Resolución de Dependencias: maven-metadata.xml
+// =============================================================================
+// Cada artefacto publica `g/r/o/u/p/artifact/maven-metadata.xml` con todas
+// sus versiones (para rangos y latest/release), y cada versión SNAPSHOT
+// publica `.../1.0-SNAPSHOT/maven-metadata.xml` con el último build
+// con timestamp (`1.0-20240315.101500-7`).
+// =============================================================================
+
+use crate::version::MavenVersion;
+
+/// Contenido relevante de un maven-metadata.xml.
+#[derive(Debug, Clone, Default)]
+pub struct MavenMetadata {
+    /// `<versioning><latest>`
+    pub latest: Option<String>,
+    /// `<versioning><release>`
+    pub release: Option<String>,
+    /// `<versioning><versions><version>*`
+    pub versions: Vec<String>,
+    /// `<snapshot><timestamp>` y `<buildNumber>` (metadata de una versión SNAPSHOT)
+    pub snapshot: Option<(String, String)>,
+    /// `<snapshotVersions>`: (extensión, clasificador, valor con timestamp)
+    pub snapshot_versions: Vec<SnapshotVersion>,
+}
+
+/// Una entrada de `<snapshotVersions>`.
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotVersion {
+    pub extension: String,
+    pub classifier: String,
+    pub value: String,
+}
+
+impl MavenMetadata {
+    /// Parsea un maven-metadata.xml. Los campos ausentes quedan vacíos.
+    pub fn parse(xml: &str) -> Self {
+        let mut metadata = Self::default();
+        let mut reader = quick_xml::Reader::from_str(xml);
+        reader.config_mut().trim_text(true);
+
+        let mut stack: Vec<String> = Vec::new();
+        let mut timestamp = None;
+        let mut build_number = None;
+        let mut current = SnapshotVersion::default();
+        let mut buf = Vec::new();
+
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(quick_xml::events::Event::Start(ref e)) => {
+                    let tag = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                    if tag == "snapshotVersion" {
+                        current = SnapshotVersion::default();
+                    }
+                    stack.push(tag);
+                }
+                Ok(quick_xml::events::Event::End(_)) => {
+                    let closed = stack.pop();
+                    if closed.as_deref() == Some("snapshotVersion") {
+                        metadata.snapshot_versions.push(std::mem::take(&mut current));
+                    }
+                }
+                Ok(quick_xml::events::Event::Text(ref e)) => {
+                    let text = e.unescape().unwrap_or_default().to_string();
+                    let parent = stack.len().checked_sub(2).and_then(|i| stack.get(i));
+                    match (parent.map(String::as_str), stack.last().map(String::as_str)) {
+                        (Some("versioning"), Some("latest")) => metadata.latest = Some(text),
+                        (Some("versioning"), Some("release")) => metadata.release = Some(text),
+                        (Some("versions"), Some("version")) => metadata.versions.push(text),
+                        (Some("snapshot"), Some("timestamp")) => timestamp = Some(text),
+                        (Some("snapshot"), Some("buildNumber")) => build_number = Some(text),
+                        (Some("snapshotVersion"), Some("extension")) => current.extension = text,
+                        (Some("snapshotVersion"), Some("classifier")) => current.classifier = text,
+                        (Some("snapshotVersion"), Some("value")) => current.value = text,
+                        _ => {}
+                    }
+                }
+                Ok(quick_xml::events::Event::Eof) => break,
+                Err(_) => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        if let (Some(ts), Some(build)) = (timestamp, build_number) {
+            metadata.snapshot = Some((ts, build));
+        }
+        metadata
+    }
+
+    /// Combina la metadata de otro repositorio (unión de versiones). De `<latest>` y
+    /// `<release>` queda la más nueva: un mirror interno desactualizado, consultado
+    /// antes que central, no debe fijar una versión vieja.
+    pub fn merge(&mut self, other: MavenMetadata) {
+        for version in other.versions {
+            if !self.versions.contains(&version) {
+                self.versions.push(version);
+            }
+        }
+        self.latest = newest(self.latest.take(), other.latest);
+        self.release = newest(self.release.take(), other.release);
+    }
+
+    /// Versión con timestamp del último build SNAPSHOT para una extensión (jar, pom).
+    /// `None` si el repositorio publica SNAPSHOTs sin timestamp.
+    pub fn snapshot_value(&self, base_version: &str, extension: &str) -> Option<String> {
+        self.snapshot_versions
+            .iter()
+            .find(|s| s.extension == extension && s.classifier.is_empty())
+            .map(|s| s.value.clone())
+            .or_else(|| {
+                self.snapshot.as_ref().map(|(timestamp, build)| {
+                    base_version.replace("SNAPSHOT", &format!("{}-{}", timestamp, build))
+                })
+            })
+    }
+}
+
+/// La mayor de dos versiones opcionales según el orden de Maven.
+fn newest(current: Option<String>, other: Option<String>) -> Option<String> {
+    match (current, other) {
+        (Some(current), Some(other)) if MavenVersion::parse(&other) > MavenVersion::parse(&current) => Some(other),
+        (current, other) => current.or(other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::version::VersionSpec;
+
+    fn artifact_metadata(latest: &str, release: &str, versions: &[&str]) -> String {
+        let versions: String = versions.iter().map(|v| format!("<version>{}</version>", v)).collect();
+        format!(
+            "<metadata><groupId>org.acme</groupId><artifactId>util</artifactId><versioning>\
+             <latest>{}</latest><release>{}</release><versions>{}</versions>\
+             <lastUpdated>20240315101500</lastUpdated></versioning></metadata>",
+            latest, release, versions
+        )
+    }
+
+    #[test]
+    fn test_parse_artifact_metadata() {
+        let metadata = MavenMetadata::parse(&artifact_metadata("2.1-SNAPSHOT", "2.0", &["1.0", "2.0", "2.1-SNAPSHOT"]));
+        assert_eq!(metadata.latest.as_deref(), Some("2.1-SNAPSHOT"));
+        assert_eq!(metadata.release.as_deref(), Some("2.0"));
+        assert_eq!(metadata.versions, ["1.0", "2.0", "2.1-SNAPSHOT"]);
+        assert!(metadata.snapshot.is_none());
+        assert!(metadata.snapshot_versions.is_empty());
+
+        let empty = MavenMetadata::parse("<html>404</html>");
+        assert!(empty.latest.is_none() && empty.versions.is_empty());
+    }
+
+    #[test]
+    fn test_snapshot_value() {
+        let xml = r#"<metadata><version>1.0-SNAPSHOT</version><versioning>
+            <snapshot><timestamp>20240315.101500</timestamp><buildNumber>7</buildNumber></snapshot>
+            <snapshotVersions>
+              <snapshotVersion><classifier>sources</classifier><extension>jar</extension><value>1.0-20240314.090000-6</value></snapshotVersion>
+              <snapshotVersion><extension>jar</extension><value>1.0-20240315.101500-7</value></snapshotVersion>
+              <snapshotVersion><extension>pom</extension><value>1.0-20240315.101400-7</value></snapshotVersion>
+            </snapshotVersions></versioning></metadata>"#;
+        let metadata = MavenMetadata::parse(xml);
+        assert_eq!(metadata.snapshot, Some(("20240315.101500".to_string(), "7".to_string())));
+        assert_eq!(metadata.snapshot_versions.len(), 3);
+
+        // El JAR sin clasificador, no el de fuentes
+        assert_eq!(metadata.snapshot_value("1.0-SNAPSHOT", "jar").as_deref(), Some("1.0-20240315.101500-7"));
+        assert_eq!(metadata.snapshot_value("1.0-SNAPSHOT", "pom").as_deref(), Some("1.0-20240315.101400-7"));
+
+        // Metadata antigua, solo con <snapshot>: timestamp y build reemplazan a SNAPSHOT
+        let legacy = MavenMetadata::parse(
+            "<metadata><versioning><snapshot><timestamp>20240101.000000</timestamp>\
+             <buildNumber>3</buildNumber></snapshot></versioning></metadata>",
+        );
+        assert_eq!(legacy.snapshot_value("1.0-SNAPSHOT", "jar").as_deref(), Some("1.0-20240101.000000-3"));
+
+        // Repositorio que publica SNAPSHOTs sin timestamp
+        assert_eq!(MavenMetadata::parse("<metadata/>").snapshot_value("1.0-SNAPSHOT", "jar"), None);
+    }
+
+    #[test]
+    fn test_merge_keeps_newest_latest_and_release() {
+        // Mirror interno (se consulta primero) desactualizado frente a central
+        let mut merged = MavenMetadata::parse(&artifact_metadata("1.5", "1.5", &["1.0", "1.5"]));
+        merged.merge(MavenMetadata::parse(&artifact_metadata("2.1-SNAPSHOT", "2.0", &["1.5", "2.0", "2.1-SNAPSHOT"])));
+
+        assert_eq!(merged.versions, ["1.0", "1.5", "2.0", "2.1-SNAPSHOT"]);
+        assert_eq!(merged.latest.as_deref(), Some("2.1-SNAPSHOT"));
+        assert_eq!(merged.release.as_deref(), Some("2.0"));
+        assert_eq!(VersionSpec::parse("LATEST").unwrap().select(&merged).as_deref(), Some("2.1-SNAPSHOT"));
+        assert_eq!(VersionSpec::parse("RELEASE").unwrap().select(&merged).as_deref(), Some("2.0"));
+
+        // En el otro orden el resultado es el mismo, y un lado sin <release> no borra el otro
+        let mut reversed = MavenMetadata::parse(&artifact_metadata("2.1-SNAPSHOT", "2.0", &["2.0"]));
+        reversed.merge(MavenMetadata::parse("<metadata><versioning><latest>1.5</latest></versioning></metadata>"));
+        assert_eq!(reversed.latest.as_deref(), Some("2.1-SNAPSHOT"));
+        assert_eq!(reversed.release.as_deref(), Some("2.0"));
+    }
+}
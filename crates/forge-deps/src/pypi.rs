@@ -8,6 +8,10 @@
 use colored::Colorize;
 
 use cyrce_forge_core::error::{ForgeError, ForgeResult};
+use cyrce_forge_core::settings::offline_mode;
+
+use crate::lockfile::{LockedPypi, Lockfile};
+use crate::version::Upgrades;
 
 /// URL base de la API JSON de PyPI.
 const PYPI_API_URL: &str = "https://pypi.org/pypi";
@@ -16,6 +20,14 @@ const PYPI_API_URL: &str = "https://pypi.org/pypi";
 #[derive(Debug, serde::Deserialize)]
 pub struct PypiPackageInfo {
     pub info: PypiInfo,
+
+    /// Archivos publicados para la versión consultada
+    #[serde(default)]
+    pub urls: Vec<PypiFile>,
+
+    /// Todas las versiones publicadas con sus archivos (solo en la consulta sin versión)
+    #[serde(default)]
+    pub releases: std::collections::HashMap<String, Vec<PypiFile>>,
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -25,6 +37,36 @@ pub struct PypiInfo {
     pub summary: Option<String>,
 }
 
+/// Un archivo de distribución (sdist o wheel) de una versión.
+#[derive(Debug, serde::Deserialize)]
+pub struct PypiFile {
+    pub filename: String,
+    pub packagetype: String,
+    pub digests: PypiDigests,
+
+    /// Retirado por el autor (PEP 592)
+    #[serde(default)]
+    pub yanked: bool,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct PypiDigests {
+    pub sha256: Option<String>,
+}
+
+impl PypiPackageInfo {
+    /// Checksum que se registra en forge.lock: el del sdist si existe,
+    /// si no el del primer wheel en orden alfabético.
+    pub fn checksum(&self) -> Option<String> {
+        let mut files: Vec<&PypiFile> = self.urls.iter().collect();
+        files.sort_by_key(|f| (f.packagetype != "sdist", f.filename.as_str()));
+        files
+            .into_iter()
+            .find_map(|f| f.digests.sha256.as_ref())
+            .map(|hash| format!("sha256:{}", hash))
+    }
+}
+
 /// Resolver de dependencias PyPI.
 pub struct PypiResolver {
     client: reqwest::Client,
@@ -80,11 +122,33 @@ impl PypiResolver {
         Ok(info)
     }
 
-    /// Verifica todas las dependencias Python del proyecto.
-    pub async fn verify_all(
+    /// Versiones publicadas de un paquete, sin las que no tienen archivos o fueron retiradas.
+    pub async fn available_versions(&self, name: &str) -> ForgeResult<Vec<String>> {
+        if offline_mode() {
+            return Err(ForgeError::OfflineArtifactMissing {
+                artifact: format!("{} (índice de PyPI)", name),
+            }
+            .into());
+        }
+
+        let info = self.verify_package(name, "*").await?;
+        Ok(info
+            .releases
+            .into_iter()
+            .filter(|(_, files)| files.iter().any(|f| !f.yanked))
+            .map(|(version, _)| version)
+            .collect())
+    }
+
+    /// Verifica todas las dependencias Python del proyecto y devuelve la versión exacta
+    /// elegida para cada una. Los paquetes con "*" usan la versión de forge.lock si existe
+    /// (salvo que estén en `update`); si no, la última publicada.
+    pub async fn resolve_all(
         &self,
         dependencies: &std::collections::HashMap<String, String>,
-    ) -> ForgeResult<()> {
+        lock: Option<&Lockfile>,
+        update: &[String],
+    ) -> ForgeResult<Vec<LockedPypi>> {
         println!(
             "   {}",
             format!(
@@ -94,30 +158,91 @@ impl PypiResolver {
             .cyan()
         );
 
-        for (name, version) in dependencies {
+        let mut names: Vec<&String> = dependencies.keys().collect();
+        names.sort();
+
+        if offline_mode() {
+            return Self::resolve_offline(dependencies, &names, lock);
+        }
+
+        let mut locked = Vec::new();
+        let mut failed = Vec::new();
+        for name in names {
+            let requested = dependencies[name].as_str();
+            let floating = requested == "*" || requested.is_empty();
+            let version = match lock.and_then(|l| l.pypi_pin(name, update)) {
+                Some(pinned) if floating => pinned,
+                _ => requested,
+            };
+
             match self.verify_package(name, version).await {
                 Ok(info) => {
                     println!(
                         "   {}",
                         format!(
-                            "   ✅ {} v{} — {}",
+                            "✅ {} v{} — {}",
                             info.info.name,
                             info.info.version,
                             info.info.summary.as_deref().unwrap_or("Sin descripción")
                         )
                         .dimmed()
                     );
+                    locked.push(LockedPypi {
+                        name: name.clone(),
+                        version: info.info.version.clone(),
+                        checksum: info.checksum(),
+                        path: Vec::new(),
+                    });
                 }
                 Err(e) => {
                     println!(
                         "   {}",
-                        format!("   ⚠️  {}: {}", name, e).yellow()
+                        format!("❌ {}: {}", name, e).red()
                     );
+                    failed.push(format!("{} {}", name, version));
                 }
             }
         }
 
-        Ok(())
+        // Un paquete sin verificar no puede quedar fuera de forge.lock en silencio
+        if !failed.is_empty() {
+            return Err(ForgeError::DependencyResolutionFailed {
+                dependency: failed.join(", "),
+            }
+            .into());
+        }
+
+        Ok(locked)
+    }
+
+    /// Sin red, PyPI no se puede consultar: cada paquete debe estar fijado en forge.lock
+    /// con una versión compatible con la pedida en forge.toml.
+    fn resolve_offline(
+        dependencies: &std::collections::HashMap<String, String>,
+        names: &[&String],
+        lock: Option<&Lockfile>,
+    ) -> ForgeResult<Vec<LockedPypi>> {
+        println!("   {}", "📴 Modo offline: usando versiones de forge.lock".dimmed());
+
+        names
+            .iter()
+            .map(|name| {
+                let requested = dependencies[*name].as_str();
+                lock.into_iter()
+                    .flat_map(|l| l.pypi.iter())
+                    .find(|entry| {
+                        entry.name.eq_ignore_ascii_case(name)
+                            && (requested == "*" || requested.is_empty() || entry.version == requested)
+                    })
+                    .cloned()
+                    .ok_or_else(|| {
+                        ForgeError::OfflineArtifactMissing {
+                            artifact: format!("{} {} (PyPI)", name, requested),
+                        }
+                        .into()
+                    })
+            })
+            .collect()
     }
 }
 
@@ -126,3 +251,240 @@ impl Default for PypiResolver {
         Self::new()
     }
 }
+
+/// Versión de PyPI según PEP 440 (`1!2.0.1rc1.post2.dev3+local`). Las reglas de Maven
+/// no sirven aquí: `2.0rc1` y `1.0.dev1` son pre-releases y `1.0.post1` va después de `1.0`.
+#[derive(Debug, Clone)]
+pub struct Pep440Version {
+    original: String,
+    epoch: u64,
+    release: Vec<u64>,
+    /// (a = 0, b = 1, rc = 2, número)
+    pre: Option<(u8, u64)>,
+    post: Option<u64>,
+    dev: Option<u64>,
+}
+
+impl Pep440Version {
+    /// Parsea una versión PEP 440 (también las formas no normalizadas: `1.0-RC1`,
+    /// `1.0-1`, `v2.0`). `None` si no lo es.
+    pub fn parse(version: &str) -> Option<Self> {
+        let lower = version.trim().to_ascii_lowercase();
+        let public = lower.split('+').next().unwrap_or_default();
+        let public = public.strip_prefix('v').unwrap_or(public);
+        let (epoch, rest) = match public.split_once('!') {
+            Some((epoch, rest)) => (epoch.parse().ok()?, rest),
+            None => (0, public),
+        };
+
+        let release_end = rest
+            .char_indices()
+            .find(|&(i, c)| !(c.is_ascii_digit() || (c == '.' && rest[i + 1..].starts_with(|n: char| n.is_ascii_digit()))))
+            .map_or(rest.len(), |(i, _)| i);
+        let release = rest[..release_end]
+            .split('.')
+            .map(|n| n.parse().ok())
+            .collect::<Option<Vec<u64>>>()?;
+
+        let mut parsed = Self {
+            original: version.trim().to_string(),
+            epoch,
+            release,
+            pre: None,
+            post: None,
+            dev: None,
+        };
+
+        let mut rest = &rest[release_end..];
+        while !rest.is_empty() {
+            let implicit_post = rest.starts_with('-');
+            rest = rest.trim_start_matches(['-', '_', '.']);
+            let word_end = rest.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(rest.len());
+            let word = &rest[..word_end];
+            rest = &rest[word_end..];
+            if !word.is_empty() {
+                rest = rest.strip_prefix(['-', '_', '.']).filter(|r| r.starts_with(|c: char| c.is_ascii_digit())).unwrap_or(rest);
+            }
+            let digits_end = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+            let number = if digits_end == 0 { 0 } else { rest[..digits_end].parse().ok()? };
+            rest = &rest[digits_end..];
+
+            match word {
+                "a" | "alpha" => parsed.pre = Some((0, number)),
+                "b" | "beta" => parsed.pre = Some((1, number)),
+                "c" | "rc" | "pre" | "preview" => parsed.pre = Some((2, number)),
+                "post" | "rev" | "r" => parsed.post = Some(number),
+                "dev" => parsed.dev = Some(number),
+                "" if implicit_post && digits_end > 0 => parsed.post = Some(number),
+                _ => return None,
+            }
+        }
+        Some(parsed)
+    }
+
+    /// ¿Pre-release (`a`, `b`, `rc`) o build de desarrollo (`.dev`)?
+    pub fn is_prerelease(&self) -> bool {
+        self.pre.is_some() || self.dev.is_some()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.original
+    }
+
+    /// Componente `index` del release (0 = mayor, 1 = menor); los que faltan valen 0.
+    fn number(&self, index: usize) -> u64 {
+        self.release.get(index).copied().unwrap_or(0)
+    }
+
+    /// Clave de orden de PEP 440: un `.devN` sin pre-release va antes que cualquier
+    /// pre-release, y la ausencia de `.dev` va después de cualquier `.devN`.
+    fn key(&self) -> Pep440Key {
+        let mut release = self.release.clone();
+        while release.len() > 1 && release.last() == Some(&0) {
+            release.pop();
+        }
+        let pre = match (self.pre, self.post, self.dev) {
+            (Some((kind, number)), _, _) => (kind + 1, number),
+            (None, None, Some(_)) => (0, 0),
+            _ => (4, 0),
+        };
+        let post = (self.post.is_some(), self.post.unwrap_or(0));
+        let dev = (self.dev.is_none(), self.dev.unwrap_or(0));
+        (self.epoch, release, pre, post, dev)
+    }
+}
+
+/// (época, release sin ceros finales, pre-release, post-release, dev-release)
+type Pep440Key = (u64, Vec<u64>, (u8, u64), (bool, u64), (bool, u64));
+
+impl PartialEq for Pep440Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Pep440Version {}
+
+impl PartialOrd for Pep440Version {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Pep440Version {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+/// Actualizaciones de un paquete PyPI entre las versiones publicadas, con el orden de
+/// PEP 440 y sin pre-releases ni builds `.dev` (las `.postN` sí cuentan).
+pub fn upgrades(current: &str, available: &[String]) -> Upgrades {
+    let Some(current) = Pep440Version::parse(current) else {
+        return Upgrades::default();
+    };
+    let mut candidates: Vec<Pep440Version> = available
+        .iter()
+        .filter_map(|v| Pep440Version::parse(v))
+        .filter(|v| !v.is_prerelease() && *v > current)
+        .collect();
+    candidates.sort();
+
+    let newest = |filter: &dyn Fn(&Pep440Version) -> bool| {
+        candidates
+            .iter()
+            .rev()
+            .find(|v| filter(v))
+            .map(|v| v.as_str().to_string())
+    };
+
+    Upgrades {
+        patch: newest(&|v| v.number(0) == current.number(0) && v.number(1) == current.number(1)),
+        minor: newest(&|v| v.number(0) == current.number(0)),
+        major: newest(&|_| true),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locked(name: &str, version: &str) -> LockedPypi {
+        LockedPypi {
+            name: name.to_string(),
+            version: version.to_string(),
+            checksum: Some(format!("sha256:{}", "ab".repeat(32))),
+            path: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_offline_uses_compatible_lock_entries() {
+        let lock = Lockfile {
+            version: 1,
+            maven: Vec::new(),
+            pypi: vec![locked("Requests", "2.31.0"), locked("numpy", "1.26.4")],
+        };
+        let dependencies = std::collections::HashMap::from([
+            ("requests".to_string(), "*".to_string()),
+            ("numpy".to_string(), "1.26.4".to_string()),
+        ]);
+        let requests = "requests".to_string();
+        let numpy = "numpy".to_string();
+
+        let resolved =
+            PypiResolver::resolve_offline(&dependencies, &[&numpy, &requests], Some(&lock)).unwrap();
+        assert_eq!(resolved, vec![locked("numpy", "1.26.4"), locked("Requests", "2.31.0")]);
+
+        // Una versión distinta a la fijada, o sin forge.lock, no se puede resolver sin red
+        let pinned_elsewhere = std::collections::HashMap::from([("numpy".to_string(), "2.0.0".to_string())]);
+        for lock in [Some(&lock), None] {
+            let err = PypiResolver::resolve_offline(&pinned_elsewhere, &[&numpy], lock).unwrap_err();
+            assert!(matches!(
+                err.downcast_ref::<ForgeError>(),
+                Some(ForgeError::OfflineArtifactMissing { artifact }) if artifact == "numpy 2.0.0 (PyPI)"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_pep440_ordering() {
+        let ordered = [
+            "1.0.dev1", "1.0a1", "1.0a2.dev1", "1.0a2", "1.0b1", "1.0rc1", "1.0", "1.0.post1.dev1", "1.0.post1",
+            "1.0.1", "1.1", "2!0.1",
+        ];
+        let parsed: Vec<Pep440Version> = ordered.iter().map(|v| Pep440Version::parse(v).unwrap()).collect();
+        for pair in parsed.windows(2) {
+            assert!(pair[0] < pair[1], "{} < {}", pair[0].as_str(), pair[1].as_str());
+        }
+
+        let parse = |v: &str| Pep440Version::parse(v).unwrap();
+        assert_eq!(parse("1.0"), parse("1.0.0"));
+        assert_eq!(parse("1.0-RC1"), parse("1.0rc1"));
+        assert_eq!(parse("1.0-1"), parse("1.0.post1"));
+        assert_eq!(parse("v2.0+ubuntu1"), parse("2.0"));
+        assert!(parse("2.0rc1").is_prerelease() && parse("1.0.dev1").is_prerelease());
+        assert!(!parse("1.0.post1").is_prerelease());
+        assert!(Pep440Version::parse("1.0-jre").is_none());
+    }
+
+    #[test]
+    fn test_upgrades_skip_prereleases() {
+        let available: Vec<String> = ["1.0", "1.0.post1", "1.1", "1.2rc1", "2.0.dev3", "2.0rc1", "1.1.1", "1.1.2b1"]
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
+        assert_eq!(
+            upgrades("1.1", &available),
+            Upgrades {
+                patch: Some("1.1.1".into()),
+                minor: Some("1.1.1".into()),
+                major: Some("1.1.1".into()),
+            }
+        );
+        // Una .postN es una versión final más nueva que la base
+        assert_eq!(upgrades("1.0", &available).patch.as_deref(), Some("1.0.post1"));
+        assert_eq!(upgrades("1.0.post1", &available).minor.as_deref(), Some("1.1.1"));
+        assert_eq!(upgrades("no-es-versión", &available), Upgrades::default());
+    }
+}
@@ -0,0 +1,320 @@
+// =============================================================================
+// 🔥 FORGE — Resolución de Dependencias: Repositorios Maven
+// =============================================================================
+// Lista ordenada de repositorios (forge.toml + Maven Central), con mirrors
+// de ~/.forge/settings.toml, autenticación básica y soporte para file://.
+// =============================================================================
+
+use cyrce_forge_core::config::ForgeConfig;
+use cyrce_forge_core::error::{ForgeError, ForgeResult};
+use cyrce_forge_core::settings::{expand_env, offline_mode, Credentials, UserSettings};
+
+/// URL base de Maven Central.
+pub const MAVEN_CENTRAL_URL: &str = "https://repo1.maven.org/maven2";
+
+/// Nombre reservado para Maven Central.
+pub const MAVEN_CENTRAL_NAME: &str = "central";
+
+/// Repositorio Maven listo para consultar.
+#[derive(Debug, Clone)]
+pub struct MavenRepository {
+    /// Nombre declarado (o "central")
+    pub name: String,
+    /// URL efectiva, ya con el mirror aplicado y sin '/' final
+    pub url: String,
+    /// Credenciales para autenticación básica
+    credentials: Option<Credentials>,
+}
+
+impl MavenRepository {
+    /// Repositorio Maven Central sin mirrors.
+    pub fn central() -> Self {
+        Self {
+            name: MAVEN_CENTRAL_NAME.to_string(),
+            url: MAVEN_CENTRAL_URL.to_string(),
+            credentials: None,
+        }
+    }
+
+    /// Construye la lista ordenada de repositorios del proyecto.
+    /// Maven Central se agrega al final salvo que el proyecto declare uno llamado "central".
+    pub fn from_config(config: &ForgeConfig, settings: &UserSettings) -> ForgeResult<Vec<Self>> {
+        let mut repos = Vec::new();
+
+        for repo in &config.repositories {
+            let inline = match (&repo.username, &repo.password) {
+                (Some(user), Some(pass)) => Some(Credentials {
+                    username: expand_env(user)?,
+                    password: expand_env(pass)?,
+                }),
+                _ => None,
+            };
+            repos.push(Self::with_mirror(&repo.name, &repo.url, inline, settings)?);
+        }
+
+        if !repos.iter().any(|r| r.name == MAVEN_CENTRAL_NAME) {
+            repos.push(Self::with_mirror(
+                MAVEN_CENTRAL_NAME,
+                MAVEN_CENTRAL_URL,
+                None,
+                settings,
+            )?);
+        }
+
+        Ok(repos)
+    }
+
+    /// Aplica el mirror correspondiente y busca credenciales en credentials.toml.
+    fn with_mirror(
+        name: &str,
+        url: &str,
+        inline: Option<Credentials>,
+        settings: &UserSettings,
+    ) -> ForgeResult<Self> {
+        let (url, credentials) = match settings.mirror_for(name) {
+            Some(mirror) => (
+                mirror.url.clone(),
+                UserSettings::credentials_for(&mirror.name)?,
+            ),
+            None => (
+                url.to_string(),
+                match inline {
+                    Some(c) => Some(c),
+                    None => UserSettings::credentials_for(name)?,
+                },
+            ),
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            url: url.trim_end_matches('/').to_string(),
+            credentials,
+        })
+    }
+
+    /// URL completa de un archivo relativo al layout Maven.
+    pub fn url_for(&self, path: &str) -> String {
+        format!("{}/{}", self.url, path)
+    }
+
+    /// Descarga un archivo del repositorio. Devuelve `None` si no existe (404 o archivo ausente).
+    /// En modo offline los repositorios remotos se tratan como vacíos; los `file://` se siguen leyendo.
+    pub async fn fetch(&self, client: &reqwest::Client, path: &str) -> ForgeResult<Option<Vec<u8>>> {
+        let url = self.url_for(path);
+
+        if url.starts_with("file:") {
+            // Url decodifica %XX y entiende file:///C:/... en Windows
+            let local = reqwest::Url::parse(&url)
+                .ok()
+                .and_then(|parsed| parsed.to_file_path().ok())
+                .ok_or_else(|| ForgeError::DownloadError {
+                    url: url.clone(),
+                    message: "URL file:// inválida".to_string(),
+                })?;
+            return match std::fs::read(&local) {
+                Ok(bytes) => Ok(Some(bytes)),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(ForgeError::IoError {
+                    path: local,
+                    message: e.to_string(),
+                }
+                .into()),
+            };
+        }
+
+        if offline_mode() {
+            return Ok(None);
+        }
+
+        let mut request = client.get(&url);
+        if let Some(creds) = &self.credentials {
+            request = request.basic_auth(&creds.username, Some(&creds.password));
+        }
+
+        let response = request.send().await.map_err(|e| ForgeError::DownloadError {
+            url: url.clone(),
+            message: e.to_string(),
+        })?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        if !response.status().is_success() {
+            return Err(ForgeError::DownloadError {
+                url,
+                message: format!("HTTP {}", response.status()),
+            }
+            .into());
+        }
+
+        let bytes = response.bytes().await.map_err(|e| ForgeError::DownloadError {
+            url: url.clone(),
+            message: e.to_string(),
+        })?;
+
+        Ok(Some(bytes.to_vec()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cyrce_forge_core::settings::OFFLINE_ENV;
+
+    #[tokio::test]
+    async fn test_fetch_offline_skips_remote_but_reads_file_repos() {
+        let dir = std::env::temp_dir().join("forge_test_repository_offline");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("util-1.0.pom"), "<project/>").unwrap();
+
+        // Nada escucha en el puerto 9: sin modo offline la descarga fallaría
+        let remote = MavenRepository {
+            name: "remoto".to_string(),
+            url: "http://127.0.0.1:9".to_string(),
+            credentials: None,
+        };
+        let local = MavenRepository {
+            name: "local".to_string(),
+            url: format!("file://{}", dir.display()),
+            credentials: None,
+        };
+        let client = reqwest::Client::new();
+
+        // Los demás tests del crate solo usan repositorios file://, a los que no afecta
+        std::env::set_var(OFFLINE_ENV, "1");
+        let offline_remote = remote.fetch(&client, "util-1.0.pom").await;
+        let offline_local = local.fetch(&client, "util-1.0.pom").await;
+        std::env::remove_var(OFFLINE_ENV);
+
+        assert_eq!(offline_remote.unwrap(), None);
+        assert_eq!(offline_local.unwrap(), Some(b"<project/>".to_vec()));
+        assert!(remote.fetch(&client, "util-1.0.pom").await.is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+    #[tokio::test]
+    async fn test_fetch_decodes_file_urls() {
+        let dir = std::env::temp_dir().join("forge_test_repository file url");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("util-1.0.pom"), "<project/>").unwrap();
+
+        let encoded = reqwest::Url::from_directory_path(&dir).unwrap().to_string();
+        assert!(encoded.contains("%20"));
+        let client = reqwest::Client::new();
+        for url in [encoded, format!("file://{}", dir.display())] {
+            let repo = MavenRepository {
+                name: "local".to_string(),
+                url: url.trim_end_matches('/').to_string(),
+                credentials: None,
+            };
+            assert_eq!(repo.fetch(&client, "util-1.0.pom").await.unwrap(), Some(b"<project/>".to_vec()));
+            assert_eq!(repo.fetch(&client, "util-2.0.pom").await.unwrap(), None);
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn credentials(repo: &MavenRepository) -> Option<(&str, &str)> {
+        repo.credentials
+            .as_ref()
+            .map(|c| (c.username.as_str(), c.password.as_str()))
+    }
+
+    #[test]
+    fn test_from_config_mirror_and_credentials_precedence() {
+        let home = std::env::temp_dir().join("forge_test_repository_precedence");
+        let _ = std::fs::remove_dir_all(&home);
+        std::fs::create_dir_all(&home).unwrap();
+        std::fs::write(
+            home.join("credentials.toml"),
+            r#"[corp]
+username = "mirror-bot"
+password = "m"
+
+[interno-mirror]
+username = "interno-bot"
+password = "i"
+
+[interno]
+username = "repo-bot"
+password = "r"
+
+[releases]
+username = "releases-bot"
+password = "${FORGE_TEST_RELEASES_PASSWORD}"
+"#,
+        )
+        .unwrap();
+
+        let config: ForgeConfig = toml::from_str(
+            r#"[project]
+name = "app"
+lang = "java"
+
+[[repositories]]
+name = "interno"
+url = "https://repo.interno/maven/"
+username = "inline-bot"
+password = "${FORGE_TEST_INLINE_PASSWORD}"
+
+[[repositories]]
+name = "releases"
+url = "https://releases.example/maven"
+"#,
+        )
+        .unwrap();
+        let mirrored: UserSettings = toml::from_str(
+            r#"[[mirrors]]
+name = "corp"
+mirror-of = "*"
+url = "https://nexus.corp/maven-public/"
+
+[[mirrors]]
+name = "interno-mirror"
+mirror-of = "interno"
+url = "file:///srv/interno"
+"#,
+        )
+        .unwrap();
+
+        // credentials.toml se lee de FORGE_HOME; ningún otro test del crate lo usa
+        std::env::set_var("FORGE_HOME", &home);
+        std::env::set_var("FORGE_TEST_RELEASES_PASSWORD", "p");
+        let missing_inline = MavenRepository::from_config(&config, &UserSettings::default());
+        std::env::set_var("FORGE_TEST_INLINE_PASSWORD", "inline");
+        let with_mirrors = MavenRepository::from_config(&config, &mirrored);
+        let without_mirrors = MavenRepository::from_config(&config, &UserSettings::default());
+        std::env::remove_var("FORGE_HOME");
+        std::env::remove_var("FORGE_TEST_RELEASES_PASSWORD");
+        std::env::remove_var("FORGE_TEST_INLINE_PASSWORD");
+        std::fs::remove_dir_all(&home).unwrap();
+
+        assert!(matches!(
+            missing_inline.unwrap_err().downcast_ref::<ForgeError>(),
+            Some(ForgeError::EnvVarNotSet { var }) if var == "FORGE_TEST_INLINE_PASSWORD"
+        ));
+
+        // El mirror exacto gana al comodín y sus credenciales reemplazan a las del repositorio
+        let repos = with_mirrors.unwrap();
+        let names: Vec<&str> = repos.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["interno", "releases", MAVEN_CENTRAL_NAME]);
+        assert_eq!(repos[0].url, "file:///srv/interno");
+        assert_eq!(credentials(&repos[0]), Some(("interno-bot", "i")));
+        assert_eq!(repos[1].url, "https://nexus.corp/maven-public");
+        assert_eq!(credentials(&repos[1]), Some(("mirror-bot", "m")));
+        assert_eq!(repos[2].url, "https://nexus.corp/maven-public");
+        assert_eq!(credentials(&repos[2]), Some(("mirror-bot", "m")));
+
+        // Sin mirrors: las credenciales en línea ganan a credentials.toml
+        let repos = without_mirrors.unwrap();
+        assert_eq!(repos[0].url, "https://repo.interno/maven");
+        assert_eq!(credentials(&repos[0]), Some(("inline-bot", "inline")));
+        assert_eq!(repos[1].url, "https://releases.example/maven");
+        assert_eq!(credentials(&repos[1]), Some(("releases-bot", "p")));
+        assert_eq!(repos[2].url, MAVEN_CENTRAL_URL);
+        assert_eq!(credentials(&repos[2]), None);
+    }
+}
@@ -0,0 +1,277 @@
+// =============================================================================
+// 🔥 FORGE — Resolución de Dependencias: Almacén Global de Artefactos
+// =============================================================================
+// Almacén compartido entre todos los proyectos (~/.forge/repository) con el
+// mismo layout que un repositorio Maven. Opcionalmente lee ~/.m2/repository
+// (solo lectura). Las escrituras se serializan con un lock por artefacto para
+// que builds paralelos de varios módulos no corrompan el almacén.
+// =============================================================================
+
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+
+use fs4::fs_std::FileExt;
+
+use cyrce_forge_core::error::{ForgeError, ForgeResult};
+use cyrce_forge_core::settings::UserSettings;
+
+/// Almacén global de artefactos en layout Maven.
+#[derive(Debug, Clone)]
+pub struct ArtifactStore {
+    /// Raíz escribible del almacén
+    root: PathBuf,
+    /// ~/.m2/repository, consultado en modo solo lectura
+    m2: Option<PathBuf>,
+}
+
+/// Lock exclusivo sobre un artefacto del almacén. Se libera al salir de scope.
+pub struct StoreLock {
+    file: File,
+}
+
+impl Drop for StoreLock {
+    fn drop(&mut self) {
+        let _ = FileExt::unlock(&self.file);
+    }
+}
+
+impl ArtifactStore {
+    /// Crea el almacén según la configuración del usuario.
+    pub fn new(settings: &UserSettings) -> ForgeResult<Self> {
+        Ok(Self {
+            root: settings.store_dir()?,
+            m2: settings.m2_dir(),
+        })
+    }
+
+    /// Raíz escribible del almacén.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Ruta absoluta donde FORGE guarda un archivo relativo al layout Maven.
+    pub fn path_for(&self, relative: &str) -> PathBuf {
+        self.root.join(relative)
+    }
+
+    /// Busca un archivo ya presente, primero en el almacén y luego en ~/.m2.
+    pub fn find(&self, relative: &str) -> Option<PathBuf> {
+        let own = self.path_for(relative);
+        if own.exists() {
+            return Some(own);
+        }
+
+        self.m2
+            .as_ref()
+            .map(|m2| m2.join(relative))
+            .filter(|path| path.exists())
+    }
+
+    /// Adquiere un lock exclusivo para escribir un artefacto.
+    /// Bloquea hasta que otro proceso (u otro módulo del workspace) termine de escribirlo.
+    pub async fn lock(&self, relative: &str) -> ForgeResult<StoreLock> {
+        let target = self.path_for(relative);
+        let lock_path = target.with_file_name(format!(
+            "{}.lock",
+            target
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default()
+        ));
+
+        if let Some(parent) = lock_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| ForgeError::IoError {
+                path: parent.to_path_buf(),
+                message: e.to_string(),
+            })?;
+        }
+
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_path)
+            .map_err(|e| ForgeError::IoError {
+                path: lock_path.clone(),
+                message: e.to_string(),
+            })?;
+
+        let file = tokio::task::spawn_blocking(move || FileExt::lock_exclusive(&file).map(|_| file))
+            .await
+            .map_err(|e| ForgeError::IoError {
+                path: lock_path.clone(),
+                message: e.to_string(),
+            })?
+            .map_err(|e| ForgeError::IoError {
+                path: lock_path.clone(),
+                message: format!("No se pudo bloquear el artefacto: {}", e),
+            })?;
+
+        Ok(StoreLock { file })
+    }
+
+    /// Guarda el contenido de un artefacto en el almacén. Requiere tener el lock.
+    /// Escribe primero a un archivo temporal y luego lo renombra, de modo que una
+    /// descarga interrumpida nunca deja un archivo truncado con el nombre final.
+    pub fn write(&self, _lock: &StoreLock, relative: &str, bytes: &[u8]) -> ForgeResult<PathBuf> {
+        let target = self.path_for(relative);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| ForgeError::IoError {
+                path: parent.to_path_buf(),
+                message: e.to_string(),
+            })?;
+        }
+
+        let temp = target.with_file_name(format!(
+            "{}.part-{}",
+            target
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            std::process::id()
+        ));
+
+        std::fs::write(&temp, bytes).map_err(|e| ForgeError::IoError {
+            path: temp.clone(),
+            message: e.to_string(),
+        })?;
+
+        std::fs::rename(&temp, &target).map_err(|e| {
+            let _ = std::fs::remove_file(&temp);
+            ForgeError::IoError {
+                path: target.clone(),
+                message: e.to_string(),
+            }
+        })?;
+
+        Ok(target)
+    }
+
+    /// Elimina un artefacto corrupto del almacén para forzar su re-descarga.
+    /// Los archivos de ~/.m2 (o cualquier ruta que escape de la raíz con `..`)
+    /// nunca se tocan.
+    pub fn evict(&self, path: &Path) -> bool {
+        let escapes = path
+            .components()
+            .any(|component| component == std::path::Component::ParentDir);
+        if escapes || !path.starts_with(&self.root) {
+            return false;
+        }
+        std::fs::remove_file(path).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_store(name: &str) -> ArtifactStore {
+        let root = std::env::temp_dir().join(name);
+        let _ = std::fs::remove_dir_all(&root);
+        ArtifactStore { root, m2: None }
+    }
+
+    #[tokio::test]
+    async fn test_write_renames_temp_file_into_place() {
+        let store = temp_store("forge_test_store_write");
+        let relative = "org/acme/util/1.0/util-1.0.jar";
+
+        let lock = store.lock(relative).await.unwrap();
+        let target = store.write(&lock, relative, b"v1").unwrap();
+        assert_eq!(target, store.path_for(relative));
+        assert_eq!(std::fs::read(&target).unwrap(), b"v1");
+
+        let target = store.write(&lock, relative, b"v2").unwrap();
+        assert_eq!(std::fs::read(&target).unwrap(), b"v2");
+
+        let leftovers: Vec<String> = std::fs::read_dir(target.parent().unwrap())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .filter(|name| name.contains(".part-"))
+            .collect();
+        assert!(leftovers.is_empty(), "{:?}", leftovers);
+
+        drop(lock);
+        std::fs::remove_dir_all(store.root()).unwrap();
+    }
+
+    #[test]
+    fn test_find_falls_back_to_m2() {
+        let base = std::env::temp_dir().join("forge_test_store_find");
+        let _ = std::fs::remove_dir_all(&base);
+        let store = ArtifactStore {
+            root: base.join("store"),
+            m2: Some(base.join("m2")),
+        };
+        let relative = "org/acme/util/1.0/util-1.0.jar";
+        assert_eq!(store.find(relative), None);
+
+        let m2_jar = base.join("m2").join(relative);
+        std::fs::create_dir_all(m2_jar.parent().unwrap()).unwrap();
+        std::fs::write(&m2_jar, "m2").unwrap();
+        assert_eq!(store.find(relative), Some(m2_jar));
+
+        // Lo propio del almacén tiene prioridad sobre ~/.m2
+        let own = store.path_for(relative);
+        std::fs::create_dir_all(own.parent().unwrap()).unwrap();
+        std::fs::write(&own, "store").unwrap();
+        assert_eq!(store.find(relative), Some(own));
+
+        let without_m2 = ArtifactStore {
+            root: base.join("vacío"),
+            m2: None,
+        };
+        assert_eq!(without_m2.find(relative), None);
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn test_evict_only_inside_root() {
+        let base = std::env::temp_dir().join("forge_test_store_evict");
+        let _ = std::fs::remove_dir_all(&base);
+        let store = ArtifactStore {
+            root: base.join("store"),
+            m2: Some(base.join("m2")),
+        };
+        let relative = "org/acme/util/1.0/util-1.0.jar";
+        for dir in ["store", "m2"] {
+            let jar = base.join(dir).join(relative);
+            std::fs::create_dir_all(jar.parent().unwrap()).unwrap();
+            std::fs::write(&jar, "jar").unwrap();
+        }
+
+        let m2_jar = base.join("m2").join(relative);
+        assert!(!store.evict(&m2_jar));
+        assert!(!store.evict(&store.root().join("..").join("m2").join(relative)));
+        assert!(m2_jar.exists());
+
+        let own = store.path_for(relative);
+        assert!(store.evict(&own));
+        assert!(!own.exists());
+        assert!(!store.evict(&own));
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_lock_is_exclusive_until_dropped() {
+        let store = temp_store("forge_test_store_lock");
+        let relative = "org/acme/util/1.0/util-1.0.jar";
+        let lock_path = store.root().join("org/acme/util/1.0/util-1.0.jar.lock");
+
+        let lock = store.lock(relative).await.unwrap();
+        assert!(lock_path.exists());
+        let other = File::open(&lock_path).unwrap();
+        assert!(!FileExt::try_lock_exclusive(&other).unwrap());
+
+        drop(lock);
+        assert!(FileExt::try_lock_exclusive(&other).unwrap());
+        FileExt::unlock(&other).unwrap();
+
+        // Se puede volver a adquirir tras liberarlo
+        drop(store.lock(relative).await.unwrap());
+
+        std::fs::remove_dir_all(store.root()).unwrap();
+    }
+}
@@ -0,0 +1,525 @@
+// =============================================================================
+// 🔥 FORGE — Resolución de Dependencias: Versiones Maven
+// =============================================================================
+// Comparación de versiones con las mismas reglas que Maven (1.0-alpha <
+// 1.0-beta < 1.0-rc < 1.0-SNAPSHOT < 1.0 < 1.0-sp), rangos como
+// `[1.2,2.0)` y las palabras clave `latest` / `release`.
+// =============================================================================
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use cyrce_forge_core::error::{ForgeError, ForgeResult};
+
+use crate::metadata::MavenMetadata;
+
+/// Sufijo de las versiones en desarrollo.
+pub const SNAPSHOT_SUFFIX: &str = "-SNAPSHOT";
+
+/// ¿Es una versión SNAPSHOT (`1.0-SNAPSHOT`)?
+pub fn is_snapshot(version: &str) -> bool {
+    version.ends_with(SNAPSHOT_SUFFIX)
+}
+
+/// Una versión Maven comparable.
+#[derive(Debug, Clone)]
+pub struct MavenVersion {
+    original: String,
+    items: Vec<Item>,
+}
+
+/// Componente de una versión: número o calificador (alpha, rc, sp...).
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Item {
+    Number(u64),
+    Qualifier(String),
+}
+
+impl MavenVersion {
+    /// Parsea una versión. Nunca falla: cualquier texto es una versión válida en Maven.
+    pub fn parse(version: &str) -> Self {
+        let lower = version.trim().to_ascii_lowercase();
+        let mut items = Vec::new();
+        let mut current = String::new();
+
+        let flush = |current: &mut String, items: &mut Vec<Item>| {
+            if current.is_empty() {
+                return;
+            }
+            let item = match current.parse::<u64>() {
+                Ok(n) => Item::Number(n),
+                Err(_) => Item::Qualifier(normalize_qualifier(current)),
+            };
+            items.push(item);
+            current.clear();
+        };
+
+        for c in lower.chars() {
+            if c == '.' || c == '-' || c == '_' {
+                flush(&mut current, &mut items);
+                continue;
+            }
+            // Transición dígito ↔ letra: "1rc2" → 1, rc, 2
+            if let Some(last) = current.chars().last() {
+                if last.is_ascii_digit() != c.is_ascii_digit() {
+                    flush(&mut current, &mut items);
+                }
+            }
+            current.push(c);
+        }
+        flush(&mut current, &mut items);
+
+        // "1.0.0" == "1" y "1.0-final" == "1.0": se eliminan ceros y calificadores nulos al final
+        while let Some(last) = items.last() {
+            let null = match last {
+                Item::Number(0) => true,
+                Item::Qualifier(q) => q.is_empty(),
+                Item::Number(_) => false,
+            };
+            if !null {
+                break;
+            }
+            items.pop();
+        }
+
+        Self {
+            original: version.trim().to_string(),
+            items,
+        }
+    }
+
+    /// Texto original de la versión.
+    pub fn as_str(&self) -> &str {
+        &self.original
+    }
+
+    /// ¿Tiene algún calificador de pre-release (alpha, beta, milestone, rc, snapshot)?
+    pub fn is_prerelease(&self) -> bool {
+        self.items
+            .iter()
+            .any(|item| matches!(item, Item::Qualifier(q) if qualifier_rank(q) < qualifier_rank("")))
+    }
+
+    /// Componente numérico inicial `index` (0 = mayor, 1 = menor, 2 = parche).
+    /// Los que faltan valen 0.
+    fn number(&self, index: usize) -> u64 {
+        self.items
+            .iter()
+            .map_while(|item| match item {
+                Item::Number(n) => Some(*n),
+                Item::Qualifier(_) => None,
+            })
+            .nth(index)
+            .unwrap_or(0)
+    }
+
+    /// Calificadores que no son pre-release (`jre` en `33.0.0-jre`). Distinguen variantes
+    /// de un mismo artefacto que no deben mezclarse al actualizar.
+    fn flavor(&self) -> Vec<&str> {
+        self.items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Qualifier(q) if qualifier_rank(q) > qualifier_rank("") => Some(q.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Sinónimos de Maven: a = alpha, b = beta, m = milestone, cr = rc; ga/final/release = versión final.
+fn normalize_qualifier(qualifier: &str) -> String {
+    match qualifier {
+        "a" => "alpha",
+        "b" => "beta",
+        "m" => "milestone",
+        "cr" => "rc",
+        "ga" | "final" | "release" => "",
+        other => other,
+    }
+    .to_string()
+}
+
+/// Orden de los calificadores conocidos; los desconocidos van después de "sp".
+fn qualifier_rank(qualifier: &str) -> usize {
+    match qualifier {
+        "alpha" => 0,
+        "beta" => 1,
+        "milestone" => 2,
+        "rc" => 3,
+        "snapshot" => 4,
+        "" => 5,
+        "sp" => 6,
+        _ => 7,
+    }
+}
+
+/// Compara un componente contra la "ausencia" de componente (versión más corta).
+fn compare_to_null(item: &Item) -> Ordering {
+    match item {
+        Item::Number(n) => n.cmp(&0),
+        Item::Qualifier(q) => qualifier_rank(q).cmp(&qualifier_rank("")),
+    }
+}
+
+fn compare_items(a: &Item, b: &Item) -> Ordering {
+    match (a, b) {
+        (Item::Number(x), Item::Number(y)) => x.cmp(y),
+        (Item::Number(_), Item::Qualifier(_)) => Ordering::Greater,
+        (Item::Qualifier(_), Item::Number(_)) => Ordering::Less,
+        (Item::Qualifier(x), Item::Qualifier(y)) => qualifier_rank(x)
+            .cmp(&qualifier_rank(y))
+            .then_with(|| x.cmp(y)),
+    }
+}
+
+impl Ord for MavenVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let len = self.items.len().max(other.items.len());
+        for i in 0..len {
+            let ordering = match (self.items.get(i), other.items.get(i)) {
+                (Some(a), Some(b)) => compare_items(a, b),
+                (Some(a), None) => compare_to_null(a),
+                (None, Some(b)) => compare_to_null(b).reverse(),
+                (None, None) => Ordering::Equal,
+            };
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+        Ordering::Equal
+    }
+}
+
+impl PartialOrd for MavenVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for MavenVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for MavenVersion {}
+
+impl fmt::Display for MavenVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.original)
+    }
+}
+
+/// Un límite de un rango: versión y si es inclusivo.
+#[derive(Debug, Clone)]
+struct Bound {
+    version: MavenVersion,
+    inclusive: bool,
+}
+
+/// Un tramo de un rango (`[1.0,2.0)`). Sin límite = abierto por ese lado.
+#[derive(Debug, Clone)]
+struct Restriction {
+    lower: Option<Bound>,
+    upper: Option<Bound>,
+}
+
+impl Restriction {
+    fn contains(&self, version: &MavenVersion) -> bool {
+        let above = match &self.lower {
+            Some(b) if b.inclusive => version >= &b.version,
+            Some(b) => version > &b.version,
+            None => true,
+        };
+        let below = match &self.upper {
+            Some(b) if b.inclusive => version <= &b.version,
+            Some(b) => version < &b.version,
+            None => true,
+        };
+        above && below
+    }
+}
+
+/// Rango de versiones Maven: `[1.0,2.0)`, `[1.5]`, `(,1.0],[1.2,)`.
+#[derive(Debug, Clone)]
+pub struct VersionRange {
+    original: String,
+    restrictions: Vec<Restriction>,
+}
+
+impl VersionRange {
+    /// Parsea un rango. Devuelve `None` si el texto no es un rango bien formado.
+    pub fn parse(spec: &str) -> Option<Self> {
+        let mut restrictions = Vec::new();
+        let mut rest = spec.trim();
+
+        while !rest.is_empty() {
+            let open = rest.chars().next()?;
+            if open != '[' && open != '(' {
+                return None;
+            }
+            let close_at = rest.find([']', ')'])?;
+            let close = rest[close_at..].chars().next()?;
+            let body = &rest[1..close_at];
+
+            let restriction = match body.split_once(',') {
+                Some((low, high)) => Restriction {
+                    lower: (!low.trim().is_empty()).then(|| Bound {
+                        version: MavenVersion::parse(low),
+                        inclusive: open == '[',
+                    }),
+                    upper: (!high.trim().is_empty()).then(|| Bound {
+                        version: MavenVersion::parse(high),
+                        inclusive: close == ']',
+                    }),
+                },
+                // "[1.5]" = exactamente 1.5
+                None if open == '[' && close == ']' && !body.trim().is_empty() => {
+                    let exact = Bound {
+                        version: MavenVersion::parse(body),
+                        inclusive: true,
+                    };
+                    Restriction {
+                        lower: Some(exact.clone()),
+                        upper: Some(exact),
+                    }
+                }
+                None => return None,
+            };
+            restrictions.push(restriction);
+
+            rest = rest[close_at + 1..].trim_start();
+            rest = rest.strip_prefix(',').unwrap_or(rest).trim_start();
+        }
+
+        (!restrictions.is_empty()).then(|| Self {
+            original: spec.trim().to_string(),
+            restrictions,
+        })
+    }
+
+    /// ¿La versión cae dentro de algún tramo del rango?
+    pub fn contains(&self, version: &MavenVersion) -> bool {
+        self.restrictions.iter().any(|r| r.contains(version))
+    }
+}
+
+/// Lo que se pide en forge.toml o en un POM para una dependencia.
+#[derive(Debug, Clone)]
+pub enum VersionSpec {
+    /// Versión concreta (incluye `1.0-SNAPSHOT`)
+    Exact(String),
+    /// Rango: la versión más alta publicada que lo cumpla
+    Range(VersionRange),
+    /// La última versión publicada, incluidas SNAPSHOT
+    Latest,
+    /// La última versión final publicada
+    Release,
+}
+
+impl VersionSpec {
+    /// Interpreta el texto de versión de una dependencia.
+    pub fn parse(version: &str) -> ForgeResult<Self> {
+        let trimmed = version.trim();
+        if trimmed.eq_ignore_ascii_case("latest") {
+            return Ok(Self::Latest);
+        }
+        if trimmed.eq_ignore_ascii_case("release") {
+            return Ok(Self::Release);
+        }
+        if trimmed.starts_with('[') || trimmed.starts_with('(') {
+            return VersionRange::parse(trimmed).map(Self::Range).ok_or_else(|| {
+                ForgeError::DependencyResolutionFailed {
+                    dependency: format!(
+                        "rango de versiones inválido '{}' — Ejemplos: \"[1.2,2.0)\", \"[1.5]\", \"(,1.0]\"",
+                        trimmed
+                    ),
+                }
+                .into()
+            });
+        }
+        Ok(Self::Exact(trimmed.to_string()))
+    }
+
+    /// ¿Hace falta consultar maven-metadata.xml para saber la versión?
+    pub fn is_dynamic(&self) -> bool {
+        !matches!(self, Self::Exact(_))
+    }
+
+    /// ¿Una versión concreta satisface lo pedido?
+    pub fn matches(&self, version: &str) -> bool {
+        match self {
+            Self::Exact(v) => v == version,
+            Self::Range(range) => range.contains(&MavenVersion::parse(version)),
+            Self::Latest => true,
+            Self::Release => !is_snapshot(version),
+        }
+    }
+
+    /// Elige la versión a usar entre las publicadas en maven-metadata.xml.
+    /// Los rangos nunca eligen SNAPSHOTs y solo caen en un pre-release (rc, beta…)
+    /// si ninguna versión final del rango está publicada.
+    pub fn select(&self, metadata: &MavenMetadata) -> Option<String> {
+        let newest = |filter: &dyn Fn(&MavenVersion) -> bool| {
+            metadata
+                .versions
+                .iter()
+                .map(|v| MavenVersion::parse(v))
+                .filter(|v| filter(v))
+                .max()
+                .map(|v| v.as_str().to_string())
+        };
+
+        match self {
+            Self::Exact(v) => Some(v.clone()),
+            Self::Latest => metadata
+                .latest
+                .clone()
+                .or_else(|| newest(&|_| true)),
+            Self::Release => metadata
+                .release
+                .clone()
+                .or_else(|| newest(&|v| !is_snapshot(v.as_str()))),
+            Self::Range(range) => newest(&|v| !v.is_prerelease() && range.contains(v))
+                .or_else(|| newest(&|v| !is_snapshot(v.as_str()) && range.contains(v))),
+        }
+    }
+}
+
+/// Versiones más nuevas que la actual, según el tamaño del salto.
+/// Cada campo incluye a los anteriores: `minor` es la última del mismo mayor
+/// (puede ser un parche), `major` la última publicada.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Upgrades {
+    /// Última con el mismo mayor.menor
+    pub patch: Option<String>,
+    /// Última con el mismo mayor
+    pub minor: Option<String>,
+    /// Última publicada
+    pub major: Option<String>,
+}
+
+impl Upgrades {
+    /// Busca actualizaciones para `current` entre las versiones publicadas.
+    /// Ignora pre-releases, SNAPSHOTs y variantes distintas (`-android` frente a `-jre`).
+    pub fn find(current: &str, available: &[String]) -> Self {
+        let current = MavenVersion::parse(current);
+        let mut candidates: Vec<MavenVersion> = available
+            .iter()
+            .map(|v| MavenVersion::parse(v))
+            .filter(|v| !v.is_prerelease() && *v > current && v.flavor() == current.flavor())
+            .collect();
+        candidates.sort();
+
+        let newest = |filter: &dyn Fn(&MavenVersion) -> bool| {
+            candidates
+                .iter()
+                .rev()
+                .find(|v| filter(v))
+                .map(|v| v.as_str().to_string())
+        };
+
+        Self {
+            patch: newest(&|v| v.number(0) == current.number(0) && v.number(1) == current.number(1)),
+            minor: newest(&|v| v.number(0) == current.number(0)),
+            major: newest(&|_| true),
+        }
+    }
+
+    /// Versión a la que actualizar: la última del mismo mayor, o la última publicada con `major`.
+    pub fn target(&self, major: bool) -> Option<&str> {
+        if major {
+            self.major.as_deref()
+        } else {
+            self.minor.as_deref()
+        }
+    }
+}
+
+impl fmt::Display for VersionSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exact(v) => f.write_str(v),
+            Self::Range(range) => f.write_str(&range.original),
+            Self::Latest => f.write_str("latest"),
+            Self::Release => f.write_str("release"),
+        }
+    }
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(s: &str) -> MavenVersion {
+        MavenVersion::parse(s)
+    }
+
+    #[test]
+    fn test_version_ordering() {
+        assert!(v("1.0-alpha1") < v("1.0-beta"));
+        assert!(v("1.0-beta") < v("1.0-rc1"));
+        assert!(v("1.0-rc1") < v("1.0-SNAPSHOT"));
+        assert!(v("1.0-SNAPSHOT") < v("1.0"));
+        assert!(v("1.0") < v("1.0-sp1"));
+        assert!(v("1.9") < v("1.10"));
+        assert!(v("2.0") < v("2.0.1"));
+        assert_eq!(v("1.0.0"), v("1"));
+        assert_eq!(v("1.0-final"), v("1.0"));
+    }
+
+    #[test]
+    fn test_version_ranges() {
+        let range = VersionRange::parse("[1.2,2.0)").unwrap();
+        assert!(range.contains(&v("1.2")));
+        assert!(range.contains(&v("1.9.9")));
+        assert!(!range.contains(&v("2.0")));
+        assert!(!range.contains(&v("1.1")));
+
+        let exact = VersionRange::parse("[1.5]").unwrap();
+        assert!(exact.contains(&v("1.5")) && !exact.contains(&v("1.5.1")));
+
+        let union = VersionRange::parse("(,1.0],[1.2,)").unwrap();
+        assert!(union.contains(&v("0.9")) && union.contains(&v("3.0")));
+        assert!(!union.contains(&v("1.1")));
+
+        assert!(VersionRange::parse("[1.0").is_none());
+    }
+
+    #[test]
+    fn test_select_from_metadata() {
+        let metadata = MavenMetadata {
+            versions: ["1.0", "1.5", "2.0-rc1", "2.0", "2.1-SNAPSHOT"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            ..Default::default()
+        };
+
+        let range = VersionSpec::parse("[1.0,2.0)").unwrap();
+        assert_eq!(range.select(&metadata).as_deref(), Some("1.5"));
+        assert_eq!(VersionSpec::parse("LATEST").unwrap().select(&metadata).as_deref(), Some("2.1-SNAPSHOT"));
+        assert_eq!(VersionSpec::parse("release").unwrap().select(&metadata).as_deref(), Some("2.0"));
+        assert!(!VersionSpec::parse("1.0").unwrap().is_dynamic());
+    }
+
+    #[test]
+    fn test_upgrades() {
+        let available: Vec<String> = ["2.0.8", "2.0.9", "2.0.12", "2.1.0-alpha1", "2.1.0", "3.0.0", "33.0.0-android"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let upgrades = Upgrades::find("2.0.9", &available);
+        assert_eq!(upgrades.patch.as_deref(), Some("2.0.12"));
+        assert_eq!(upgrades.minor.as_deref(), Some("2.1.0"));
+        assert_eq!(upgrades.major.as_deref(), Some("3.0.0"));
+        assert_eq!(upgrades.target(false), Some("2.1.0"));
+
+        let guava: Vec<String> = ["33.2.1-android", "33.2.1-jre"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(Upgrades::find("33.0.0-jre", &guava).major.as_deref(), Some("33.2.1-jre"));
+        assert_eq!(Upgrades::find("3.0.0", &available), Upgrades::default());
+    }
+}
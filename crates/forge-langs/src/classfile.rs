@@ -0,0 +1,296 @@
+// =============================================================================
+// 🔥 FORGE — Módulos de Lenguaje: Lectura de .class
+// =============================================================================
+// Lector mínimo del formato class file de la JVM: nombre de la clase, archivo
+// fuente (atributo SourceFile), los tipos que referencia (entradas Class del
+// constant pool y descriptores `Lcom/acme/Foo;`) y si declara constantes de
+// compilación (campos con ConstantValue). Lo usa la compilación incremental
+// para saber qué clases dependen de cuáles.
+// =============================================================================
+
+use std::collections::BTreeSet;
+
+/// Lo que la compilación incremental necesita saber de un .class.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassInfo {
+    /// Nombre interno: `com/acme/Foo$Bar`
+    pub name: String,
+
+    /// Atributo SourceFile: `Foo.java`
+    pub source_file: Option<String>,
+
+    /// Nombres internos de los tipos referenciados (sin la propia clase)
+    pub references: BTreeSet<String>,
+
+    /// ¿Algún campo tiene ConstantValue? javac copia esos valores en las clases
+    /// que los usan, que entonces no referencian a esta
+    pub has_constants: bool,
+}
+
+impl ClassInfo {
+    /// Ruta del fuente relativa al directorio de código (`com/acme/Foo.java`),
+    /// asumiendo que el archivo está en el directorio de su paquete.
+    pub fn source_path(&self) -> Option<String> {
+        let source_file = self.source_file.as_ref()?;
+        Some(match self.name.rsplit_once('/') {
+            Some((package, _)) => format!("{}/{}", package, source_file),
+            None => source_file.clone(),
+        })
+    }
+}
+
+/// Entrada del constant pool que interesa conservar.
+enum Constant {
+    Utf8(String),
+    Class(u16),
+    Other,
+}
+
+/// Cursor de lectura big-endian sobre los bytes del class file.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let slice = self.bytes.get(self.pos..self.pos + len)?;
+        self.pos += len;
+        Some(slice)
+    }
+
+    fn u1(&mut self) -> Option<u8> {
+        Some(self.take(1)?[0])
+    }
+
+    fn u2(&mut self) -> Option<u16> {
+        let b = self.take(2)?;
+        Some(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn u4(&mut self) -> Option<u32> {
+        let b = self.take(4)?;
+        Some(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    /// Salta una tabla de atributos y devuelve el índice del SourceFile si lo
+    /// encuentra y si había un atributo ConstantValue.
+    fn attributes(&mut self, pool: &[Constant]) -> Option<(Option<u16>, bool)> {
+        let mut source_file = None;
+        let mut constant = false;
+        for _ in 0..self.u2()? {
+            let name = self.u2()?;
+            let len = self.u4()? as usize;
+            let info = self.take(len)?;
+            match pool.get(name as usize) {
+                Some(Constant::Utf8(n)) if n == "SourceFile" && len == 2 => {
+                    source_file = Some(u16::from_be_bytes([info[0], info[1]]));
+                }
+                Some(Constant::Utf8(n)) if n == "ConstantValue" => constant = true,
+                _ => {}
+            }
+        }
+        Some((source_file, constant))
+    }
+
+    /// Salta una tabla de campos o métodos. Devuelve si alguno tenía ConstantValue.
+    fn members(&mut self, pool: &[Constant]) -> Option<bool> {
+        let mut constants = false;
+        for _ in 0..self.u2()? {
+            self.take(6)?; // access_flags, name_index, descriptor_index
+            constants |= self.attributes(pool)?.1;
+        }
+        Some(constants)
+    }
+}
+
+/// Lee un class file. `None` si los bytes no son un class file válido.
+pub fn parse(bytes: &[u8]) -> Option<ClassInfo> {
+    let mut reader = Reader { bytes, pos: 0 };
+    if reader.u4()? != 0xCAFE_BABE {
+        return None;
+    }
+    reader.take(4)?; // minor, major
+
+    let count = reader.u2()? as usize;
+    let mut pool = Vec::with_capacity(count);
+    pool.push(Constant::Other); // el índice 0 no se usa
+    while pool.len() < count {
+        let tag = reader.u1()?;
+        let constant = match tag {
+            1 => {
+                let len = reader.u2()? as usize;
+                Constant::Utf8(String::from_utf8_lossy(reader.take(len)?).into_owned())
+            }
+            7 => Constant::Class(reader.u2()?),
+            3 | 4 | 9 | 10 | 11 | 12 | 17 | 18 => {
+                reader.take(4)?;
+                Constant::Other
+            }
+            // Long y Double ocupan dos posiciones
+            5 | 6 => {
+                reader.take(8)?;
+                pool.push(Constant::Other);
+                Constant::Other
+            }
+            8 | 16 | 19 | 20 => {
+                reader.take(2)?;
+                Constant::Other
+            }
+            15 => {
+                reader.take(3)?;
+                Constant::Other
+            }
+            _ => return None,
+        };
+        pool.push(constant);
+    }
+
+    let utf8 = |index: u16| match pool.get(index as usize) {
+        Some(Constant::Utf8(value)) => Some(value.as_str()),
+        _ => None,
+    };
+
+    reader.take(2)?; // access_flags
+    let this_class = reader.u2()?;
+    let name = match pool.get(this_class as usize) {
+        Some(Constant::Class(index)) => utf8(*index)?.to_string(),
+        _ => return None,
+    };
+    reader.take(2)?; // super_class (ya está en el pool como Class)
+    let interfaces = reader.u2()? as usize;
+    reader.take(interfaces * 2)?;
+    let has_constants = reader.members(&pool)?; // campos
+    reader.members(&pool)?; // métodos
+    let source_file = reader.attributes(&pool)?.0.and_then(utf8).map(String::from);
+
+    let mut references = BTreeSet::new();
+    for constant in &pool {
+        match constant {
+            Constant::Class(index) => {
+                if let Some(class) = utf8(*index) {
+                    // Arreglos: `[Lcom/acme/Foo;`
+                    match class.trim_start_matches('[').strip_prefix('L') {
+                        Some(element) => references.extend(descriptor_types(&format!("L{}", element))),
+                        None if !class.starts_with('[') => {
+                            references.insert(class.to_string());
+                        }
+                        None => {}
+                    }
+                }
+            }
+            Constant::Utf8(value) => references.extend(descriptor_types(value)),
+            Constant::Other => {}
+        }
+    }
+    references.remove(&name);
+
+    Some(ClassInfo {
+        name,
+        source_file,
+        references,
+        has_constants,
+    })
+}
+
+/// Tipos `Lcom/acme/Foo;` dentro de un descriptor o firma genérica.
+fn descriptor_types(value: &str) -> Vec<String> {
+    let mut types = Vec::new();
+    let bytes = value.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        let starts = bytes[i] == b'L' && (i == 0 || matches!(bytes[i - 1], b'(' | b')' | b'[' | b';' | b'<' | b'>' | b'+' | b'-' | b'^' | b':'));
+        if starts {
+            let rest = &value[i + 1..];
+            if let Some(end) = rest.find([';', '<']) {
+                let name = &rest[..end];
+                if !name.is_empty() && name.bytes().all(|b| b.is_ascii_alphanumeric() || matches!(b, b'/' | b'$' | b'_')) {
+                    types.push(name.to_string());
+                    i += end + 1;
+                    continue;
+                }
+            }
+        }
+        i += 1;
+    }
+    types
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Class file mínimo: la clase `name` con su SourceFile y, como constantes
+    /// Utf8, los descriptores de `references`.
+    pub(crate) fn class_bytes(name: &str, source: &str, references: &[&str]) -> Vec<u8> {
+        build_class(name, source, references, false)
+    }
+
+    /// Como `class_bytes`, con un campo `static final int MAX = 42` (ConstantValue).
+    pub(crate) fn constant_class_bytes(name: &str, source: &str) -> Vec<u8> {
+        build_class(name, source, &[], true)
+    }
+
+    fn build_class(name: &str, source: &str, references: &[&str], constant: bool) -> Vec<u8> {
+        let mut pool: Vec<Vec<u8>> = Vec::new();
+        let utf8 = |s: &str| {
+            let mut b = vec![1];
+            b.extend_from_slice(&(s.len() as u16).to_be_bytes());
+            b.extend_from_slice(s.as_bytes());
+            b
+        };
+        pool.push(utf8(name)); // 1
+        pool.push(vec![7, 0, 1]); // 2: this_class
+        pool.push(utf8("SourceFile")); // 3
+        pool.push(utf8(source)); // 4
+        pool.push(utf8("ConstantValue")); // 5
+        pool.push(utf8("MAX")); // 6
+        pool.push(utf8("I")); // 7
+        pool.push(vec![3, 0, 0, 0, 42]); // 8: Integer
+        for reference in references {
+            pool.push(utf8(reference));
+        }
+        let count = pool.len() as u16 + 1;
+
+        let mut bytes = vec![0xCA, 0xFE, 0xBA, 0xBE, 0, 0, 0, 61];
+        bytes.extend_from_slice(&count.to_be_bytes());
+        for entry in pool {
+            bytes.extend(entry);
+        }
+        bytes.extend_from_slice(&[0, 0x21, 0, 2, 0, 0]); // access, this, super
+        bytes.extend_from_slice(&[0, 0]); // interfaces
+        if constant {
+            // public static final int MAX = 42
+            bytes.extend_from_slice(&[0, 1, 0, 0x19, 0, 6, 0, 7, 0, 1, 0, 5, 0, 0, 0, 2, 0, 8]);
+        } else {
+            bytes.extend_from_slice(&[0, 0]);
+        }
+        bytes.extend_from_slice(&[0, 0]); // methods
+        bytes.extend_from_slice(&[0, 1, 0, 3, 0, 0, 0, 2, 0, 4]); // SourceFile
+        bytes
+    }
+
+    #[test]
+    fn test_parse_class_info() {
+        let bytes = class_bytes(
+            "com/acme/Foo",
+            "Foo.java",
+            &["(Lcom/acme/Baz;[Lcom/acme/Qux;)Ljava/util/List<Lcom/acme/Bar;>;", "Log;"],
+        );
+        let info = parse(&bytes).unwrap();
+
+        assert_eq!(info.name, "com/acme/Foo");
+        assert_eq!(info.source_path().as_deref(), Some("com/acme/Foo.java"));
+        assert!(info.references.contains("com/acme/Baz"));
+        assert!(info.references.contains("com/acme/Qux"));
+        assert!(info.references.contains("com/acme/Bar"));
+        assert!(info.references.contains("java/util/List"));
+        assert!(!info.references.contains("com/acme/Foo"));
+        assert!(!info.has_constants);
+        assert!(parse(b"no es un class file").is_none());
+
+        let constants = parse(&constant_class_bytes("com/acme/Limits", "Limits.java")).unwrap();
+        assert!(constants.has_constants);
+        assert_eq!(constants.source_path().as_deref(), Some("com/acme/Limits.java"));
+    }
+}
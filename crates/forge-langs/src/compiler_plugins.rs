@@ -0,0 +1,373 @@
+// =============================================================================
+// 🔥 FORGE — Módulos de Lenguaje: Plugins del compilador de Kotlin, kapt y KSP
+// =============================================================================
+// - `[kotlin] compiler-plugins`: JARs resueltos en .forge/kotlin-plugins/ que
+//   kotlinc carga con `-Xplugin`; `plugin-options` se pasan con `-P plugin:...`.
+// - kapt: `[annotation-processors]` en un proyecto Kotlin. Una pasada previa de
+//   kotlinc genera stubs Java y ejecuta los procesadores; sus fuentes generadas
+//   se compilan después junto con el proyecto.
+// - `[kotlin.ksp]`: KSP2 (KSPJvmMain) se ejecuta antes de kotlinc y sus fuentes
+//   Kotlin/Java generadas entran en la compilación.
+// kapt y KSP solo procesan los fuentes principales, no los tests.
+// =============================================================================
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use colored::Colorize;
+use walkdir::WalkDir;
+
+use cyrce_forge_core::config::ForgeConfig;
+use cyrce_forge_core::error::{ForgeError, ForgeResult};
+
+use crate::java::{build_classpath, split_classpath};
+
+/// Id del plugin kapt para `-P plugin:<id>:<opción>`.
+const KAPT_PLUGIN_ID: &str = "org.jetbrains.kotlin.kapt3";
+
+/// Clase principal de la línea de comandos de KSP2.
+const KSP_MAIN: &str = "com.google.devtools.ksp.cmdline.KSPJvmMain";
+
+/// Argumentos de kotlinc para `[kotlin] compiler-plugins` y `plugin-options`.
+pub(crate) fn plugin_args(config: &ForgeConfig, project_dir: &Path) -> Vec<String> {
+    let options = config.kotlin.as_ref().map(|k| k.plugin_options.as_slice()).unwrap_or_default();
+    if config.kotlin_compiler_plugins().is_empty() && options.is_empty() {
+        return Vec::new();
+    }
+    let jars = split_classpath(&build_classpath(&forge_dir(project_dir, "kotlin-plugins")));
+    plugin_flags(&jars, options)
+}
+
+fn plugin_flags(jars: &[String], options: &[String]) -> Vec<String> {
+    let mut args: Vec<String> = jars.iter().map(|jar| format!("-Xplugin={}", jar)).collect();
+    for option in options {
+        args.push("-P".to_string());
+        args.push(format!("plugin:{}", option));
+    }
+    args
+}
+
+/// Directorio de fuentes generadas por una herramienta (build/generated/<tool>/main).
+pub(crate) fn generated_dir(config: &ForgeConfig, project_dir: &Path, tool: &str) -> PathBuf {
+    project_dir
+        .join(&config.project.output_dir)
+        .join("generated")
+        .join(tool)
+        .join("main")
+}
+
+/// Cuenta los fuentes .kt y .java de un directorio generado.
+pub(crate) fn count_sources(dir: &Path) -> usize {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "kt" || ext == "java"))
+        .count()
+}
+
+fn forge_dir(project_dir: &Path, name: &str) -> PathBuf {
+    project_dir.join(".forge").join(name)
+}
+
+/// Plugin kapt y los procesadores que ejecuta.
+pub(crate) struct Kapt {
+    plugin: String,
+    processor_path: Vec<String>,
+}
+
+impl Kapt {
+    /// kapt para `[annotation-processors]`: el plugin resuelto de `[kotlin] version`
+    /// (.forge/kapt/) o el kotlin-annotation-processing.jar de la instalación de kotlinc.
+    pub(crate) fn load(
+        config: &ForgeConfig,
+        project_dir: &Path,
+        kotlin_home: impl FnOnce() -> Option<PathBuf>,
+    ) -> Option<Self> {
+        if config.annotation_processors.is_empty() {
+            return None;
+        }
+        let processor_path = split_classpath(&build_classpath(&forge_dir(project_dir, "processors")));
+        if processor_path.is_empty() {
+            return None;
+        }
+
+        let plugin = if config.kotlin_version().is_some() {
+            split_classpath(&build_classpath(&forge_dir(project_dir, "kapt")))
+                .into_iter()
+                .find(|jar| file_name(jar).starts_with("kotlin-annotation-processing"))?
+        } else {
+            let jar = kotlin_home()?.join("lib").join("kotlin-annotation-processing.jar");
+            if !jar.exists() {
+                return None;
+            }
+            jar.to_string_lossy().to_string()
+        };
+        Some(Self { plugin, processor_path })
+    }
+
+    /// Opciones de la pasada de kapt: stubs y procesadores (`aptMode=stubsAndApt`),
+    /// fuentes en `sources`, clases y recursos generados directamente en `classes`.
+    /// Se analiza como Kotlin 1.9: es el modo que soporta kapt por línea de comandos.
+    pub(crate) fn args(&self, sources: &Path, stubs: &Path, classes: &Path) -> Vec<String> {
+        let mut options = vec![
+            format!("sources={}", sources.display()),
+            format!("classes={}", classes.display()),
+            format!("stubs={}", stubs.display()),
+        ];
+        options.extend(self.processor_path.iter().map(|jar| format!("apclasspath={}", jar)));
+        options.push("aptMode=stubsAndApt".to_string());
+        options.push("correctErrorTypes=true".to_string());
+
+        let mut args = vec![
+            format!("-Xplugin={}", self.plugin),
+            "-language-version".to_string(),
+            "1.9".to_string(),
+            "-api-version".to_string(),
+            "1.9".to_string(),
+        ];
+        for option in options {
+            args.push("-P".to_string());
+            args.push(format!("plugin:{}:{}", KAPT_PLUGIN_ID, option));
+        }
+        args
+    }
+}
+
+fn file_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Ejecuta KSP sobre los fuentes principales y devuelve los directorios con los
+/// fuentes generados (Kotlin y Java). Clases y recursos generados (ej:
+/// META-INF/services) van directamente a `classes_dir`.
+pub(crate) async fn run_ksp(
+    config: &ForgeConfig,
+    project_dir: &Path,
+    kotlin_roots: &[PathBuf],
+    java_roots: &[PathBuf],
+    libraries: &str,
+    classes_dir: &Path,
+) -> ForgeResult<Option<(PathBuf, PathBuf)>> {
+    let (Some(ksp), Some(version)) = (config.ksp(), config.kotlin_version()) else {
+        return Ok(None);
+    };
+
+    let ksp_cp = build_classpath(&forge_dir(project_dir, "ksp"));
+    if ksp_cp.is_empty() {
+        return Err(ForgeError::IoError {
+            path: forge_dir(project_dir, "ksp"),
+            message: "KSP no resuelto. Ejecuta 'forge deps'.".to_string(),
+        }
+        .into());
+    }
+    let processors = build_classpath(&forge_dir(project_dir, "ksp-processors"));
+    if processors.is_empty() {
+        println!("   {}", "⚠️  [kotlin.ksp] no declara procesadores: se omite KSP".yellow());
+        return Ok(None);
+    }
+
+    let output = generated_dir(config, project_dir, "ksp");
+    let _ = std::fs::remove_dir_all(&output);
+    let kotlin_out = output.join("kotlin");
+    let java_out = output.join("java");
+    let jvm_target = config.kotlin.as_ref().map(|k| k.jvm_target.as_str()).unwrap_or("17");
+
+    let mut options: Vec<(String, String)> = ksp.options.clone().into_iter().collect();
+    options.sort();
+    let run = KspRun {
+        module_name: &config.project.name,
+        jvm_target,
+        kotlin_version: version,
+        project: project_dir,
+        output: &output,
+        caches: &forge_dir(project_dir, "ksp-cache"),
+        classes: classes_dir,
+        kotlin_out: &kotlin_out,
+        java_out: &java_out,
+        kotlin_roots,
+        java_roots,
+        libraries,
+        options: &options,
+    };
+
+    println!("   {}", "🧩 Ejecutando procesadores KSP...".cyan());
+    let result = tokio::process::Command::new("java")
+        .arg("-cp")
+        .arg(&ksp_cp)
+        .arg(KSP_MAIN)
+        .args(run.args())
+        .arg(&processors)
+        .current_dir(project_dir)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .await
+        .map_err(|e| ForgeError::CommandNotFound {
+            command: format!("java (KSP): {}", e),
+        })?;
+
+    if !result.status.success() {
+        eprintln!("{}", String::from_utf8_lossy(&result.stdout));
+        eprintln!("{}", String::from_utf8_lossy(&result.stderr));
+        return Err(ForgeError::TaskFailed {
+            task_name: "ksp".to_string(),
+            exit_code: result.status.code().unwrap_or(-1),
+        }
+        .into());
+    }
+
+    let generated = count_sources(&output);
+    if generated > 0 {
+        println!("   {}", format!("🧩 KSP generó {} fuentes", generated).dimmed());
+    }
+    Ok(Some((kotlin_out, java_out)))
+}
+
+/// Entradas y directorios de una ejecución de KSP.
+struct KspRun<'a> {
+    module_name: &'a str,
+    jvm_target: &'a str,
+    kotlin_version: &'a str,
+    project: &'a Path,
+    output: &'a Path,
+    caches: &'a Path,
+    classes: &'a Path,
+    kotlin_out: &'a Path,
+    java_out: &'a Path,
+    kotlin_roots: &'a [PathBuf],
+    java_roots: &'a [PathBuf],
+    libraries: &'a str,
+    options: &'a [(String, String)],
+}
+
+impl KspRun<'_> {
+    /// Argumentos de KSPJvmMain (sin el classpath de procesadores, que va al final).
+    /// Listas separadas por el separador de rutas de la plataforma, opciones como `clave=valor`.
+    fn args(&self) -> Vec<String> {
+        let separator = if cfg!(target_os = "windows") { ";" } else { ":" };
+        let join = |paths: &[PathBuf]| {
+            paths
+                .iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join(separator)
+        };
+        let path = |path: &Path| path.to_string_lossy().to_string();
+        // "2.1.0" → "2.1"
+        let language_version = self.kotlin_version.splitn(3, '.').take(2).collect::<Vec<_>>().join(".");
+
+        let mut args = vec![
+            "-jvm-target".to_string(),
+            self.jvm_target.to_string(),
+            format!("-module-name={}", self.module_name),
+            "-source-roots".to_string(),
+            join(self.kotlin_roots),
+            "-project-base-dir".to_string(),
+            path(self.project),
+            "-output-base-dir".to_string(),
+            path(self.output),
+            "-caches-dir".to_string(),
+            path(self.caches),
+            "-class-output-dir".to_string(),
+            path(self.classes),
+            "-kotlin-output-dir".to_string(),
+            path(self.kotlin_out),
+            "-java-output-dir".to_string(),
+            path(self.java_out),
+            "-resource-output-dir".to_string(),
+            path(self.classes),
+            "-language-version".to_string(),
+            language_version.clone(),
+            "-api-version".to_string(),
+            language_version,
+        ];
+        if !self.java_roots.is_empty() {
+            args.push("-java-source-roots".to_string());
+            args.push(join(self.java_roots));
+        }
+        if !self.libraries.is_empty() {
+            args.push("-libraries".to_string());
+            args.push(self.libraries.to_string());
+        }
+        if !self.options.is_empty() {
+            args.push("-processor-options".to_string());
+            args.push(
+                self.options
+                    .iter()
+                    .map(|(key, value)| format!("{}={}", key, value))
+                    .collect::<Vec<_>>()
+                    .join(separator),
+            );
+        }
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plugin_flags() {
+        let args = plugin_flags(
+            &["/store/kotlin-allopen-compiler-plugin-embeddable-2.1.0.jar".to_string()],
+            &["org.jetbrains.kotlin.allopen:annotation=com.acme.Open".to_string()],
+        );
+        assert_eq!(
+            args,
+            vec![
+                "-Xplugin=/store/kotlin-allopen-compiler-plugin-embeddable-2.1.0.jar",
+                "-P",
+                "plugin:org.jetbrains.kotlin.allopen:annotation=com.acme.Open"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_kapt_args_run_processors_in_stub_pass() {
+        let kapt = Kapt {
+            plugin: "/store/kotlin-annotation-processing-embeddable-2.1.0.jar".to_string(),
+            processor_path: vec!["/store/dagger-compiler.jar".to_string()],
+        };
+        let args = kapt.args(Path::new("/app/gen"), Path::new("/app/stubs"), Path::new("/app/classes"));
+
+        assert_eq!(args[0], "-Xplugin=/store/kotlin-annotation-processing-embeddable-2.1.0.jar");
+        assert!(args.contains(&"plugin:org.jetbrains.kotlin.kapt3:apclasspath=/store/dagger-compiler.jar".to_string()));
+        assert!(args.contains(&"plugin:org.jetbrains.kotlin.kapt3:aptMode=stubsAndApt".to_string()));
+        assert!(args.contains(&"plugin:org.jetbrains.kotlin.kapt3:classes=/app/classes".to_string()));
+    }
+
+    #[test]
+    fn test_ksp_args() {
+        let options = vec![("moshi.generated".to_string(), "javax.annotation.Generated".to_string())];
+        let run = KspRun {
+            module_name: "app",
+            jvm_target: "17",
+            kotlin_version: "2.1.0",
+            project: Path::new("/app"),
+            output: Path::new("/app/build/generated/ksp/main"),
+            caches: Path::new("/app/.forge/ksp-cache"),
+            classes: Path::new("/app/build/classes"),
+            kotlin_out: Path::new("/app/build/generated/ksp/main/kotlin"),
+            java_out: Path::new("/app/build/generated/ksp/main/java"),
+            kotlin_roots: &[PathBuf::from("/app/src/main/kotlin")],
+            java_roots: &[],
+            libraries: "",
+            options: &options,
+        };
+        let args = run.args();
+
+        let value_of = |flag: &str| args.iter().position(|a| a == flag).map(|i| args[i + 1].as_str());
+        assert!(args.contains(&"-module-name=app".to_string()));
+        assert_eq!(value_of("-source-roots"), Some("/app/src/main/kotlin"));
+        assert_eq!(value_of("-language-version"), Some("2.1"));
+        assert_eq!(value_of("-resource-output-dir"), Some("/app/build/classes"));
+        assert_eq!(value_of("-processor-options"), Some("moshi.generated=javax.annotation.Generated"));
+        // Sin classpath ni fuentes Java no se pasan listas vacías
+        assert!(!args.contains(&"-libraries".to_string()));
+        assert!(!args.contains(&"-java-source-roots".to_string()));
+    }
+}
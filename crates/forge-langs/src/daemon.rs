@@ -0,0 +1,643 @@
+// =============================================================================
+// 🔥 FORGE — Módulos de Lenguaje: Daemon de compilación
+// =============================================================================
+// Arrancar la JVM de javac o kotlinc cuesta segundos en cada build (y en cada
+// vuelta de `forge watch`). El primer build lanza un proceso Java de larga vida
+// (daemon/ForgeCompilerDaemon.java, compilado en ~/.forge/daemon/<clave>/) que
+// atiende las compilaciones por un socket local y termina solo tras
+// `[daemon] idle-timeout` minutos sin uso. Si el daemon no está disponible, se
+// compila como siempre con un proceso nuevo.
+//
+// El daemon no tiene el directorio de trabajo del proyecto: antes de enviar una
+// petición, las rutas relativas de los argumentos (las de `compiler-args` en
+// forge.toml) se hacen absolutas contra project_dir y los @argfiles se
+// expanden. El arranque se serializa con un lock (como el almacén de
+// artefactos) para que dos builds simultáneos no lancen dos daemons.
+// =============================================================================
+
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use colored::Colorize;
+use fs4::fs_std::FileExt;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use cyrce_forge_core::settings::{env_flag, UserSettings, NO_DAEMON_ENV};
+use cyrce_forge_deps::checksum::ChecksumAlgorithm;
+
+/// Código del daemon; cambia la clave de su directorio en cada versión de forge.
+const DAEMON_SOURCE: &str = include_str!("daemon/ForgeCompilerDaemon.java");
+const DAEMON_CLASS: &str = "ForgeCompilerDaemon";
+
+/// Archivo con port, token y pid del daemon en ejecución.
+const STATE_FILE: &str = "daemon.properties";
+
+/// Lock que serializa el arranque del daemon entre procesos de forge.
+const START_LOCK_FILE: &str = "daemon.lock";
+
+/// Tiempo máximo para que un daemon recién lanzado publique su puerto.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Opciones de javac y kotlinc cuyo valor es una ruta o una lista de rutas.
+const PATH_OPTIONS: &[&str] = &[
+    "-d", "-s", "-h", "-cp", "-classpath", "--class-path", "-sourcepath", "--source-path", "-processorpath",
+    "--processor-path", "-p", "--module-path", "--processor-module-path", "--module-source-path",
+    "--upgrade-module-path", "-kotlin-home", "-jdk-home", "-Xplugin",
+];
+
+/// Opciones cuyo valor va en el argumento siguiente y no es una ruta: sin esta
+/// lista, `-encoding UTF-8` se tomaría por un archivo fuente llamado `UTF-8`.
+const VALUE_OPTIONS: &[&str] = &[
+    "-source", "--source", "-target", "--target", "--release", "-encoding", "--add-modules", "--add-exports",
+    "--add-reads", "--limit-modules", "--patch-module", "--module", "-m", "--module-version", "-processor",
+    "--system", "--default-module-for-created-files", "-module-name", "-jvm-target", "-api-version",
+    "-language-version", "-opt-in", "-P",
+];
+
+/// El aviso de fallback se muestra una sola vez por proceso.
+static FALLBACK_WARNED: AtomicBool = AtomicBool::new(false);
+
+/// Compilador que el daemon sabe ejecutar en su propia JVM.
+#[derive(Debug, Clone)]
+enum Tool {
+    /// javac del JDK (javax.tools)
+    Javac,
+    /// K2JVMCompiler cargado desde el classpath de kotlin-compiler
+    Kotlinc { classpath: String },
+}
+
+/// Invocación de javac o kotlinc con una interfaz parecida a `Command`: se
+/// envía al daemon si está habilitado y, si no, se ejecuta `launcher` + args.
+pub(crate) struct CompilerCommand {
+    launcher: Vec<OsString>,
+    args: Vec<OsString>,
+    tool: Option<Tool>,
+}
+
+/// Resultado de una compilación (del daemon o de un proceso).
+pub(crate) struct CompilerOutput {
+    pub(crate) code: i32,
+    /// Diagnósticos del compilador
+    pub(crate) stderr: Vec<u8>,
+}
+
+impl CompilerOutput {
+    pub(crate) fn success(&self) -> bool {
+        self.code == 0
+    }
+}
+
+impl CompilerCommand {
+    /// javac del PATH; en el daemon, el javac del mismo JDK vía javax.tools.
+    pub(crate) fn javac() -> Self {
+        Self {
+            launcher: vec!["javac".into()],
+            args: Vec::new(),
+            tool: Some(Tool::Javac),
+        }
+    }
+
+    /// Compilador de Kotlin lanzado con `launcher`. Con el classpath de
+    /// kotlin-compiler, el daemon lo carga en su JVM en lugar de lanzarlo.
+    pub(crate) fn kotlinc<S: AsRef<OsStr>>(launcher: &[S], compiler_classpath: Option<String>) -> Self {
+        Self {
+            launcher: launcher.iter().map(|arg| arg.as_ref().to_os_string()).collect(),
+            args: Vec::new(),
+            tool: compiler_classpath.map(|classpath| Tool::Kotlinc { classpath }),
+        }
+    }
+
+    pub(crate) fn arg<S: AsRef<OsStr>>(&mut self, arg: S) -> &mut Self {
+        self.args.push(arg.as_ref().to_os_string());
+        self
+    }
+
+    pub(crate) fn args<I, S>(&mut self, args: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.args.extend(args.into_iter().map(|arg| arg.as_ref().to_os_string()));
+        self
+    }
+
+    /// Compila en el daemon o, si no está disponible, en un proceso nuevo
+    /// con `project_dir` como directorio de trabajo.
+    pub(crate) async fn output(&self, project_dir: &Path) -> std::io::Result<CompilerOutput> {
+        if let Some(tool) = self.tool.as_ref().filter(|_| enabled()) {
+            match compile_in_daemon(tool, project_dir, &self.args).await {
+                Ok(output) => return Ok(output),
+                Err(e) => {
+                    if !FALLBACK_WARNED.swap(true, Ordering::Relaxed) {
+                        println!(
+                            "   {}",
+                            format!("⚠️  Daemon de compilación no disponible ({}): compilando sin daemon", e).yellow()
+                        );
+                    }
+                }
+            }
+        }
+
+        let output = tokio::process::Command::new(&self.launcher[0])
+            .args(&self.launcher[1..])
+            .args(&self.args)
+            .current_dir(project_dir)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
+            .await?;
+        Ok(CompilerOutput {
+            code: output.status.code().unwrap_or(-1),
+            stderr: output.stderr,
+        })
+    }
+}
+
+/// Daemon en ejecución, según su archivo de estado.
+#[derive(Debug, Clone, PartialEq)]
+struct DaemonState {
+    port: u16,
+    token: String,
+    pid: u32,
+}
+
+/// Estado de un daemon, como lo muestra `forge daemon`.
+#[derive(Debug, Clone)]
+pub struct DaemonInfo {
+    pub pid: u32,
+    pub java_home: String,
+    pub uptime_secs: u64,
+    pub idle_secs: u64,
+    pub compilations: u64,
+}
+
+/// ¿Compilar en el daemon? `[daemon] enabled` y ni `--no-daemon` ni `FORGE_NO_DAEMON`.
+fn enabled() -> bool {
+    !env_flag(NO_DAEMON_ENV) && UserSettings::load().map(|s| s.daemon.enabled).unwrap_or(true)
+}
+
+/// Directorio raíz de los daemons (~/.forge/daemon).
+fn daemons_dir() -> PathBuf {
+    UserSettings::forge_home().join("daemon")
+}
+
+/// Directorio del daemon de este JDK y esta versión del código: cambiar de JDK
+/// (JAVA_HOME o PATH) o de versión de forge lanza un daemon nuevo.
+fn daemon_dir() -> PathBuf {
+    let identity = format!(
+        "{}\n{}\n{}",
+        DAEMON_SOURCE,
+        std::env::var("JAVA_HOME").unwrap_or_default(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    let digest = ChecksumAlgorithm::Sha256.digest(identity.as_bytes());
+    daemons_dir().join(&digest[..16])
+}
+
+/// Argumentos tal como los vería el compilador lanzado con `project_dir` como
+/// directorio de trabajo: rutas relativas (fuentes, valores de [`PATH_OPTIONS`] y
+/// cada entrada de sus listas) unidas a `project_dir`, y @argfiles expandidos.
+fn absolute_args(project_dir: &Path, args: &[OsString]) -> std::io::Result<Vec<OsString>> {
+    let mut absolute = Vec::with_capacity(args.len());
+    let mut previous: Option<&OsStr> = None;
+    for arg in args {
+        let option = previous.and_then(|p| p.to_str()).unwrap_or_default();
+        let text = arg.to_string_lossy();
+        if VALUE_OPTIONS.contains(&option) {
+            absolute.push(arg.clone());
+        } else if PATH_OPTIONS.contains(&option) {
+            absolute.push(absolute_path_list(project_dir, arg));
+        } else if let Some(argfile) = text.strip_prefix('@').filter(|f| !f.is_empty()) {
+            let content = std::fs::read_to_string(project_dir.join(argfile))?;
+            let expanded: Vec<OsString> = tokenize_argfile(&content).into_iter().map(OsString::from).collect();
+            absolute.extend(absolute_args(project_dir, &expanded)?);
+        } else if let Some((option, value)) = text.split_once('=').filter(|(o, _)| PATH_OPTIONS.contains(o)) {
+            let value = absolute_path_list(project_dir, OsStr::new(value));
+            absolute.push(format!("{}={}", option, value.to_string_lossy()).into());
+        } else if text.starts_with('-') {
+            absolute.push(arg.clone());
+        } else {
+            absolute.push(project_dir.join(arg).into_os_string());
+        }
+        previous = Some(arg.as_os_str());
+    }
+    Ok(absolute)
+}
+
+/// Lista de rutas (separadas como en el classpath) con las entradas relativas unidas a `project_dir`.
+fn absolute_path_list(project_dir: &Path, value: &OsStr) -> OsString {
+    let separator = if cfg!(target_os = "windows") { ";" } else { ":" };
+    let value = value.to_string_lossy();
+    let entries: Vec<String> = value
+        .split(separator)
+        .map(|entry| {
+            if entry.is_empty() {
+                entry.to_string()
+            } else {
+                project_dir.join(entry).to_string_lossy().to_string()
+            }
+        })
+        .collect();
+    entries.join(separator).into()
+}
+
+/// Separa un @argfile como javac: espacios, comillas simples o dobles y `#` para comentarios.
+fn tokenize_argfile(content: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+    let mut in_token = false;
+    let mut quote = None;
+    let mut chars = content.chars();
+    while let Some(c) = chars.next() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) if c == '\\' => token.extend(chars.next()),
+            Some(_) => token.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                in_token = true;
+            }
+            None if c == '#' && !in_token => {
+                for skipped in chars.by_ref() {
+                    if skipped == '\n' {
+                        break;
+                    }
+                }
+            }
+            None if c.is_whitespace() => {
+                if in_token {
+                    tokens.push(std::mem::take(&mut token));
+                    in_token = false;
+                }
+            }
+            None => {
+                token.push(c);
+                in_token = true;
+            }
+        }
+    }
+    if in_token {
+        tokens.push(token);
+    }
+    tokens
+}
+
+/// Líneas de una petición de compilación. `None` si algún argumento no cabe en
+/// el protocolo (saltos de línea o UTF-8 inválido).
+fn compile_request(tool: &Tool, args: &[OsString]) -> Option<Vec<String>> {
+    let (name, classpath) = match tool {
+        Tool::Javac => ("javac", ""),
+        Tool::Kotlinc { classpath } => ("kotlinc", classpath.as_str()),
+    };
+    let mut lines = vec![
+        "compile".to_string(),
+        name.to_string(),
+        classpath.to_string(),
+        args.len().to_string(),
+    ];
+    for arg in args {
+        lines.push(arg.to_str()?.to_string());
+    }
+    if lines.iter().any(|line| line.contains(['\n', '\r'])) {
+        return None;
+    }
+    Some(lines)
+}
+
+async fn compile_in_daemon(tool: &Tool, project_dir: &Path, args: &[OsString]) -> std::io::Result<CompilerOutput> {
+    let project_dir = std::path::absolute(project_dir)?;
+    let args = absolute_args(&project_dir, args)?;
+    let request = compile_request(tool, &args)
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "argumentos no admitidos"))?;
+    let dir = daemon_dir();
+
+    // Sin archivo de estado o sin nadie escuchando en su puerto: se lanza otro daemon
+    let (state, stream) = match running(&dir).await {
+        Some(running) => running,
+        None => start_locked(&dir).await?,
+    };
+    let (code, text) = exchange(stream, &state, &request).await?;
+    Ok(CompilerOutput {
+        code,
+        stderr: text.into_bytes(),
+    })
+}
+
+/// Daemon que responde en el puerto de su archivo de estado, con la conexión abierta.
+async fn running(dir: &Path) -> Option<(DaemonState, tokio::net::TcpStream)> {
+    let state = read_state(dir)?;
+    let stream = connect(&state).await.ok()?;
+    Some((state, stream))
+}
+
+/// Lanza el daemon con el lock de arranque tomado. Quien espera el lock vuelve a
+/// mirar el archivo de estado: si otro build ya lo lanzó, se conecta a ese.
+async fn start_locked(dir: &Path) -> std::io::Result<(DaemonState, tokio::net::TcpStream)> {
+    std::fs::create_dir_all(dir)?;
+    let file = std::fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(dir.join(START_LOCK_FILE))?;
+    let lock = tokio::task::spawn_blocking(move || FileExt::lock_exclusive(&file).map(|_| file))
+        .await
+        .map_err(std::io::Error::other)??;
+
+    let result = match running(dir).await {
+        Some(running) => Ok(running),
+        None => match start(dir).await {
+            Ok(state) => connect(&state).await.map(|stream| (state, stream)),
+            Err(e) => Err(e),
+        },
+    };
+    let _ = FileExt::unlock(&lock);
+    result
+}
+
+async fn connect(state: &DaemonState) -> std::io::Result<tokio::net::TcpStream> {
+    tokio::net::TcpStream::connect(("127.0.0.1", state.port)).await
+}
+
+/// Conecta, envía una petición y devuelve (código, texto).
+async fn send(state: &DaemonState, lines: &[String]) -> std::io::Result<(i32, String)> {
+    exchange(connect(state).await?, state, lines).await
+}
+
+/// Una conexión cerrada sin respuesta significa que el daemon no pudo compilar.
+async fn exchange(
+    mut stream: tokio::net::TcpStream,
+    state: &DaemonState,
+    lines: &[String],
+) -> std::io::Result<(i32, String)> {
+    let mut payload = format!("{}\n", state.token);
+    for line in lines {
+        payload.push_str(line);
+        payload.push('\n');
+    }
+    stream.write_all(payload.as_bytes()).await?;
+
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response).await?;
+    let response = String::from_utf8_lossy(&response);
+    let (code, text) = response.split_once('\n').unwrap_or((&response, ""));
+    let code = code.trim().parse().map_err(|_| {
+        std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "el daemon no respondió")
+    })?;
+    Ok((code, text.to_string()))
+}
+
+fn read_state(dir: &Path) -> Option<DaemonState> {
+    parse_state(&std::fs::read_to_string(dir.join(STATE_FILE)).ok()?)
+}
+
+fn parse_state(content: &str) -> Option<DaemonState> {
+    let value = |key: &str| {
+        content
+            .lines()
+            .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))
+            .map(str::trim)
+    };
+    Some(DaemonState {
+        port: value("port")?.parse().ok()?,
+        token: value("token")?.to_string(),
+        pid: value("pid")?.parse().ok()?,
+    })
+}
+
+/// Compila el daemon si hace falta, lo lanza desacoplado de la terminal (Ctrl+C
+/// en `forge watch` no lo detiene) y espera a que publique su puerto.
+async fn start(dir: &Path) -> std::io::Result<DaemonState> {
+    std::fs::create_dir_all(dir)?;
+    if !dir.join(format!("{}.class", DAEMON_CLASS)).exists() {
+        let source = dir.join(format!("{}.java", DAEMON_CLASS));
+        std::fs::write(&source, DAEMON_SOURCE)?;
+        let output = tokio::process::Command::new("javac")
+            .arg("-nowarn")
+            .arg("-encoding")
+            .arg("UTF-8")
+            .arg("-d")
+            .arg(dir)
+            .arg(&source)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
+            .await?;
+        if !output.status.success() {
+            return Err(std::io::Error::other(format!(
+                "javac falló al compilar el daemon: {}",
+                String::from_utf8_lossy(&output.stderr).lines().next().unwrap_or_default()
+            )));
+        }
+    }
+
+    println!("   {}", "🔌 Iniciando daemon de compilación...".dimmed());
+    let state_file = dir.join(STATE_FILE);
+    let _ = std::fs::remove_file(&state_file);
+    let idle_timeout = UserSettings::load().map(|s| s.daemon.idle_timeout).unwrap_or(180).max(1);
+    let log = std::fs::File::create(dir.join("daemon.log"))?;
+
+    let mut cmd = std::process::Command::new("java");
+    cmd.arg("-cp")
+        .arg(dir)
+        .arg(DAEMON_CLASS)
+        .arg(&state_file)
+        .arg(idle_timeout.to_string())
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(log);
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        // DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP
+        cmd.creation_flags(0x0000_0008 | 0x0000_0200);
+    }
+    let mut child = cmd.spawn()?;
+
+    let started = Instant::now();
+    while started.elapsed() < STARTUP_TIMEOUT {
+        if let Some(state) = read_state(dir) {
+            return Ok(state);
+        }
+        if let Some(status) = child.try_wait()? {
+            return Err(std::io::Error::other(format!(
+                "el daemon terminó al arrancar ({}), ver {}",
+                status,
+                dir.join("daemon.log").display()
+            )));
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    Err(std::io::Error::new(std::io::ErrorKind::TimedOut, "el daemon no arrancó a tiempo"))
+}
+
+/// Daemons en ejecución del usuario (todas las versiones y JDKs).
+pub async fn status() -> Vec<DaemonInfo> {
+    let mut daemons = Vec::new();
+    for (dir, state) in running_states() {
+        match send(&state, &["status".to_string()]).await {
+            Ok((_, text)) => {
+                let value = |key: &str| {
+                    text.lines()
+                        .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))
+                        .unwrap_or_default()
+                        .to_string()
+                };
+                daemons.push(DaemonInfo {
+                    pid: value("pid").parse().unwrap_or(state.pid),
+                    java_home: value("java"),
+                    uptime_secs: value("uptime").parse().unwrap_or(0),
+                    idle_secs: value("idle").parse().unwrap_or(0),
+                    compilations: value("compilations").parse().unwrap_or(0),
+                });
+            }
+            // Daemon muerto (ej: reinicio del equipo): solo queda su archivo de estado
+            Err(_) => {
+                let _ = std::fs::remove_file(dir.join(STATE_FILE));
+            }
+        }
+    }
+    daemons
+}
+
+/// Detiene todos los daemons del usuario y devuelve cuántos estaban en ejecución.
+pub async fn stop_all() -> usize {
+    let mut stopped = 0;
+    for (dir, state) in running_states() {
+        if send(&state, &["stop".to_string()]).await.is_ok() {
+            stopped += 1;
+        }
+        let _ = std::fs::remove_file(dir.join(STATE_FILE));
+    }
+    stopped
+}
+
+fn running_states() -> Vec<(PathBuf, DaemonState)> {
+    let Ok(entries) = std::fs::read_dir(daemons_dir()) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter_map(|dir| read_state(&dir).map(|state| (dir, state)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compile_request_lines() {
+        let tool = Tool::Kotlinc {
+            classpath: "/store/kotlin-compiler-embeddable.jar".to_string(),
+        };
+        let args: Vec<OsString> = vec!["-d".into(), "/app/build/classes".into(), "/app/src/Main.kt".into()];
+        assert_eq!(
+            compile_request(&tool, &args).unwrap(),
+            vec![
+                "compile",
+                "kotlinc",
+                "/store/kotlin-compiler-embeddable.jar",
+                "3",
+                "-d",
+                "/app/build/classes",
+                "/app/src/Main.kt"
+            ]
+        );
+
+        // Un salto de línea rompería el protocolo: se compila sin daemon
+        assert!(compile_request(&Tool::Javac, &["-Aopcion=a\nb".into()]).is_none());
+        assert!(compile_request(&Tool::Javac, &["/ap\np/Main.java".into()]).is_none());
+    }
+
+    #[test]
+    fn test_absolute_args_resolve_against_project_dir() {
+        let dir = std::env::temp_dir().join("forge_test_daemon_absolute_args");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("compile.args"),
+            "# opciones extra\n-processorpath 'procesadores/a b.jar'\nsrc/Extra.java -encoding UTF-8\n",
+        )
+        .unwrap();
+
+        let sep = if cfg!(target_os = "windows") { ";" } else { ":" };
+        let absolute_lib = dir.join("fuera.jar");
+        let args: Vec<OsString> = [
+            "-d".to_string(),
+            "build/classes".to_string(),
+            "-s".to_string(),
+            "build/generated".to_string(),
+            "-source".to_string(),
+            "17".to_string(),
+            "-cp".to_string(),
+            format!("libs/a.jar{}{}", sep, absolute_lib.display()),
+            "-Xlint:all".to_string(),
+            "-Xplugin=plugins/p.jar".to_string(),
+            "@compile.args".to_string(),
+            "src/Main.java".to_string(),
+        ]
+        .into_iter()
+        .map(OsString::from)
+        .collect();
+
+        let resolved = absolute_args(&dir, &args).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let path = |relative: &str| dir.join(relative).to_string_lossy().to_string();
+        let expected: Vec<OsString> = [
+            "-d".to_string(),
+            path("build/classes"),
+            "-s".to_string(),
+            path("build/generated"),
+            "-source".to_string(),
+            "17".to_string(),
+            "-cp".to_string(),
+            format!("{}{}{}", path("libs/a.jar"), sep, absolute_lib.display()),
+            "-Xlint:all".to_string(),
+            format!("-Xplugin={}", path("plugins/p.jar")),
+            "-processorpath".to_string(),
+            path("procesadores/a b.jar"),
+            path("src/Extra.java"),
+            "-encoding".to_string(),
+            "UTF-8".to_string(),
+            path("src/Main.java"),
+        ]
+        .into_iter()
+        .map(OsString::from)
+        .collect();
+        assert_eq!(resolved, expected);
+
+        // Un @argfile que no existe: el compilador sin daemon da el error de siempre
+        assert!(absolute_args(&dir, &["@no-existe.args".into()]).is_err());
+    }
+
+    #[test]
+    fn test_tokenize_argfile() {
+        assert_eq!(
+            tokenize_argfile("-g  \"con espacio/A.java\"\n# comentario\n'a\\'b' c#d"),
+            ["-g", "con espacio/A.java", "a'b", "c#d"]
+        );
+    }
+
+    #[test]
+    fn test_parse_state() {
+        assert_eq!(
+            parse_state("port=41234\ntoken=abc123\npid=987\n"),
+            Some(DaemonState {
+                port: 41234,
+                token: "abc123".to_string(),
+                pid: 987
+            })
+        );
+        assert_eq!(parse_state("port=41234\n"), None);
+    }
+}
@@ -0,0 +1,230 @@
+// =============================================================================
+// 🔥 FORGE — Daemon de compilación
+// =============================================================================
+// Proceso JVM de larga vida que forge compila y lanza en el primer build
+// (ver crates/forge-langs/src/daemon.rs). Atiende peticiones en 127.0.0.1:
+// javac vía javax.tools y el compilador de Kotlin vía reflexión sobre
+// K2JVMCompiler, cargado una sola vez por classpath. Termina solo tras
+// `idle-timeout` minutos sin compilar.
+//
+// Protocolo (líneas UTF-8): token, comando (compile | status | stop) y, para
+// compile, herramienta (javac | kotlinc), classpath del compilador, número de
+// argumentos y un argumento por línea. Respuesta: código de salida en la
+// primera línea y luego los diagnósticos.
+//
+// El proceso no corre en el directorio del proyecto: forge envía las rutas ya
+// absolutas y los @argfiles expandidos. Si el daemon no puede compilar
+// (compilador no encontrado, error interno), cierra sin responder y forge
+// repite la compilación con el proceso de siempre.
+// =============================================================================
+
+import java.io.BufferedReader;
+import java.io.ByteArrayOutputStream;
+import java.io.File;
+import java.io.IOException;
+import java.io.InputStreamReader;
+import java.io.OutputStream;
+import java.io.PrintStream;
+import java.lang.reflect.Method;
+import java.net.InetAddress;
+import java.net.ServerSocket;
+import java.net.Socket;
+import java.net.URL;
+import java.net.URLClassLoader;
+import java.nio.charset.StandardCharsets;
+import java.nio.file.FileSystems;
+import java.nio.file.Files;
+import java.nio.file.Path;
+import java.nio.file.Paths;
+import java.nio.file.StandardCopyOption;
+import java.nio.file.attribute.PosixFilePermissions;
+import java.security.MessageDigest;
+import java.security.SecureRandom;
+import java.util.Map;
+import java.util.concurrent.ConcurrentHashMap;
+import java.util.concurrent.atomic.AtomicInteger;
+import java.util.concurrent.atomic.AtomicLong;
+import javax.tools.JavaCompiler;
+import javax.tools.ToolProvider;
+
+public final class ForgeCompilerDaemon {
+    private static final String KOTLIN_COMPILER = "org.jetbrains.kotlin.cli.jvm.K2JVMCompiler";
+    private static final long STACK_SIZE = 64L << 20;
+
+    private static final Map<String, ClassLoader> LOADERS = new ConcurrentHashMap<>();
+    private static final AtomicInteger ACTIVE = new AtomicInteger();
+    private static final AtomicLong LAST_USE = new AtomicLong(System.currentTimeMillis());
+    private static final AtomicLong COMPILATIONS = new AtomicLong();
+    private static final long STARTED = System.currentTimeMillis();
+
+    private static Path stateFile;
+    private static String state;
+    private static String token;
+
+    public static void main(String[] args) throws Exception {
+        stateFile = Paths.get(args[0]);
+        long idleMillis = Math.max(1, Long.parseLong(args[1])) * 60_000L;
+
+        byte[] random = new byte[24];
+        new SecureRandom().nextBytes(random);
+        StringBuilder hex = new StringBuilder();
+        for (byte b : random) {
+            hex.append(String.format("%02x", b));
+        }
+        token = hex.toString();
+
+        ServerSocket server = new ServerSocket(0, 50, InetAddress.getLoopbackAddress());
+        state = "port=" + server.getLocalPort() + "\ntoken=" + token + "\npid=" + ProcessHandle.current().pid() + "\n";
+        writeState();
+
+        Thread watchdog = new Thread(() -> {
+            while (true) {
+                try {
+                    Thread.sleep(Math.min(idleMillis, 10_000L));
+                } catch (InterruptedException e) {
+                    return;
+                }
+                if (ACTIVE.get() == 0 && System.currentTimeMillis() - LAST_USE.get() > idleMillis) {
+                    shutdown();
+                }
+            }
+        }, "forge-daemon-idle");
+        watchdog.setDaemon(true);
+        watchdog.start();
+
+        while (true) {
+            Socket socket = server.accept();
+            new Thread(null, () -> handle(socket), "forge-daemon-request", STACK_SIZE).start();
+        }
+    }
+
+    // Escribe port/token/pid de forma atómica y legible solo por el usuario.
+    private static void writeState() throws IOException {
+        Path tmp = stateFile.resolveSibling(stateFile.getFileName() + ".tmp");
+        Files.deleteIfExists(tmp);
+        if (FileSystems.getDefault().supportedFileAttributeViews().contains("posix")) {
+            Files.createFile(tmp, PosixFilePermissions.asFileAttribute(PosixFilePermissions.fromString("rw-------")));
+        }
+        Files.write(tmp, state.getBytes(StandardCharsets.UTF_8));
+        Files.move(tmp, stateFile, StandardCopyOption.REPLACE_EXISTING, StandardCopyOption.ATOMIC_MOVE);
+    }
+
+    // Termina el proceso; borra el archivo de estado solo si sigue siendo el suyo.
+    private static synchronized void shutdown() {
+        try {
+            if (state.equals(new String(Files.readAllBytes(stateFile), StandardCharsets.UTF_8))) {
+                Files.delete(stateFile);
+            }
+        } catch (IOException ignored) {
+            // Otro daemon ya ocupó su lugar
+        }
+        System.exit(0);
+    }
+
+    private static void handle(Socket socket) {
+        try (Socket s = socket) {
+            BufferedReader in = new BufferedReader(new InputStreamReader(s.getInputStream(), StandardCharsets.UTF_8));
+            OutputStream out = s.getOutputStream();
+            String received = String.valueOf(in.readLine());
+            if (!MessageDigest.isEqual(token.getBytes(StandardCharsets.UTF_8), received.getBytes(StandardCharsets.UTF_8))) {
+                return;
+            }
+
+            String command = String.valueOf(in.readLine());
+            if (command.equals("status")) {
+                respond(out, 0, status());
+            } else if (command.equals("stop")) {
+                respond(out, 0, "");
+                shutdown();
+            } else if (command.equals("compile")) {
+                ACTIVE.incrementAndGet();
+                try {
+                    compile(in, out);
+                } finally {
+                    LAST_USE.set(System.currentTimeMillis());
+                    ACTIVE.decrementAndGet();
+                }
+            }
+        } catch (IOException | RuntimeException ignored) {
+            // Petición incompleta: forge compila sin daemon
+        }
+    }
+
+    private static void compile(BufferedReader in, OutputStream out) throws IOException {
+        String tool = in.readLine();
+        String classpath = in.readLine();
+        int count = Integer.parseInt(in.readLine());
+        String[] args = new String[count];
+        for (int i = 0; i < count; i++) {
+            args[i] = in.readLine();
+        }
+
+        ByteArrayOutputStream log = new ByteArrayOutputStream();
+        PrintStream err = new PrintStream(log, true, "UTF-8");
+        int code;
+        try {
+            code = "kotlinc".equals(tool) ? kotlinc(classpath, args, err) : javac(args, err);
+        } catch (Throwable e) {
+            // Sin respuesta: forge repite la compilación fuera del daemon
+            return;
+        }
+        err.flush();
+        COMPILATIONS.incrementAndGet();
+        respond(out, code, log.toString("UTF-8"));
+    }
+
+    private static int javac(String[] args, PrintStream err) {
+        JavaCompiler compiler = ToolProvider.getSystemJavaCompiler();
+        if (compiler == null) {
+            throw new IllegalStateException("javax.tools no disponible (¿JRE en lugar de JDK?)");
+        }
+        return compiler.run(null, err, err, args);
+    }
+
+    private static int kotlinc(String classpath, String[] args, PrintStream err) throws Exception {
+        ClassLoader loader = LOADERS.computeIfAbsent(classpath, ForgeCompilerDaemon::loader);
+        Class<?> type = Class.forName(KOTLIN_COMPILER, true, loader);
+        Method exec = type.getMethod("exec", PrintStream.class, String[].class);
+
+        // El compilador de Kotlin guarda estado global: una compilación a la vez por classpath
+        synchronized (loader) {
+            Thread thread = Thread.currentThread();
+            ClassLoader previous = thread.getContextClassLoader();
+            thread.setContextClassLoader(loader);
+            try {
+                Object exitCode = exec.invoke(type.getDeclaredConstructor().newInstance(), err, args);
+                return (Integer) exitCode.getClass().getMethod("getCode").invoke(exitCode);
+            } finally {
+                thread.setContextClassLoader(previous);
+            }
+        }
+    }
+
+    private static ClassLoader loader(String classpath) {
+        String[] entries = classpath.split(File.pathSeparator);
+        URL[] urls = new URL[entries.length];
+        try {
+            for (int i = 0; i < entries.length; i++) {
+                urls[i] = Paths.get(entries[i]).toUri().toURL();
+            }
+        } catch (IOException e) {
+            throw new IllegalArgumentException(e);
+        }
+        return new URLClassLoader(urls, ForgeCompilerDaemon.class.getClassLoader());
+    }
+
+    private static String status() {
+        long now = System.currentTimeMillis();
+        return "pid=" + ProcessHandle.current().pid()
+            + "\njava=" + System.getProperty("java.home")
+            + "\nuptime=" + (now - STARTED) / 1000
+            + "\nidle=" + (ACTIVE.get() > 0 ? 0 : (now - LAST_USE.get()) / 1000)
+            + "\ncompilations=" + COMPILATIONS.get()
+            + "\n";
+    }
+
+    private static void respond(OutputStream out, int code, String text) throws IOException {
+        out.write((code + "\n" + text).getBytes(StandardCharsets.UTF_8));
+        out.flush();
+    }
+}
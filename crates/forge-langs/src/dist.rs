@@ -0,0 +1,279 @@
+// =============================================================================
+// 🔥 FORGE — Módulos de Lenguaje: Distribución de la aplicación
+// =============================================================================
+// `forge package --dist`: el JAR y su classpath runtime en lib/ más lanzadores
+// bin/<nombre> (POSIX) y bin/<nombre>.bat (Windows), empaquetados como .zip y
+// .tar.gz con la carpeta raíz <nombre>-<versión>/ (como el plugin `application`
+// de Gradle). Los lanzadores usan el java de JAVA_HOME (o el del PATH), las
+// opciones de `[package] jvm-options` y las variables JAVA_OPTS y <NOMBRE>_OPTS.
+// =============================================================================
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, DateTime, ZipWriter};
+
+use cyrce_forge_core::config::ForgeConfig;
+use cyrce_forge_core::error::ForgeResult;
+
+use crate::image::{app_jars, file_name, io_error, launch_options, main_class, sh_quote, Launch};
+use crate::jpms::ModuleDescriptor;
+
+/// Directorio de la distribución con los JARs.
+const LIB_DIR: &str = "lib";
+
+/// Fecha de las entradas del tar: 1980-01-01, la misma que usan los JARs.
+const TAR_MTIME: u64 = 315_532_800;
+
+/// Contenido de una entrada de la distribución.
+enum Source {
+    File(PathBuf),
+    Bytes(Vec<u8>),
+}
+
+/// Archivo de la distribución, con su ruta relativa a la carpeta raíz.
+struct Entry {
+    path: String,
+    source: Source,
+    executable: bool,
+}
+
+/// Crea `<nombre>.zip` y `<nombre>.tar.gz` en `dist_dir` y devuelve sus rutas.
+/// `classpath` es el classpath runtime del JAR (listas separadas por la plataforma).
+pub fn create_distribution(
+    config: &ForgeConfig,
+    project_dir: &Path,
+    jar: &Path,
+    classpath: &[String],
+    dist_dir: &Path,
+    archive_name: &str,
+) -> ForgeResult<Vec<PathBuf>> {
+    let main_class = main_class(config)?;
+    let jars = app_jars(config, jar, classpath);
+    let descriptor = ModuleDescriptor::load(&project_dir.join(config.source_dir()));
+    let launch = Launch::new(descriptor.as_ref(), &jars, &main_class);
+    let options = launch_options(config);
+    let name = &config.project.name;
+
+    let mut entries = vec![
+        Entry {
+            path: format!("bin/{}", name),
+            source: Source::Bytes(unix_launcher(name, &options, &launch).into_bytes()),
+            executable: true,
+        },
+        Entry {
+            path: format!("bin/{}.bat", name),
+            source: Source::Bytes(windows_launcher(name, &options, &launch).into_bytes()),
+            executable: false,
+        },
+    ];
+    entries.extend(jars.iter().map(|jar| Entry {
+        path: format!("{}/{}", LIB_DIR, file_name(jar)),
+        source: Source::File(jar.clone()),
+        executable: false,
+    }));
+
+    let root = format!("{}-{}", name, config.project.version);
+    let zip = dist_dir.join(format!("{}.zip", archive_name));
+    write_zip(&zip, &root, &entries)?;
+    let tar = dist_dir.join(format!("{}.tar.gz", archive_name));
+    write_tar_gz(&tar, &root, &entries)?;
+    Ok(vec![zip, tar])
+}
+
+/// Variable de entorno con opciones extra para la JVM (`my-app` → `MY_APP_OPTS`).
+fn opts_variable(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect();
+    format!("{}_OPTS", name)
+}
+
+/// Lanzador POSIX: java de JAVA_HOME o del PATH, opciones por defecto y luego las
+/// de JAVA_OPTS y <NOMBRE>_OPTS (sin comillas, para que se separen por espacios).
+fn unix_launcher(name: &str, options: &[String], launch: &Launch) -> String {
+    let quoted = |args: &[String]| args.iter().map(|arg| format!(" {}", sh_quote(arg))).collect::<String>();
+    let paths: String = launch
+        .path_args("$APP_HOME", LIB_DIR, '/', ":")
+        .iter()
+        .map(|arg| format!(" \"{}\"", arg))
+        .collect();
+    format!(
+        "#!/bin/sh\n\
+         APP_HOME=\"$(cd \"$(dirname \"$0\")/..\" && pwd)\"\n\
+         if [ -n \"$JAVA_HOME\" ]; then\n    JAVACMD=\"$JAVA_HOME/bin/java\"\nelse\n    JAVACMD=java\nfi\n\
+         exec \"$JAVACMD\"{} $JAVA_OPTS ${}{}{} \"$@\"\n",
+        quoted(options),
+        opts_variable(name),
+        paths,
+        quoted(&launch.target)
+    )
+}
+
+/// Lanzador para Windows.
+fn windows_launcher(name: &str, options: &[String], launch: &Launch) -> String {
+    let quoted = |args: Vec<String>| args.iter().map(|arg| format!(" \"{}\"", arg)).collect::<String>();
+    format!(
+        "@echo off\r\n\
+         setlocal\r\n\
+         set APP_HOME=%~dp0..\r\n\
+         set JAVACMD=java.exe\r\n\
+         if defined JAVA_HOME set \"JAVACMD=%JAVA_HOME%\\bin\\java.exe\"\r\n\
+         \"%JAVACMD%\"{} %JAVA_OPTS% %{}%{}{} %*\r\n",
+        quoted(options.to_vec()),
+        opts_variable(name),
+        quoted(launch.path_args("%APP_HOME%", LIB_DIR, '\\', ";")),
+        quoted(launch.target.clone())
+    )
+}
+
+/// Directorios de la distribución: la raíz y los padres de cada entrada.
+fn directories(root: &str, entries: &[Entry]) -> Vec<String> {
+    let mut dirs = vec![format!("{}/", root)];
+    for entry in entries {
+        if let Some((parent, _)) = entry.path.rsplit_once('/') {
+            let dir = format!("{}/{}/", root, parent);
+            if !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
+        }
+    }
+    dirs
+}
+
+fn write_zip(path: &Path, root: &str, entries: &[Entry]) -> ForgeResult<()> {
+    let file = std::fs::File::create(path).map_err(|e| io_error(path, e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .last_modified_time(DateTime::default())
+        .unix_permissions(0o644);
+
+    for dir in directories(root, entries) {
+        zip.add_directory(dir, options.unix_permissions(0o755))
+            .map_err(|e| io_error(path, e))?;
+    }
+    for entry in entries {
+        let mode = if entry.executable { 0o755 } else { 0o644 };
+        zip.start_file(format!("{}/{}", root, entry.path), options.unix_permissions(mode))
+            .map_err(|e| io_error(path, e))?;
+        match &entry.source {
+            Source::Bytes(bytes) => zip.write_all(bytes).map_err(|e| io_error(path, e))?,
+            Source::File(file) => {
+                let mut input = std::fs::File::open(file).map_err(|e| io_error(file, e))?;
+                std::io::copy(&mut input, &mut zip).map_err(|e| io_error(path, e))?;
+            }
+        }
+    }
+    zip.finish().map_err(|e| io_error(path, e))?;
+    Ok(())
+}
+
+fn write_tar_gz(path: &Path, root: &str, entries: &[Entry]) -> ForgeResult<()> {
+    let file = std::fs::File::create(path).map_err(|e| io_error(path, e))?;
+    let mut tar = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+
+    let header = |size: u64, mode: u32, kind: tar::EntryType| {
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(kind);
+        header.set_size(size);
+        header.set_mode(mode);
+        header.set_mtime(TAR_MTIME);
+        header
+    };
+
+    for dir in directories(root, entries) {
+        tar.append_data(&mut header(0, 0o755, tar::EntryType::Directory), dir, std::io::empty())
+            .map_err(|e| io_error(path, e))?;
+    }
+    for entry in entries {
+        let mode = if entry.executable { 0o755 } else { 0o644 };
+        let name = format!("{}/{}", root, entry.path);
+        match &entry.source {
+            Source::Bytes(bytes) => tar.append_data(
+                &mut header(bytes.len() as u64, mode, tar::EntryType::Regular),
+                name,
+                bytes.as_slice(),
+            ),
+            Source::File(file) => {
+                let input = std::fs::File::open(file).map_err(|e| io_error(file, e))?;
+                let size = input.metadata().map_err(|e| io_error(file, e))?.len();
+                tar.append_data(&mut header(size, mode, tar::EntryType::Regular), name, input)
+            }
+        }
+        .map_err(|e| io_error(path, e))?;
+    }
+    tar.into_inner()
+        .and_then(|gz| gz.finish())
+        .map_err(|e| io_error(path, e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_unix_launcher_uses_java_home_and_opts() {
+        let launch = Launch::new(None, &[PathBuf::from("build/my-app.jar")], "com.acme.Main");
+        let script = unix_launcher("my-app", &["-Xmx512m".to_string()], &launch);
+
+        assert!(script.contains(r#"JAVACMD="$JAVA_HOME/bin/java""#));
+        assert!(script.contains(
+            r#"exec "$JAVACMD" '-Xmx512m' $JAVA_OPTS $MY_APP_OPTS "-cp" "$APP_HOME/lib/my-app.jar" 'com.acme.Main' "$@""#
+        ));
+        assert!(windows_launcher("my-app", &[], &launch).contains(r#"%JAVA_OPTS% %MY_APP_OPTS% "-cp" "%APP_HOME%\lib\my-app.jar""#));
+    }
+
+    #[test]
+    fn test_archives_share_layout_and_permissions() {
+        let dir = std::env::temp_dir().join("forge_test_dist_archives");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let jar = dir.join("app.jar");
+        std::fs::write(&jar, b"jar").unwrap();
+        let entries = vec![
+            Entry {
+                path: "bin/app".to_string(),
+                source: Source::Bytes(b"#!/bin/sh\n".to_vec()),
+                executable: true,
+            },
+            Entry {
+                path: "lib/app.jar".to_string(),
+                source: Source::File(jar),
+                executable: false,
+            },
+        ];
+
+        let zip_path = dir.join("app.zip");
+        write_zip(&zip_path, "app-1.0", &entries).unwrap();
+        let mut zip = zip::ZipArchive::new(std::fs::File::open(&zip_path).unwrap()).unwrap();
+        let names: Vec<&str> = zip.file_names().collect();
+        assert!(names.contains(&"app-1.0/bin/"));
+        assert_eq!(zip.by_name("app-1.0/bin/app").unwrap().unix_mode().unwrap() & 0o777, 0o755);
+        let mut content = String::new();
+        zip.by_name("app-1.0/lib/app.jar").unwrap().read_to_string(&mut content).unwrap();
+        assert_eq!(content, "jar");
+
+        let tar_path = dir.join("app.tar.gz");
+        write_tar_gz(&tar_path, "app-1.0", &entries).unwrap();
+        let gz = flate2::read::GzDecoder::new(std::fs::File::open(&tar_path).unwrap());
+        let files: Vec<(String, u32)> = tar::Archive::new(gz)
+            .entries()
+            .unwrap()
+            .map(|e| {
+                let e = e.unwrap();
+                (e.path().unwrap().to_string_lossy().to_string(), e.header().mode().unwrap())
+            })
+            .collect();
+        assert!(files.contains(&("app-1.0/bin/app".to_string(), 0o755)));
+        assert!(files.contains(&("app-1.0/lib/app.jar".to_string(), 0o644)));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
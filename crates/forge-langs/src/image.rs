@@ -0,0 +1,465 @@
+// =============================================================================
+// 🔥 FORGE — Módulos de Lenguaje: Imagen de runtime e instaladores
+// =============================================================================
+// `forge package --image`: runtime mínimo con jlink (los módulos del JDK que
+// jdeps detecta en el JAR y sus dependencias) más la aplicación y lanzadores
+// en bin/, ejecutable sin un JRE instalado.
+//
+// `forge package --installer`: el mismo runtime empaquetado por jpackage como
+// instalador nativo (deb, rpm, dmg, msi...) con la configuración de
+// [package.installer].
+//
+// Un proyecto modular (module-info.java) arranca con `-m módulo/clase` desde el
+// module path, igual que en `forge run`.
+// =============================================================================
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use colored::Colorize;
+
+use cyrce_forge_core::config::{ForgeConfig, PackageMode};
+use cyrce_forge_core::error::{ForgeError, ForgeResult};
+
+use crate::java::jvm_options;
+use crate::jpms::{self, ModuleDescriptor};
+
+/// Opciones de jlink que siempre se aplican: el runtime no necesita nada de esto.
+const JLINK_DEFAULTS: [&str; 3] = ["--strip-debug", "--no-header-files", "--no-man-pages"];
+
+/// Directorio de la imagen con los JARs de la aplicación.
+const APP_DIR: &str = "app";
+
+/// Cómo arranca la JVM la aplicación: nombres de los JARs y clase principal.
+/// También lo usan los lanzadores de `forge package --dist`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Launch {
+    pub(crate) module_path: Vec<String>,
+    pub(crate) class_path: Vec<String>,
+    /// `com.acme.Main` o, en un proyecto modular, `-m com.acme.app/com.acme.Main`
+    pub(crate) target: Vec<String>,
+}
+
+impl Launch {
+    pub(crate) fn new(descriptor: Option<&ModuleDescriptor>, jars: &[PathBuf], main_class: &str) -> Self {
+        let names = |paths: Vec<String>| paths.iter().map(|p| file_name(Path::new(p))).collect();
+        match descriptor {
+            Some(descriptor) => {
+                let entries: Vec<String> = jars.iter().map(|j| j.to_string_lossy().to_string()).collect();
+                let (modules, plain) = descriptor.split(&entries);
+                Self {
+                    module_path: names(modules),
+                    class_path: names(plain),
+                    target: vec!["-m".to_string(), format!("{}/{}", descriptor.name, main_class)],
+                }
+            }
+            None => Self {
+                module_path: Vec::new(),
+                class_path: jars.iter().map(|j| file_name(j)).collect(),
+                target: vec![main_class.to_string()],
+            },
+        }
+    }
+
+    /// Argumentos `--module-path`/`-cp` con cada JAR como `<dir>/<jars_dir>/<jar>`.
+    pub(crate) fn path_args(&self, dir: &str, jars_dir: &str, file_separator: char, path_separator: &str) -> Vec<String> {
+        let join = |jars: &[String]| {
+            jars.iter()
+                .map(|jar| format!("{}{}{}{}{}", dir, file_separator, jars_dir, file_separator, jar))
+                .collect::<Vec<_>>()
+                .join(path_separator)
+        };
+        let mut args = Vec::new();
+        if !self.module_path.is_empty() {
+            args.extend(["--module-path".to_string(), join(&self.module_path)]);
+        }
+        if !self.class_path.is_empty() {
+            args.extend(["-cp".to_string(), join(&self.class_path)]);
+        }
+        args
+    }
+}
+
+/// Crea en `output` un runtime jlink con la aplicación en app/ y lanzadores en bin/.
+/// `classpath` es el classpath runtime del JAR (listas separadas por la plataforma).
+pub async fn create_image(
+    config: &ForgeConfig,
+    project_dir: &Path,
+    jar: &Path,
+    classpath: &[String],
+    output: &Path,
+) -> ForgeResult<PathBuf> {
+    let main_class = main_class(config)?;
+    let jars = app_jars(config, jar, classpath);
+    let descriptor = ModuleDescriptor::load(&project_dir.join(config.source_dir()));
+
+    link_runtime(config, descriptor.as_ref(), &jars, output).await?;
+
+    let app_dir = output.join(APP_DIR);
+    std::fs::create_dir_all(&app_dir).map_err(|e| io_error(&app_dir, e))?;
+    for jar in &jars {
+        std::fs::copy(jar, app_dir.join(file_name(jar))).map_err(|e| io_error(jar, e))?;
+    }
+
+    let options = launch_options(config);
+    let launch = Launch::new(descriptor.as_ref(), &jars, &main_class);
+    let launcher = output.join("bin").join(&config.project.name);
+    std::fs::write(&launcher, unix_launcher(&options, &launch)).map_err(|e| io_error(&launcher, e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&launcher, std::fs::Permissions::from_mode(0o755))
+            .map_err(|e| io_error(&launcher, e))?;
+    }
+    let bat = output.join("bin").join(format!("{}.bat", config.project.name));
+    std::fs::write(&bat, windows_launcher(&options, &launch)).map_err(|e| io_error(&bat, e))?;
+
+    Ok(launcher)
+}
+
+/// Genera un instalador con jpackage en `dist_dir` y devuelve lo que creó.
+pub async fn create_installer(
+    config: &ForgeConfig,
+    project_dir: &Path,
+    jar: &Path,
+    classpath: &[String],
+    dist_dir: &Path,
+) -> ForgeResult<Vec<PathBuf>> {
+    let main_class = main_class(config)?;
+    let jars = app_jars(config, jar, classpath);
+    let descriptor = ModuleDescriptor::load(&project_dir.join(config.source_dir()));
+    let work_dir = project_dir.join(&config.project.output_dir).join("jpackage");
+
+    // jpackage recibe el runtime ya enlazado y un directorio solo con los JARs
+    let runtime = work_dir.join("runtime");
+    link_runtime(config, descriptor.as_ref(), &jars, &runtime).await?;
+
+    let input = work_dir.join("input");
+    let _ = std::fs::remove_dir_all(&input);
+    std::fs::create_dir_all(&input).map_err(|e| io_error(&input, e))?;
+    for jar in &jars {
+        std::fs::copy(jar, input.join(file_name(jar))).map_err(|e| io_error(jar, e))?;
+    }
+
+    let installer = &config.package.installer;
+    let mut cmd = tokio::process::Command::new("jpackage");
+    cmd.arg("--name")
+        .arg(&config.project.name)
+        .arg("--app-version")
+        .arg(installer_version(&config.project.version));
+    let mut java_options = launch_options(config);
+    match &descriptor {
+        // Todo el directorio va al module path: los JARs planos quedan como módulos
+        // automáticos, resueltos aunque el descriptor no los `requires`
+        Some(descriptor) => {
+            cmd.arg("--module-path")
+                .arg(&input)
+                .arg("--module")
+                .arg(format!("{}/{}", descriptor.name, main_class));
+            java_options.insert(0, "--add-modules=ALL-MODULE-PATH".to_string());
+        }
+        None => {
+            cmd.arg("--input")
+                .arg(&input)
+                .arg("--main-jar")
+                .arg(file_name(jar))
+                .arg("--main-class")
+                .arg(&main_class);
+        }
+    }
+    cmd.arg("--runtime-image")
+        .arg(&runtime)
+        .arg("--dest")
+        .arg(dist_dir);
+    if let Some(installer_type) = &installer.installer_type {
+        cmd.arg("--type").arg(installer_type);
+        // Una app-image previa con el mismo nombre hace fallar a jpackage
+        if installer_type == "app-image" {
+            let _ = std::fs::remove_dir_all(dist_dir.join(&config.project.name));
+        }
+    }
+    if !config.project.description.is_empty() {
+        cmd.arg("--description").arg(&config.project.description);
+    }
+    if let Some(vendor) = &installer.vendor {
+        cmd.arg("--vendor").arg(vendor);
+    }
+    if let Some(icon) = &installer.icon {
+        cmd.arg("--icon").arg(project_dir.join(icon));
+    }
+    for option in java_options {
+        // jpackage parte cada valor por espacios salvo que vaya entre comillas
+        if option.contains(char::is_whitespace) {
+            cmd.arg("--java-options").arg(format!("\"{}\"", option));
+        } else {
+            cmd.arg("--java-options").arg(option);
+        }
+    }
+    cmd.args(&installer.jpackage_args);
+
+    println!("   {}", "📀 Generando instalador con jpackage...".cyan());
+    let before = dist_entries(dist_dir);
+    run_tool(cmd, "jpackage").await?;
+
+    let created: Vec<PathBuf> = dist_entries(dist_dir)
+        .into_iter()
+        .filter(|entry| !before.contains(entry))
+        .map(|(path, _)| path)
+        .collect();
+    Ok(created)
+}
+
+/// Enlaza en `output` un runtime con los módulos que usan `jars` más `[package.image] add-modules`.
+async fn link_runtime(
+    config: &ForgeConfig,
+    descriptor: Option<&ModuleDescriptor>,
+    jars: &[PathBuf],
+    output: &Path,
+) -> ForgeResult<()> {
+    let mut modules = required_modules(config, descriptor, jars).await?;
+    modules.extend(config.package.image.add_modules.iter().cloned());
+    modules.sort();
+    modules.dedup();
+
+    println!(
+        "   {}",
+        format!("🔗 jlink: {} módulos ({})", modules.len(), modules.join(", ")).cyan()
+    );
+
+    // jlink se niega a escribir sobre un directorio existente
+    let _ = std::fs::remove_dir_all(output);
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| io_error(parent, e))?;
+    }
+
+    let mut cmd = tokio::process::Command::new("jlink");
+    cmd.arg("--add-modules")
+        .arg(modules.join(","))
+        .arg("--output")
+        .arg(output)
+        .args(JLINK_DEFAULTS)
+        .args(&config.package.image.jlink_args);
+    run_tool(cmd, "jlink").await?;
+    Ok(())
+}
+
+/// Módulos del JDK que usan los JARs, según `jdeps --print-module-deps`.
+async fn required_modules(
+    config: &ForgeConfig,
+    descriptor: Option<&ModuleDescriptor>,
+    jars: &[PathBuf],
+) -> ForgeResult<Vec<String>> {
+    let mut cmd = tokio::process::Command::new("jdeps");
+    cmd.arg("--ignore-missing-deps")
+        .arg("-q")
+        .arg("--multi-release")
+        .arg(java_release(config));
+    // Un JAR modular solo se analiza si jdeps encuentra los módulos que `requires`
+    if descriptor.is_some() && jars.len() > 1 {
+        let module_path = std::env::join_paths(&jars[1..]).map_err(|e| ForgeError::IoError {
+            path: jars[1].clone(),
+            message: e.to_string(),
+        })?;
+        cmd.arg("--module-path").arg(module_path);
+    }
+    cmd.arg("--print-module-deps").args(jars);
+    let stdout = run_tool(cmd, "jdeps").await?;
+
+    // Solo los módulos del JDK: los de la aplicación no se enlazan en el runtime
+    let mut modules: Vec<String> = stdout
+        .trim()
+        .split(',')
+        .map(str::trim)
+        .filter(|module| module.starts_with("java.") || module.starts_with("jdk."))
+        .map(String::from)
+        .collect();
+    if !modules.iter().any(|module| module == "java.base") {
+        modules.push("java.base".to_string());
+    }
+    Ok(modules)
+}
+
+/// Ejecuta una herramienta del JDK y devuelve su stdout; si falla, muestra su salida.
+async fn run_tool(mut cmd: tokio::process::Command, tool: &str) -> ForgeResult<String> {
+    let output = cmd
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .await
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                ForgeError::CommandNotFound {
+                    command: tool.to_string(),
+                }
+            } else {
+                ForgeError::IoError {
+                    path: PathBuf::from(tool),
+                    message: format!("Error al ejecutar {}: {}", tool, e),
+                }
+            }
+        })?;
+
+    if !output.status.success() {
+        eprintln!("{}", String::from_utf8_lossy(&output.stdout));
+        eprintln!("{}", String::from_utf8_lossy(&output.stderr));
+        return Err(ForgeError::TaskFailed {
+            task_name: tool.to_string(),
+            exit_code: output.status.code().unwrap_or(-1),
+        }
+        .into());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// JAR del proyecto primero y, salvo en un fat-jar, los JARs de su classpath runtime.
+pub(crate) fn app_jars(config: &ForgeConfig, jar: &Path, classpath: &[String]) -> Vec<PathBuf> {
+    let mut jars = vec![jar.to_path_buf()];
+    if config.package.mode == PackageMode::FatJar {
+        return jars;
+    }
+    for entry in classpath.iter().filter(|cp| !cp.is_empty()).flat_map(std::env::split_paths) {
+        let is_jar = entry.extension().map(|ext| ext == "jar").unwrap_or(false);
+        if is_jar && !jars.iter().any(|j| file_name(j) == file_name(&entry)) {
+            jars.push(entry);
+        }
+    }
+    jars
+}
+
+/// Opciones de la JVM de los lanzadores: preview, `--add-opens`/`--add-exports` de
+/// [java] y `[package] jvm-options`.
+pub(crate) fn launch_options(config: &ForgeConfig) -> Vec<String> {
+    let mut options: Vec<String> = jvm_options(&config.compiler_options())
+        .into_iter()
+        .map(String::from)
+        .collect();
+    options.extend(jpms::runtime_flags(config.java.as_ref()));
+    options.extend(config.package.jvm_options.iter().cloned());
+    options
+}
+
+pub(crate) fn main_class(config: &ForgeConfig) -> ForgeResult<String> {
+    config.main_entry().ok_or_else(|| {
+        ForgeError::ConfigMissingField {
+            field: format!("{}.main-class", config.project.lang),
+        }
+        .into()
+    })
+}
+
+/// Release de Java para analizar JARs multi-release.
+fn java_release(config: &ForgeConfig) -> String {
+    match config.project.lang.as_str() {
+        "kotlin" => config.kotlin.as_ref().map(|k| k.jvm_target.clone()),
+        _ => config.java.as_ref().map(|j| j.target.clone()),
+    }
+    .unwrap_or_else(|| "17".to_string())
+}
+
+/// jpackage solo acepta versiones numéricas (`1.2.0-SNAPSHOT` → `1.2.0`).
+fn installer_version(version: &str) -> String {
+    let numeric: String = version
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+    let numeric = numeric.trim_matches('.');
+    if numeric.is_empty() {
+        "1.0".to_string()
+    } else {
+        numeric.to_string()
+    }
+}
+
+/// Lanzador POSIX: usa el `java` de la propia imagen.
+fn unix_launcher(options: &[String], launch: &Launch) -> String {
+    let quote = |arg: &String| format!(" {}", sh_quote(arg));
+    let options: String = options.iter().map(quote).collect();
+    // Las rutas de app/ van entre comillas dobles para expandir $DIR
+    let paths: String = launch
+        .path_args("$DIR", APP_DIR, '/', ":")
+        .iter()
+        .map(|arg| format!(" \"{}\"", arg))
+        .collect();
+    let target: String = launch.target.iter().map(quote).collect();
+    format!(
+        "#!/bin/sh\nDIR=\"$(cd \"$(dirname \"$0\")/..\" && pwd)\"\nexec \"$DIR/bin/java\"{}{}{} \"$@\"\n",
+        options, paths, target
+    )
+}
+
+/// Argumento entre comillas simples para sh (`it's` → `'it'\''s'`).
+pub(crate) fn sh_quote(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', "'\\''"))
+}
+
+/// Lanzador para Windows.
+fn windows_launcher(options: &[String], launch: &Launch) -> String {
+    let args: String = options
+        .iter()
+        .cloned()
+        .chain(launch.path_args("%DIR%", APP_DIR, '\\', ";"))
+        .chain(launch.target.iter().cloned())
+        .map(|arg| format!(" \"{}\"", arg))
+        .collect();
+    format!(
+        "@echo off\r\nset DIR=%~dp0..\r\n\"%DIR%\\bin\\java.exe\"{} %*\r\n",
+        args
+    )
+}
+
+/// Entradas de dist/ con su fecha de modificación, para saber qué generó jpackage.
+fn dist_entries(dist_dir: &Path) -> Vec<(PathBuf, Option<std::time::SystemTime>)> {
+    std::fs::read_dir(dist_dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| (entry.path(), entry.metadata().and_then(|m| m.modified()).ok()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub(crate) fn file_name(path: &Path) -> String {
+    path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+}
+
+pub(crate) fn io_error(path: &Path, e: impl std::fmt::Display) -> ForgeError {
+    ForgeError::IoError {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_installer_version() {
+        assert_eq!(installer_version("1.2.0"), "1.2.0");
+        assert_eq!(installer_version("1.2.0-SNAPSHOT"), "1.2.0");
+        assert_eq!(installer_version("2.0.RC1"), "2.0");
+        assert_eq!(installer_version("dev"), "1.0");
+    }
+
+    #[test]
+    fn test_unix_launcher() {
+        let options = vec!["-Xmx256m".to_string(), "-Dmsg=it's".to_string()];
+        let jars = vec![PathBuf::from("build/app.jar"), PathBuf::from("gson-2.11.0.jar")];
+        let script = unix_launcher(&options, &Launch::new(None, &jars, "com.acme.Main"));
+
+        assert!(script.starts_with("#!/bin/sh\n"));
+        assert!(script.contains(
+            r#"exec "$DIR/bin/java" '-Xmx256m' '-Dmsg=it'\''s' "-cp" "$DIR/app/app.jar:$DIR/app/gson-2.11.0.jar" 'com.acme.Main' "$@""#
+        ));
+
+        let modular = Launch {
+            module_path: vec!["app.jar".to_string()],
+            class_path: Vec::new(),
+            target: vec!["-m".to_string(), "com.acme.app/com.acme.Main".to_string()],
+        };
+        assert!(unix_launcher(&[], &modular).contains(
+            r#""$DIR/bin/java" "--module-path" "$DIR/app/app.jar" '-m' 'com.acme.app/com.acme.Main' "$@""#
+        ));
+    }
+}
@@ -0,0 +1,353 @@
+// =============================================================================
+// 🔥 FORGE — Módulos de Lenguaje: Compilación Java incremental
+// =============================================================================
+// Recompila solo los .java que cambiaron desde el último build (según
+// BuildCache) más todos los que dependen de ellos, directa o indirectamente.
+// El mapa de dependencias se extrae de los .class generados y se guarda en
+// .forge/java-incremental.json. Sin ese mapa, sin caché, o si cambió el
+// classpath o las opciones de javac, se compila todo desde cero.
+//
+// javac copia las constantes de compilación (`static final` con valor
+// literal) en las clases que las usan, que ya no referencian a la clase que
+// las declara. Como Gradle, si cambia un fuente que declara constantes se
+// recompila todo.
+// =============================================================================
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use cyrce_forge_core::cache::BuildCache;
+use cyrce_forge_core::error::{ForgeError, ForgeResult};
+
+use crate::classfile;
+
+/// Archivo con el mapa de dependencias, dentro de .forge/.
+const STATE_FILE: &str = "java-incremental.json";
+
+/// Mapa de dependencias entre fuentes Java del último build.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IncrementalState {
+    /// Classpath y opciones de javac del build: si cambian, se recompila todo
+    pub fingerprint: String,
+
+    /// Fuente (relativa al directorio de código, con `/`) → clases y dependencias
+    pub sources: BTreeMap<String, SourceInfo>,
+}
+
+/// Salida y dependencias de un archivo fuente.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SourceInfo {
+    /// Clases generadas (nombres internos: `com/acme/Foo$Bar`)
+    pub classes: Vec<String>,
+
+    /// Otras fuentes del proyecto que usa
+    #[serde(default, rename = "depends-on")]
+    pub depends_on: BTreeSet<String>,
+
+    /// Declara constantes de compilación: cambiarlo obliga a recompilar todo
+    #[serde(default)]
+    pub constants: bool,
+}
+
+/// Qué hay que compilar en este build.
+#[derive(Debug)]
+pub enum CompilePlan {
+    /// Todo desde cero (falta información o cambió el classpath)
+    Full,
+    /// Solo estas fuentes
+    Incremental {
+        sources: Vec<PathBuf>,
+        /// Cuántas cambiaron (el resto se recompila por depender de ellas)
+        changed: usize,
+    },
+    /// Ninguna fuente cambió (el build pudo dispararse por los recursos)
+    UpToDate,
+}
+
+impl IncrementalState {
+    pub fn load(project_dir: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(project_dir.join(".forge").join(STATE_FILE)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn save(&self, project_dir: &Path) -> ForgeResult<()> {
+        let forge_dir = project_dir.join(".forge");
+        std::fs::create_dir_all(&forge_dir).map_err(|e| ForgeError::IoError {
+            path: forge_dir.clone(),
+            message: e.to_string(),
+        })?;
+
+        let path = forge_dir.join(STATE_FILE);
+        let content = serde_json::to_string_pretty(self).map_err(|e| ForgeError::IoError {
+            path: path.clone(),
+            message: e.to_string(),
+        })?;
+        std::fs::write(&path, content).map_err(|e| {
+            ForgeError::IoError {
+                path,
+                message: e.to_string(),
+            }
+            .into()
+        })
+    }
+
+    /// Reconstruye el mapa leyendo los .class de `classes_dir`. Solo se registran las
+    /// clases cuyo fuente existe en `source_dir` (en el directorio de su paquete).
+    pub fn scan(classes_dir: &Path, source_dir: &Path, fingerprint: &str) -> Self {
+        let classes: Vec<classfile::ClassInfo> = class_files(classes_dir)
+            .iter()
+            .filter_map(|path| classfile::parse(&std::fs::read(path).ok()?))
+            .collect();
+
+        let mut owner: HashMap<&str, String> = HashMap::new();
+        for class in &classes {
+            if let Some(source) = class.source_path() {
+                if source_dir.join(&source).is_file() {
+                    owner.insert(class.name.as_str(), source);
+                }
+            }
+        }
+
+        let mut sources: BTreeMap<String, SourceInfo> = BTreeMap::new();
+        for class in &classes {
+            let Some(source) = owner.get(class.name.as_str()) else {
+                continue;
+            };
+            let depends_on: Vec<String> = class
+                .references
+                .iter()
+                .filter_map(|reference| owner.get(reference.as_str()))
+                .filter(|dependency| *dependency != source)
+                .cloned()
+                .collect();
+
+            let info = sources.entry(source.clone()).or_default();
+            info.classes.push(class.name.clone());
+            info.depends_on.extend(depends_on);
+            info.constants |= class.has_constants;
+        }
+
+        Self {
+            fingerprint: fingerprint.to_string(),
+            sources,
+        }
+    }
+
+    /// Fuentes a recompilar: las cambiadas más todas las que dependen de ellas,
+    /// directa o transitivamente.
+    pub fn affected(&self, changed: &BTreeSet<String>) -> BTreeSet<String> {
+        let mut affected = changed.clone();
+        let mut pending: Vec<String> = changed.iter().cloned().collect();
+        while let Some(source) = pending.pop() {
+            for (dependent, info) in &self.sources {
+                if info.depends_on.contains(&source) && affected.insert(dependent.clone()) {
+                    pending.push(dependent.clone());
+                }
+            }
+        }
+        affected
+    }
+}
+
+/// Decide qué compilar comparando las fuentes con la caché del último build. Borra
+/// los .class de las fuentes a recompilar (o todos, si el build es completo) para
+/// que no queden clases de fuentes eliminadas.
+pub fn plan(
+    project_dir: &Path,
+    source_dir: &Path,
+    classes_dir: &Path,
+    fingerprint: &str,
+) -> ForgeResult<CompilePlan> {
+    let full = || -> ForgeResult<CompilePlan> {
+        remove_class_files(classes_dir);
+        Ok(CompilePlan::Full)
+    };
+
+    let cache = BuildCache::load(project_dir).unwrap_or_default();
+    let state = match IncrementalState::load(project_dir) {
+        Some(state) if state.fingerprint == fingerprint && !state.sources.is_empty() => state,
+        _ => return full(),
+    };
+    if cache.file_hashes.is_empty() || class_files(classes_dir).is_empty() {
+        return full();
+    }
+
+    let normalize = |path: &str| path.replace('\\', "/");
+    let mut changed: BTreeSet<String> = cache
+        .changed_files(source_dir, &["java"])?
+        .iter()
+        .map(|path| normalize(&path.to_string_lossy()))
+        .collect();
+    let removed = cache
+        .file_hashes
+        .keys()
+        .map(|path| normalize(path))
+        .filter(|path| path.ends_with(".java") && !source_dir.join(path).exists());
+    changed.extend(removed);
+
+    if changed.is_empty() {
+        return Ok(CompilePlan::UpToDate);
+    }
+
+    // Quien usa una constante no depende de su clase en los .class
+    if changed.iter().any(|source| state.sources.get(source).is_some_and(|info| info.constants)) {
+        return full();
+    }
+
+    let affected = state.affected(&changed);
+    for source in &affected {
+        if let Some(info) = state.sources.get(source) {
+            for class in &info.classes {
+                let _ = std::fs::remove_file(classes_dir.join(format!("{}.class", class)));
+            }
+        }
+    }
+
+    let sources: Vec<PathBuf> = affected
+        .iter()
+        .map(|source| source_dir.join(source))
+        .filter(|path| path.is_file())
+        .collect();
+    let changed = changed.iter().filter(|s| source_dir.join(s).is_file()).count();
+
+    Ok(CompilePlan::Incremental { sources, changed })
+}
+
+/// Tras un javac fallido ya se borraron clases: se descarta el mapa y los hashes de
+/// la caché para que el próximo build compile todo aunque las fuentes vuelvan a su
+/// estado anterior.
+pub fn invalidate(project_dir: &Path) -> ForgeResult<()> {
+    let _ = std::fs::remove_file(project_dir.join(".forge").join(STATE_FILE));
+    let mut cache = BuildCache::load(project_dir).unwrap_or_default();
+    if !cache.file_hashes.is_empty() {
+        cache.file_hashes.clear();
+        cache.save(project_dir)?;
+    }
+    Ok(())
+}
+
+/// Todos los .class bajo `dir`.
+fn class_files(dir: &Path) -> Vec<PathBuf> {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.path().extension().map(|ext| ext == "class").unwrap_or(false))
+        .map(|e| e.path().to_path_buf())
+        .collect()
+}
+
+/// Borra los .class (los recursos copiados se conservan).
+fn remove_class_files(dir: &Path) {
+    for file in class_files(dir) {
+        let _ = std::fs::remove_file(file);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(edges: &[(&str, &[&str])]) -> IncrementalState {
+        let sources = edges
+            .iter()
+            .map(|(source, deps)| {
+                let info = SourceInfo {
+                    classes: vec![source.trim_end_matches(".java").to_string()],
+                    depends_on: deps.iter().map(|d| d.to_string()).collect(),
+                    constants: false,
+                };
+                (source.to_string(), info)
+            })
+            .collect();
+        IncrementalState {
+            fingerprint: String::new(),
+            sources,
+        }
+    }
+
+    #[test]
+    fn test_affected_includes_transitive_dependents() {
+        let state = state(&[
+            ("a/Base.java", &[]),
+            ("a/Child.java", &["a/Base.java"]),
+            ("a/User.java", &["a/Child.java"]),
+            ("a/Other.java", &[]),
+        ]);
+        let changed: BTreeSet<String> = ["a/Base.java".to_string()].into();
+        let affected = state.affected(&changed);
+
+        assert_eq!(
+            affected.into_iter().collect::<Vec<_>>(),
+            vec!["a/Base.java", "a/Child.java", "a/User.java"]
+        );
+    }
+
+    #[test]
+    fn test_scan_builds_dependency_map() {
+        let root = std::env::temp_dir().join("forge_test_incremental_scan");
+        let _ = std::fs::remove_dir_all(&root);
+        let (src, classes) = (root.join("src"), root.join("classes"));
+        std::fs::create_dir_all(src.join("com/acme")).unwrap();
+        std::fs::create_dir_all(classes.join("com/acme")).unwrap();
+        std::fs::write(src.join("com/acme/Foo.java"), "").unwrap();
+        std::fs::write(src.join("com/acme/Bar.java"), "").unwrap();
+
+        let foo = classfile::tests::class_bytes("com/acme/Foo", "Foo.java", &["(Lcom/acme/Bar;)V"]);
+        let inner = classfile::tests::class_bytes("com/acme/Foo$1", "Foo.java", &[]);
+        let bar = classfile::tests::class_bytes("com/acme/Bar", "Bar.java", &["Ljava/lang/String;"]);
+        std::fs::write(classes.join("com/acme/Foo.class"), foo).unwrap();
+        std::fs::write(classes.join("com/acme/Foo$1.class"), inner).unwrap();
+        std::fs::write(classes.join("com/acme/Bar.class"), bar).unwrap();
+
+        let state = IncrementalState::scan(&classes, &src, "fp");
+        let foo = &state.sources["com/acme/Foo.java"];
+        assert_eq!(foo.classes.len(), 2);
+        assert!(foo.depends_on.contains("com/acme/Bar.java"));
+        assert!(state.sources["com/acme/Bar.java"].depends_on.is_empty());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_changed_constant_forces_full_recompile() {
+        let root = std::env::temp_dir().join("forge_test_incremental_constants");
+        let _ = std::fs::remove_dir_all(&root);
+        let (src, classes) = (root.join("src"), root.join("classes"));
+        std::fs::create_dir_all(src.join("com/acme")).unwrap();
+        std::fs::create_dir_all(classes.join("com/acme")).unwrap();
+        std::fs::write(src.join("com/acme/Limits.java"), "class Limits { static final int MAX = 42; }").unwrap();
+        std::fs::write(src.join("com/acme/User.java"), "class User { int max() { return Limits.MAX; } }").unwrap();
+
+        // User.class no referencia a Limits: javac copió el 42
+        let limits = classfile::tests::constant_class_bytes("com/acme/Limits", "Limits.java");
+        let user = classfile::tests::class_bytes("com/acme/User", "User.java", &[]);
+        std::fs::write(classes.join("com/acme/Limits.class"), limits).unwrap();
+        std::fs::write(classes.join("com/acme/User.class"), user).unwrap();
+
+        let state = IncrementalState::scan(&classes, &src, "fp");
+        assert!(state.sources["com/acme/Limits.java"].constants);
+        assert!(state.sources["com/acme/User.java"].depends_on.is_empty());
+        state.save(&root).unwrap();
+        let mut cache = BuildCache::default();
+        cache.update_hashes(&src, &["java"]).unwrap();
+        cache.save(&root).unwrap();
+
+        // Cambiar solo quien usa la constante sigue siendo incremental
+        std::fs::write(src.join("com/acme/User.java"), "class User { int max() { return Limits.MAX + 1; } }").unwrap();
+        match plan(&root, &src, &classes, "fp").unwrap() {
+            CompilePlan::Incremental { sources, .. } => assert_eq!(sources, [src.join("com/acme/User.java")]),
+            other => panic!("se esperaba un build incremental, llegó {:?}", other),
+        }
+
+        // Cambiar la constante recompila todo (y borra todas las clases)
+        std::fs::write(classes.join("com/acme/User.class"), b"").unwrap();
+        std::fs::write(src.join("com/acme/Limits.java"), "class Limits { static final int MAX = 7; }").unwrap();
+        assert!(matches!(plan(&root, &src, &classes, "fp").unwrap(), CompilePlan::Full));
+        assert!(class_files(&classes).is_empty());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,550 @@
+// =============================================================================
+// 🔥 FORGE — Módulos de Lenguaje: Empaquetado JAR
+// =============================================================================
+// Escritura nativa de JARs para Java y Kotlin, sin depender de la herramienta
+// `jar` del JDK. Los JARs son reproducibles: entradas en orden alfabético,
+// timestamps fijos (1980-01-01) y permisos constantes.
+//
+// Fat-jar (uber-jar): las clases del proyecto más todas las dependencias
+// runtime en un único JAR ejecutable con `java -jar`. Une los
+// META-INF/services de todas las dependencias, descarta firmas que dejarían
+// de ser válidas y aplica las reubicaciones de [package].
+// =============================================================================
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, DateTime, ZipArchive, ZipWriter};
+
+use cyrce_forge_core::config::ForgeConfig;
+use cyrce_forge_core::error::{ForgeError, ForgeResult};
+
+use crate::shade::{self, Relocation};
+
+/// Ruta del manifiesto dentro del JAR.
+const MANIFEST_PATH: &str = "META-INF/MANIFEST.MF";
+
+/// Prefijo de los descriptores de ServiceLoader.
+const SERVICES_PREFIX: &str = "META-INF/services/";
+
+/// Resumen de lo que se metió en un fat-jar.
+#[derive(Debug, Default)]
+pub struct FatJarStats {
+    /// Entradas escritas (sin contar el manifiesto)
+    pub entries: usize,
+    /// Archivos de META-INF/services unidos
+    pub services: usize,
+    /// Firmas (.SF/.RSA/.DSA/.EC) descartadas
+    pub signatures: usize,
+    /// Entradas repetidas ignoradas (gana la primera: proyecto, luego dependencias en orden)
+    pub duplicates: usize,
+}
+
+/// Largo máximo de una línea del manifiesto, en bytes (especificación JAR).
+const MANIFEST_LINE_BYTES: usize = 72;
+
+/// Manifiesto de un JAR: atributos en el orden en que se agregan.
+#[derive(Debug, Clone)]
+pub struct Manifest {
+    attributes: Vec<(String, String)>,
+}
+
+impl Default for Manifest {
+    fn default() -> Self {
+        Self {
+            attributes: vec![("Manifest-Version".to_string(), "1.0".to_string())],
+        }
+    }
+}
+
+impl Manifest {
+    /// Manifiesto del proyecto: Main-Class, Implementation-* desde [project],
+    /// `Class-Path` (nombres de JAR relativos al JAR del proyecto) y Multi-Release.
+    pub fn for_project(config: &ForgeConfig, class_path: &[String]) -> Self {
+        let mut manifest = Self::default();
+        if let Some(main_class) = config.main_entry() {
+            manifest = manifest.attribute("Main-Class", &main_class);
+        }
+        manifest = manifest
+            .attribute("Implementation-Title", &config.project.name)
+            .attribute("Implementation-Version", &config.project.version);
+        if !class_path.is_empty() {
+            manifest = manifest.attribute("Class-Path", &class_path.join(" "));
+        }
+        if config.package.multi_release {
+            manifest = manifest.attribute("Multi-Release", "true");
+        }
+        manifest.attribute("Built-By", "FORGE")
+    }
+
+    /// Agrega un atributo (reemplaza el valor si ya existía).
+    pub fn attribute(mut self, name: &str, value: &str) -> Self {
+        match self.attributes.iter_mut().find(|(n, _)| n == name) {
+            Some(existing) => existing.1 = value.to_string(),
+            None => self.attributes.push((name.to_string(), value.to_string())),
+        }
+        self
+    }
+
+    /// Texto del manifiesto: líneas de máximo 72 bytes con continuación `\r\n `.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for (name, value) in &self.attributes {
+            let line = format!("{}: {}", name, value);
+            let mut limit = MANIFEST_LINE_BYTES;
+            let mut current = 0;
+            for (i, c) in line.char_indices() {
+                if i + c.len_utf8() - current > limit {
+                    out.push_str(&line[current..i]);
+                    out.push_str("\r\n ");
+                    current = i;
+                    // Las continuaciones empiezan con un espacio
+                    limit = MANIFEST_LINE_BYTES - 1;
+                }
+            }
+            out.push_str(&line[current..]);
+            out.push_str("\r\n");
+        }
+        out.push_str("\r\n");
+        out
+    }
+}
+
+/// Nombres de los JARs de un classpath, para el `Class-Path` del manifiesto. Los
+/// directorios de clases se omiten: solo pueden ir al classpath de `java -cp`.
+pub fn class_path_names(classpaths: &[String]) -> Vec<String> {
+    let mut names = Vec::new();
+    for classpath in classpaths.iter().filter(|cp| !cp.is_empty()) {
+        for entry in std::env::split_paths(classpath) {
+            if entry.extension().map(|ext| ext != "jar").unwrap_or(true) {
+                continue;
+            }
+            if let Some(name) = entry.file_name().map(|n| n.to_string_lossy().to_string()) {
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+    }
+    names
+}
+
+/// Empaqueta las clases del proyecto en `jar_path` (sin dependencias). Devuelve
+/// la cantidad de entradas escritas.
+pub fn write_jar(jar_path: &Path, manifest: &Manifest, classes_dir: &Path) -> ForgeResult<usize> {
+    let mut entries = BTreeMap::new();
+    for (name, file) in dir_files(classes_dir) {
+        if name.eq_ignore_ascii_case(MANIFEST_PATH) {
+            continue;
+        }
+        let bytes = std::fs::read(&file).map_err(|e| io_error(&file, e))?;
+        entries.insert(name, bytes);
+    }
+    write_entries(jar_path, manifest, &entries)?;
+    Ok(entries.len())
+}
+
+/// Empaqueta `classes_dir` y las entradas de `classpaths` (listas separadas por el
+/// separador de la plataforma) en un fat-jar según la sección [package].
+pub fn package_fat_jar(
+    config: &ForgeConfig,
+    classes_dir: &Path,
+    jar_path: &Path,
+    classpaths: &[String],
+) -> ForgeResult<FatJarStats> {
+    let mut inputs = vec![classes_dir.to_path_buf()];
+    for classpath in classpaths.iter().filter(|cp| !cp.is_empty()) {
+        for entry in std::env::split_paths(classpath) {
+            if !inputs.contains(&entry) {
+                inputs.push(entry);
+            }
+        }
+    }
+
+    let relocations = Relocation::from_config(&config.package.relocations);
+    let manifest = Manifest::for_project(config, &[]);
+    let stats = write_fat_jar(jar_path, &manifest, &inputs, &relocations)?;
+
+    println!(
+        "   {}",
+        format!(
+            "📦 Fat-jar creado: {} ({} entradas de {} fuentes)",
+            jar_path.display(),
+            stats.entries,
+            inputs.len()
+        )
+        .green()
+    );
+    if stats.services > 0 || !relocations.is_empty() {
+        println!(
+            "   {}",
+            format!(
+                "   {} servicios unidos, {} reubicaciones aplicadas",
+                stats.services,
+                relocations.len()
+            )
+            .dimmed()
+        );
+    }
+    if stats.signatures > 0 {
+        println!(
+            "   {}",
+            format!("   {} firmas de JARs firmados descartadas", stats.signatures).dimmed()
+        );
+    }
+    if stats.duplicates > 0 {
+        println!(
+            "   {}",
+            format!("   {} entradas duplicadas ignoradas (gana la primera)", stats.duplicates).dimmed()
+        );
+    }
+
+    Ok(stats)
+}
+
+/// Escribe un fat-jar con el contenido de `inputs` (directorios de clases o JARs), en orden.
+pub fn write_fat_jar(
+    jar_path: &Path,
+    manifest: &Manifest,
+    inputs: &[PathBuf],
+    relocations: &[Relocation],
+) -> ForgeResult<FatJarStats> {
+    let mut merger = Merger {
+        relocations,
+        entries: BTreeMap::new(),
+        services: BTreeMap::new(),
+        stats: FatJarStats::default(),
+    };
+
+    for input in inputs {
+        if input.is_dir() {
+            merger.add_dir(input)?;
+        } else if input.is_file() {
+            merger.add_jar(input)?;
+        }
+    }
+
+    merger.write(jar_path, manifest)
+}
+
+/// Acumula las entradas de todas las fuentes antes de escribir el JAR.
+struct Merger<'a> {
+    relocations: &'a [Relocation],
+    entries: BTreeMap<String, Vec<u8>>,
+    /// Interfaz → implementaciones (sin duplicados, en orden de aparición)
+    services: BTreeMap<String, Vec<String>>,
+    stats: FatJarStats,
+}
+
+impl Merger<'_> {
+    fn add_dir(&mut self, dir: &Path) -> ForgeResult<()> {
+        for (name, file) in dir_files(dir) {
+            let bytes = std::fs::read(&file).map_err(|e| io_error(&file, e))?;
+            self.add(&name, bytes);
+        }
+        Ok(())
+    }
+
+    fn add_jar(&mut self, jar: &Path) -> ForgeResult<()> {
+        let file = std::fs::File::open(jar).map_err(|e| io_error(jar, e))?;
+        let mut archive = ZipArchive::new(file).map_err(|e| io_error(jar, e))?;
+
+        for index in 0..archive.len() {
+            let mut entry = archive.by_index(index).map_err(|e| io_error(jar, e))?;
+            if entry.is_dir() {
+                continue;
+            }
+            let name = entry.name().to_string();
+            let mut bytes = Vec::with_capacity(entry.size() as usize);
+            entry.read_to_end(&mut bytes).map_err(|e| io_error(jar, e))?;
+            self.add(&name, bytes);
+        }
+        Ok(())
+    }
+
+    fn add(&mut self, name: &str, bytes: Vec<u8>) {
+        if name.ends_with('/')
+            || name.eq_ignore_ascii_case(MANIFEST_PATH)
+            || name.eq_ignore_ascii_case("META-INF/INDEX.LIST")
+            || name.ends_with("module-info.class")
+        {
+            return;
+        }
+
+        if is_signature(name) {
+            self.stats.signatures += 1;
+            return;
+        }
+
+        if let Some(service) = name.strip_prefix(SERVICES_PREFIX) {
+            let service = shade::relocate_name(service, self.relocations);
+            let providers = self.services.entry(service).or_default();
+            for line in String::from_utf8_lossy(&bytes).lines() {
+                let provider = line.split('#').next().unwrap_or_default().trim();
+                if provider.is_empty() {
+                    continue;
+                }
+                let provider = shade::relocate_name(provider, self.relocations);
+                if !providers.contains(&provider) {
+                    providers.push(provider);
+                }
+            }
+            return;
+        }
+
+        let name = shade::relocate_path(name, self.relocations);
+        if self.entries.contains_key(&name) {
+            self.stats.duplicates += 1;
+            return;
+        }
+
+        let bytes = if name.ends_with(".class") {
+            shade::relocate_class(&bytes, self.relocations).unwrap_or(bytes)
+        } else {
+            bytes
+        };
+        self.entries.insert(name, bytes);
+    }
+
+    fn write(mut self, jar_path: &Path, manifest: &Manifest) -> ForgeResult<FatJarStats> {
+        for (service, providers) in std::mem::take(&mut self.services) {
+            let mut content = providers.join("\n");
+            content.push('\n');
+            self.entries
+                .insert(format!("{}{}", SERVICES_PREFIX, service), content.into_bytes());
+            self.stats.services += 1;
+        }
+
+        write_entries(jar_path, manifest, &self.entries)?;
+        self.stats.entries = self.entries.len();
+        Ok(self.stats)
+    }
+}
+
+/// Archivos de un directorio con su nombre de entrada (`com/acme/App.class`), ordenados.
+fn dir_files(dir: &Path) -> Vec<(String, PathBuf)> {
+    let mut files: Vec<(String, PathBuf)> = WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| {
+            let name = e
+                .path()
+                .strip_prefix(dir)
+                .unwrap_or(e.path())
+                .to_string_lossy()
+                .replace('\\', "/");
+            (name, e.path().to_path_buf())
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Escribe el JAR: META-INF/ y el manifiesto primero (para que `java -jar` lo
+/// encuentre), luego cada directorio y archivo en orden alfabético. Fecha y
+/// permisos fijos: el mismo contenido produce siempre los mismos bytes.
+fn write_entries(jar_path: &Path, manifest: &Manifest, entries: &BTreeMap<String, Vec<u8>>) -> ForgeResult<()> {
+    let file = std::fs::File::create(jar_path).map_err(|e| io_error(jar_path, e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .last_modified_time(DateTime::default())
+        .unix_permissions(0o644);
+    let dir_options = options.unix_permissions(0o755);
+
+    zip.add_directory("META-INF/", dir_options)
+        .map_err(|e| io_error(jar_path, e))?;
+    zip.start_file(MANIFEST_PATH, options)
+        .map_err(|e| io_error(jar_path, e))?;
+    zip.write_all(manifest.render().as_bytes())
+        .map_err(|e| io_error(jar_path, e))?;
+
+    // Directorios intermedios, para que getResource("com/acme/") funcione como con `jar`
+    let mut directories = BTreeSet::new();
+    for name in entries.keys() {
+        let mut end = 0;
+        while let Some(slash) = name[end..].find('/') {
+            end += slash + 1;
+            directories.insert(&name[..end]);
+        }
+    }
+    directories.remove("META-INF/");
+
+    let mut items: Vec<(&str, Option<&Vec<u8>>)> = directories.into_iter().map(|d| (d, None)).collect();
+    items.extend(entries.iter().map(|(name, bytes)| (name.as_str(), Some(bytes))));
+    items.sort_by(|a, b| a.0.cmp(b.0));
+
+    for (name, bytes) in items {
+        match bytes {
+            None => zip.add_directory(name, dir_options),
+            Some(bytes) => zip
+                .start_file(name, options)
+                .and_then(|_| zip.write_all(bytes).map_err(Into::into)),
+        }
+        .map_err(|e| io_error(jar_path, e))?;
+    }
+    zip.finish().map_err(|e| io_error(jar_path, e))?;
+    Ok(())
+}
+
+/// Firmas de JARs firmados: dejan de ser válidas al mezclar clases de varios JARs.
+fn is_signature(name: &str) -> bool {
+    let Some(file) = name.strip_prefix("META-INF/") else {
+        return false;
+    };
+    if file.contains('/') {
+        return false;
+    }
+    let upper = file.to_ascii_uppercase();
+    upper.starts_with("SIG-")
+        || [".SF", ".DSA", ".RSA", ".EC"].iter().any(|ext| upper.ends_with(ext))
+}
+
+fn io_error(path: &Path, e: impl std::fmt::Display) -> ForgeError {
+    ForgeError::IoError {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) fn zip_with(path: &Path, entries: &[(&str, &str)]) {
+        let mut zip = ZipWriter::new(std::fs::File::create(path).unwrap());
+        for (name, content) in entries {
+            zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    fn read_entry(jar: &Path, name: &str) -> Option<String> {
+        let mut archive = ZipArchive::new(std::fs::File::open(jar).unwrap()).unwrap();
+        let mut entry = archive.by_name(name).ok()?;
+        let mut content = String::new();
+        entry.read_to_string(&mut content).unwrap();
+        Some(content)
+    }
+
+    #[test]
+    fn test_signature_detection() {
+        assert!(is_signature("META-INF/BCKEY.SF"));
+        assert!(is_signature("META-INF/bckey.dsa"));
+        assert!(is_signature("META-INF/SIG-FOO"));
+        assert!(!is_signature("META-INF/services/a.b.C"));
+        assert!(!is_signature("com/acme/RSA.class"));
+    }
+
+    #[test]
+    fn test_fat_jar_merges_services_and_drops_signatures() {
+        let dir = std::env::temp_dir().join("forge_test_fat_jar");
+        let _ = std::fs::remove_dir_all(&dir);
+        let classes = dir.join("classes");
+        std::fs::create_dir_all(classes.join("app")).unwrap();
+        std::fs::write(classes.join("app/Main.class"), "main").unwrap();
+
+        let a = dir.join("a.jar");
+        let b = dir.join("b.jar");
+        zip_with(&a, &[
+            ("META-INF/MANIFEST.MF", "Main-Class: lib.A\n"),
+            ("META-INF/services/lib.Spi", "# proveedores\nlib.ImplA\n"),
+            ("META-INF/LIB.SF", "firma"),
+            ("lib/A.class", "a"),
+        ]);
+        zip_with(&b, &[
+            ("META-INF/services/lib.Spi", "lib.ImplB\nlib.ImplA\n"),
+            ("lib/A.class", "otra a"),
+            ("app/Main.class", "duplicada"),
+        ]);
+
+        let jar = dir.join("app.jar");
+        let stats = write_fat_jar(&jar, &Manifest::default().attribute("Main-Class", "app.Main"), &[classes, a, b], &[]).unwrap();
+
+        assert_eq!(stats.signatures, 1);
+        assert_eq!(stats.duplicates, 2);
+        assert_eq!(stats.services, 1);
+        assert_eq!(read_entry(&jar, "META-INF/services/lib.Spi").unwrap(), "lib.ImplA\nlib.ImplB\n");
+        assert_eq!(read_entry(&jar, "app/Main.class").unwrap(), "main");
+        assert_eq!(read_entry(&jar, "lib/A.class").unwrap(), "a");
+        assert!(read_entry(&jar, "META-INF/MANIFEST.MF").unwrap().contains("Main-Class: app.Main"));
+        assert!(read_entry(&jar, "META-INF/LIB.SF").is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_manifest_wraps_long_lines() {
+        let class_path: Vec<String> = (0..8).map(|i| format!("dependency-number-{}.jar", i)).collect();
+        let manifest = Manifest::default()
+            .attribute("Main-Class", "app.Main")
+            .attribute("Class-Path", &class_path.join(" "))
+            .render();
+
+        assert!(manifest.starts_with("Manifest-Version: 1.0\r\nMain-Class: app.Main\r\n"));
+        assert!(manifest.ends_with("\r\n\r\n"));
+        for line in manifest.split("\r\n") {
+            assert!(line.len() <= MANIFEST_LINE_BYTES, "línea demasiado larga: {}", line);
+        }
+        // Al unir las continuaciones se recupera el valor original
+        let unwrapped = manifest.replace("\r\n ", "");
+        assert!(unwrapped.contains(&format!("Class-Path: {}\r\n", class_path.join(" "))));
+    }
+
+    #[test]
+    fn test_write_jar_is_reproducible() {
+        let dir = std::env::temp_dir().join("forge_test_jar_reproducible");
+        let _ = std::fs::remove_dir_all(&dir);
+        let classes = dir.join("classes");
+        std::fs::create_dir_all(classes.join("app/util")).unwrap();
+        std::fs::write(classes.join("app/Main.class"), "main").unwrap();
+        std::fs::write(classes.join("app/util/Strings.class"), "strings").unwrap();
+
+        let manifest = Manifest::default().attribute("Main-Class", "app.Main");
+        let first = dir.join("first.jar");
+        let second = dir.join("second.jar");
+        assert_eq!(write_jar(&first, &manifest, &classes).unwrap(), 2);
+        std::thread::sleep(std::time::Duration::from_millis(1100));
+        write_jar(&second, &manifest, &classes).unwrap();
+        assert_eq!(std::fs::read(&first).unwrap(), std::fs::read(&second).unwrap());
+
+        let archive = ZipArchive::new(std::fs::File::open(&first).unwrap()).unwrap();
+        let names: Vec<&str> = archive.file_names().collect();
+        assert_eq!(names[..2], ["META-INF/", MANIFEST_PATH]);
+        assert!(names.contains(&"app/util/"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_fat_jar_applies_relocations() {
+        let dir = std::env::temp_dir().join("forge_test_fat_jar_shade");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let lib = dir.join("gson.jar");
+        zip_with(&lib, &[
+            ("com/google/gson/Gson.class", "gson"),
+            ("META-INF/services/com.google.gson.Spi", "com.google.gson.Impl\n"),
+        ]);
+
+        let jar = dir.join("app.jar");
+        let relocations = vec![Relocation::new("com.google.gson", "app.shaded.gson")];
+        write_fat_jar(&jar, &Manifest::default(), &[lib], &relocations).unwrap();
+
+        assert!(read_entry(&jar, "app/shaded/gson/Gson.class").is_some());
+        assert!(read_entry(&jar, "com/google/gson/Gson.class").is_none());
+        assert_eq!(
+            read_entry(&jar, "META-INF/services/app.shaded.gson.Spi").unwrap(),
+            "app.shaded.gson.Impl\n"
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -12,9 +12,14 @@ use anyhow::Context;
 use colored::Colorize;
 use walkdir::WalkDir;
 
-use cyrce_forge_core::config::ForgeConfig;
+use cyrce_forge_core::config::{CompilerOptions, ForgeConfig, PackageMode};
 use cyrce_forge_core::error::{ForgeError, ForgeResult};
 
+use crate::daemon::CompilerCommand;
+use crate::incremental::{self, CompilePlan, IncrementalState};
+use crate::jpms::{self, ModuleDescriptor};
+use crate::{jar, junit, resources, test_report};
+
 /// Módulo de compilación Java.
 pub struct JavaModule;
 
@@ -42,6 +47,12 @@ impl JavaModule {
         // Crear directorio de salida
         std::fs::create_dir_all(&output_dir).context("No se pudo crear el directorio de salida")?;
 
+        // Recursos (configuración, META-INF/services, ...) junto a las clases
+        let resources = resources::copy_resources(config, project_dir, false, &output_dir)?;
+        if resources > 0 {
+            println!("   {}", format!("📁 {} recursos copiados", resources).dimmed());
+        }
+
         // Encontrar todos los archivos .java
         let java_files: Vec<PathBuf> = WalkDir::new(&source_dir)
             .into_iter()
@@ -63,16 +74,11 @@ impl JavaModule {
             return Ok(());
         }
 
-        println!(
-            "   {}",
-            format!("☕ Compilando {} archivos Java...", java_files.len()).cyan()
-        );
-
         // Construir classpath con dependencias descargadas y locales (sub-proyectos)
         let mut classpath = build_classpath(&deps_dir);
         let local_cp = config.get_local_classpath(project_dir);
+        let sep = if cfg!(target_os = "windows") { ";" } else { ":" };
         if !local_cp.is_empty() {
-            let sep = if cfg!(target_os = "windows") { ";" } else { ":" };
             if classpath.is_empty() {
                 classpath = local_cp;
             } else {
@@ -81,18 +87,96 @@ impl JavaModule {
             }
         }
 
-        // Construir comando javac
         let target = java_config
             .map(|j| j.target.as_str())
             .unwrap_or("17");
 
-        let mut cmd = tokio::process::Command::new("javac");
+        // JPMS: con module-info.java, las dependencias modulares van al --module-path
+        let descriptor = ModuleDescriptor::load(&source_dir);
+        let mut module_path = String::new();
+        if let Some(descriptor) = &descriptor {
+            let (modules, plain) = descriptor.split(&split_classpath(&classpath));
+            module_path = modules.join(sep);
+            classpath = plain.join(sep);
+            println!("   {}", format!("🧩 Módulo {}", descriptor.name).dimmed());
+        }
+
+        // Procesadores de anotaciones (Lombok, MapStruct, ...): sus fuentes van a build/generated
+        let processor_path = build_classpath(&project_dir.join(".forge").join("processors"));
+        let generated_dir = project_dir.join(&config.project.output_dir).join("generated").join("sources");
+        let exports = !jpms::compile_flags(java_config).is_empty();
+        let mut options = javac_options(&config.compiler_options(), target, exports);
+        options.extend(processor_options(&processor_path, &generated_dir));
+        options.extend(jpms::compile_flags(java_config));
+        if !module_path.is_empty() {
+            options.extend(["--module-path".to_string(), module_path]);
+        }
+
+        // Compilación incremental: solo lo cambiado y lo que depende de ello
+        let mut fingerprint = format!("{} -cp {}", options.join(" "), classpath);
+        if let Some(descriptor) = &descriptor {
+            fingerprint.push_str(&format!(" {}", descriptor.fingerprint()));
+        }
+        let sources = match incremental::plan(project_dir, &source_dir, &output_dir, &fingerprint)? {
+            CompilePlan::UpToDate => {
+                println!("   {}", "⚡ Sin cambios en fuentes Java".dimmed());
+                return Ok(());
+            }
+            // Solo se borraron fuentes: basta con haber borrado sus clases
+            CompilePlan::Incremental { sources, .. } if sources.is_empty() => {
+                println!("   {}", "🧹 Clases de fuentes eliminadas borradas".dimmed());
+                IncrementalState::scan(&output_dir, &source_dir, &fingerprint).save(project_dir)?;
+                return Ok(());
+            }
+            CompilePlan::Incremental { mut sources, changed } if sources.len() < java_files.len() => {
+                println!(
+                    "   {}",
+                    format!(
+                        "☕ Compilación incremental: {} de {} archivos Java ({} cambiados, {} dependientes)",
+                        sources.len(),
+                        java_files.len(),
+                        changed,
+                        sources.len() - changed
+                    )
+                    .cyan()
+                );
+                if descriptor.is_some() {
+                    // Dentro de un módulo, javac toma las clases sin cambios de -d
+                    // siempre que el descriptor se compile junto con las fuentes
+                    let module_info = source_dir.join(jpms::MODULE_INFO);
+                    if !sources.contains(&module_info) {
+                        sources.push(module_info);
+                    }
+                } else {
+                    // Las clases sin cambios se toman del directorio de salida
+                    classpath = if classpath.is_empty() {
+                        output_dir.to_string_lossy().to_string()
+                    } else {
+                        format!("{}{}{}", output_dir.display(), sep, classpath)
+                    };
+                }
+                sources
+            }
+            _ => {
+                println!(
+                    "   {}",
+                    format!("☕ Compilando {} archivos Java...", java_files.len()).cyan()
+                );
+                // Sin restos de fuentes generadas por clases que ya no existen
+                let _ = std::fs::remove_dir_all(&generated_dir);
+                java_files.clone()
+            }
+        };
+
+        if !processor_path.is_empty() {
+            std::fs::create_dir_all(&generated_dir).context("No se pudo crear el directorio de fuentes generadas")?;
+        }
+
+        // Construir comando javac
+        let mut cmd = CompilerCommand::javac();
 
         // Opciones de compilación
-        cmd.arg("-d")
-            .arg(&output_dir)
-            .arg("--release")
-            .arg(target);
+        cmd.arg("-d").arg(&output_dir).args(&options);
 
         // Agregar classpath si hay dependencias
         if !classpath.is_empty() {
@@ -100,15 +184,11 @@ impl JavaModule {
         }
 
         // Agregar archivos fuente
-        for file in &java_files {
+        for file in &sources {
             cmd.arg(file);
         }
 
-        cmd.current_dir(project_dir)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
-
-        let output = cmd.output().await.map_err(|e| {
+        let output = cmd.output(project_dir).await.map_err(|e| {
             if e.kind() == std::io::ErrorKind::NotFound {
                 ForgeError::CommandNotFound {
                     command: "javac".to_string(),
@@ -121,21 +201,25 @@ impl JavaModule {
             }
         })?;
 
-        if !output.status.success() {
+        if !output.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
             eprintln!("{}", stderr);
+            incremental::invalidate(project_dir)?;
             return Err(ForgeError::TaskFailed {
                 task_name: "javac".to_string(),
-                exit_code: output.status.code().unwrap_or(-1),
+                exit_code: output.code,
             }
             .into());
         }
 
         println!(
             "   {}",
-            format!("✅ {} archivos compilados exitosamente", java_files.len()).green()
+            format!("✅ {} archivos compilados exitosamente", sources.len()).green()
         );
 
+        // Mapa de dependencias para el próximo build incremental
+        IncrementalState::scan(&output_dir, &source_dir, &fingerprint).save(project_dir)?;
+
         Ok(())
     }
 
@@ -154,53 +238,37 @@ impl JavaModule {
             .into());
         }
 
-        println!("   {}", "📦 Empaquetando JAR...".cyan());
+        let classpath = Self::runtime_classpath(config, project_dir);
 
-        let mut cmd = tokio::process::Command::new("jar");
-        cmd.arg("cf").arg(&jar_path);
-
-        // Agregar manifiesto con Main-Class si está definido
-        if let Some(main_class) = config.main_entry() {
-            let manifest_dir = output_dir.join("META-INF");
-            std::fs::create_dir_all(&manifest_dir)?;
-            let manifest_path = manifest_dir.join("MANIFEST.MF");
-            std::fs::write(
-                &manifest_path,
-                format!(
-                    "Manifest-Version: 1.0\nMain-Class: {}\nBuilt-By: FORGE\n",
-                    main_class
-                ),
-            )?;
-            cmd.arg("--manifest").arg(&manifest_path);
+        if config.package.mode == PackageMode::FatJar {
+            println!("   {}", "📦 Empaquetando fat-jar (clases + dependencias)...".cyan());
+            jar::package_fat_jar(config, &classes_dir, &jar_path, &classpath)?;
+            return Ok(jar_path);
         }
 
-        cmd.arg("-C").arg(&classes_dir).arg(".");
-
-        cmd.current_dir(project_dir)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
+        println!("   {}", "📦 Empaquetando JAR...".cyan());
 
-        let output = cmd.output().await.map_err(|e| ForgeError::CommandNotFound {
-            command: format!("jar: {}", e),
-        })?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(ForgeError::TaskFailed {
-                task_name: format!("jar: {}", stderr),
-                exit_code: output.status.code().unwrap_or(-1),
-            }
-            .into());
-        }
+        // Las dependencias se referencian por nombre: se esperan junto al JAR
+        let manifest = jar::Manifest::for_project(config, &jar::class_path_names(&classpath));
+        let entries = jar::write_jar(&jar_path, &manifest, &classes_dir)?;
 
         println!(
             "   {}",
-            format!("📦 JAR creado: {}", jar_path.display()).green()
+            format!("📦 JAR creado: {} ({} entradas)", jar_path.display(), entries).green()
         );
 
         Ok(jar_path)
     }
 
+    /// Classpath que necesita el JAR del proyecto para ejecutarse: módulos locales y
+    /// dependencias descargadas (listas separadas por el separador de la plataforma).
+    pub fn runtime_classpath(config: &ForgeConfig, project_dir: &Path) -> Vec<String> {
+        vec![
+            config.get_local_classpath(project_dir),
+            build_classpath(&project_dir.join(".forge").join("deps")),
+        ]
+    }
+
     /// Ejecuta el proyecto Java.
     pub async fn run(config: &ForgeConfig, project_dir: &Path) -> ForgeResult<()> {
         let main_class = config
@@ -232,10 +300,26 @@ impl JavaModule {
         println!();
 
         let mut cmd = tokio::process::Command::new("java");
-        cmd.arg("-cp")
-            .arg(&classpath)
-            .arg(&main_class)
-            .current_dir(project_dir)
+        cmd.args(jvm_options(&config.compiler_options()))
+            .args(jpms::runtime_flags(config.java.as_ref()));
+
+        // Un proyecto modular se ejecuta con -m módulo/clase desde el module path
+        let source_dir = project_dir.join(config.source_dir());
+        match ModuleDescriptor::load(&source_dir) {
+            Some(descriptor) => {
+                let (modules, plain) = descriptor.split(&split_classpath(&classpath));
+                cmd.arg("--module-path").arg(modules.join(separator));
+                if !plain.is_empty() {
+                    cmd.arg("-cp").arg(plain.join(separator));
+                }
+                cmd.arg("-m").arg(format!("{}/{}", descriptor.name, main_class));
+            }
+            None => {
+                cmd.arg("-cp").arg(&classpath).arg(&main_class);
+            }
+        }
+
+        cmd.current_dir(project_dir)
             .stdout(Stdio::inherit())
             .stderr(Stdio::inherit());
 
@@ -282,10 +366,36 @@ impl JavaModule {
             Self::compile(config, project_dir).await?;
         }
 
+        // Con module-info.java, los tests no corren en el module path: se compilan y
+        // ejecutan en el classpath (como surefire con useModulePath=false) y el
+        // descriptor no se aplica; [java] add-opens/add-exports al módulo pasan a ALL-UNNAMED
+        let (compile_flags, runtime_flags) = match ModuleDescriptor::load(&project_dir.join(config.source_dir())) {
+            Some(descriptor) => {
+                println!(
+                    "   {}",
+                    format!(
+                        "ℹ️  Módulo {}: los tests se compilan y ejecutan en el classpath (módulo sin nombre)",
+                        descriptor.name
+                    )
+                    .dimmed()
+                );
+                (
+                    jpms::unnamed_flags(jpms::compile_flags(java_config), &descriptor.name),
+                    jpms::unnamed_flags(jpms::runtime_flags(java_config), &descriptor.name),
+                )
+            }
+            None => (jpms::compile_flags(java_config), jpms::runtime_flags(java_config)),
+        };
+
         // 2. Compilar los tests
         std::fs::create_dir_all(&test_classes_dir)
             .context("No se pudo crear el directorio test-classes")?;
 
+        let test_resources = resources::copy_resources(config, project_dir, true, &test_classes_dir)?;
+        if test_resources > 0 {
+            println!("   {}", format!("📁 {} recursos de test copiados", test_resources).dimmed());
+        }
+
         let test_files: Vec<PathBuf> = WalkDir::new(&test_source_dir)
             .into_iter()
             .filter_map(|e| e.ok())
@@ -324,7 +434,7 @@ impl JavaModule {
         if !local_cp.is_empty() { cp_parts.push(local_cp); }
 
         // Obtener el jar del standalone console (descargarlo si es necesario)
-        let junit_console_jar = Self::download_junit_standalone().await?;
+        let junit_console_jar = junit::console_launcher(config, project_dir).await?;
         cp_parts.push(junit_console_jar.to_string_lossy().to_string());
 
         let separator = if cfg!(target_os = "windows") { ";" } else { ":" };
@@ -334,12 +444,20 @@ impl JavaModule {
             .map(|j| j.target.as_str())
             .unwrap_or("17");
 
-        let mut javac_cmd = tokio::process::Command::new("javac");
+        let processor_path = build_classpath(&project_dir.join(".forge").join("processors"));
+        let generated_dir = output_dir.join("generated").join("test-sources");
+        let _ = std::fs::remove_dir_all(&generated_dir);
+        if !processor_path.is_empty() {
+            std::fs::create_dir_all(&generated_dir).context("No se pudo crear el directorio de fuentes generadas")?;
+        }
+
+        let mut javac_cmd = CompilerCommand::javac();
         javac_cmd
             .arg("-d")
             .arg(&test_classes_dir)
-            .arg("--release")
-            .arg(target)
+            .args(javac_options(&config.compiler_options(), target, !compile_flags.is_empty()))
+            .args(processor_options(&processor_path, &generated_dir))
+            .args(&compile_flags)
             .arg("-cp")
             .arg(&compile_classpath);
 
@@ -348,20 +466,17 @@ impl JavaModule {
         }
 
         let javac_out = javac_cmd
-            .current_dir(project_dir)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
+            .output(project_dir)
             .await
             .map_err(|e| ForgeError::CommandNotFound {
                 command: format!("javac (test): {}", e),
             })?;
 
-        if !javac_out.status.success() {
+        if !javac_out.success() {
             let stderr = String::from_utf8_lossy(&javac_out.stderr);
             return Err(ForgeError::TaskFailed {
                 task_name: format!("javac tests: {}", stderr),
-                exit_code: javac_out.status.code().unwrap_or(-1),
+                exit_code: javac_out.code,
             }
             .into());
         }
@@ -390,15 +505,17 @@ impl JavaModule {
 
         let exec_classpath = exec_cp_parts.join(separator);
 
+        let reports_dir = test_report::reports_dir(config, project_dir, "junit")?;
         let mut java_cmd = tokio::process::Command::new("java");
         java_cmd
+            .args(jvm_options(&config.compiler_options()))
+            .args(&runtime_flags)
             .arg("-jar")
             .arg(&junit_console_jar)
             .arg("--class-path")
             .arg(&exec_classpath)
             .arg("--scan-class-path")
-            .arg("--details=tree") // Salida detallada en árbol
-            .arg("--disable-banner");
+            .args(junit::launcher_args(config, &reports_dir));
 
         let status = java_cmd
             .current_dir(project_dir)
@@ -410,6 +527,8 @@ impl JavaModule {
                 command: format!("java (junit): {}", e),
             })?;
 
+        test_report::summarize(&reports_dir)?;
+
         if !status.success() {
             return Err(ForgeError::TaskFailed {
                 task_name: "java test".to_string(),
@@ -421,78 +540,189 @@ impl JavaModule {
         Ok(())
     }
 
-    /// Descarga la consola standalone de JUnit si no existe
-    async fn download_junit_standalone() -> ForgeResult<PathBuf> {
-        let tools_dir = dirs::home_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join(".forge")
-            .join("tools");
-
-        std::fs::create_dir_all(&tools_dir).map_err(|e| ForgeError::IoError {
-            path: tools_dir.clone(),
-            message: e.to_string(),
-        })?;
-
-        let jar_name = "junit-platform-console-standalone-6.0.3.jar";
-        let jar_path = tools_dir.join(jar_name);
+}
 
-        if jar_path.exists() {
-            return Ok(jar_path);
-        }
+/// javac de un proyecto Kotlin: compila los fuentes Java de `source_dirs` (los del
+/// proyecto mixto y los generados por kapt/KSP) en `output_dir`, donde kotlinc ya
+/// dejó sus clases, con las opciones de [java]. Con `run_processors = false` (kapt
+/// ya los ejecutó) javac no vuelve a correr los procesadores de anotaciones.
+/// Sin compilación incremental: kotlinc también recompila todo en cada build.
+/// Devuelve cuántos archivos compiló.
+pub(crate) async fn compile_mixed_sources(
+    config: &ForgeConfig,
+    project_dir: &Path,
+    test: bool,
+    source_dirs: &[PathBuf],
+    output_dir: &Path,
+    classpath: &str,
+    run_processors: bool,
+) -> ForgeResult<usize> {
+    let java_files: Vec<PathBuf> = source_dirs
+        .iter()
+        .flat_map(|dir| WalkDir::new(dir).into_iter().filter_map(|e| e.ok()))
+        .filter(|e| e.path().extension().map(|ext| ext == "java").unwrap_or(false))
+        .map(|e| e.path().to_path_buf())
+        .collect();
+    if java_files.is_empty() {
+        return Ok(0);
+    }
 
-        println!(
-            "   {}",
-            "⬇️  Descargando JUnit Platform Console Standalone...".dimmed()
-        );
+    println!(
+        "   {}",
+        format!("☕ Compilando {} archivos Java contra las clases de Kotlin...", java_files.len()).cyan()
+    );
+
+    // Sin [java], el target de Java sigue al jvm-target de Kotlin
+    let java_config = config.java.as_ref();
+    let target = java_config
+        .map(|j| j.target.clone())
+        .or_else(|| config.kotlin.as_ref().map(|k| k.jvm_target.clone()))
+        .unwrap_or_else(|| "17".to_string());
+    let compiler = java_config.map(|j| j.compiler.clone()).unwrap_or_default();
+
+    let processor_path = if run_processors {
+        build_classpath(&project_dir.join(".forge").join("processors"))
+    } else {
+        String::new()
+    };
+    let generated = if test { "test-sources" } else { "sources" };
+    let generated_dir = project_dir.join(&config.project.output_dir).join("generated").join(generated);
+    let _ = std::fs::remove_dir_all(&generated_dir);
+    if !processor_path.is_empty() {
+        std::fs::create_dir_all(&generated_dir).context("No se pudo crear el directorio de fuentes generadas")?;
+    }
 
-        let url = "https://repo1.maven.org/maven2/org/junit/platform/junit-platform-console-standalone/1.12.0/junit-platform-console-standalone-1.12.0.jar"; 
-        // Nota: Para no romper el programa por una versión inexistente en Maven, bajaremos la latest *real* estable (1.12.0 Platform = JUnit Jupiter 5.12/6.0-M) pero lo guardaremos como 6.0.3.
-        
-        let client = reqwest::Client::new();
-        let response = client.get(url).send().await.map_err(|e: reqwest::Error| ForgeError::DownloadError {
-            url: url.to_string(),
-            message: e.to_string()
+    let sep = if cfg!(target_os = "windows") { ";" } else { ":" };
+    let classpath = if classpath.is_empty() {
+        output_dir.to_string_lossy().to_string()
+    } else {
+        format!("{}{}{}", output_dir.display(), sep, classpath)
+    };
+
+    let output = CompilerCommand::javac()
+        .arg("-d")
+        .arg(output_dir)
+        .args(javac_options(&compiler, &target, !jpms::compile_flags(java_config).is_empty()))
+        .args(processor_options(&processor_path, &generated_dir))
+        .args((!run_processors).then_some("-proc:none"))
+        .args(jpms::compile_flags(java_config))
+        .arg("-cp")
+        .arg(&classpath)
+        .args(&java_files)
+        .output(project_dir)
+        .await
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                ForgeError::CommandNotFound {
+                    command: "javac".to_string(),
+                }
+            } else {
+                ForgeError::IoError {
+                    path: project_dir.to_path_buf(),
+                    message: format!("Error al ejecutar javac: {}", e),
+                }
+            }
         })?;
 
-        if !response.status().is_success() {
-            return Err(ForgeError::DownloadError {
-                url: url.to_string(),
-                message: format!("HTTP {}", response.status()),
-            }.into());
+    if !output.success() {
+        eprintln!("{}", String::from_utf8_lossy(&output.stderr));
+        return Err(ForgeError::TaskFailed {
+            task_name: if test { "javac tests" } else { "javac" }.to_string(),
+            exit_code: output.code,
         }
+        .into());
+    }
 
-        let bytes = response.bytes().await.map_err(|e: reqwest::Error| ForgeError::DownloadError {
-            url: url.to_string(),
-            message: e.to_string()
-        })?;
+    Ok(java_files.len())
+}
 
-        std::fs::write(&jar_path, &bytes).map_err(|e| ForgeError::IoError {
-            path: jar_path.clone(),
-            message: e.to_string(),
-        })?;
+/// Opciones de javac: `--release`, encoding, preview, `-Werror` y `compiler-args` (al final,
+/// para que puedan agregar o sobreescribir lo anterior). javac no admite `--add-exports`
+/// de módulos del JDK junto con `--release`: en ese caso se usa `-source`/`-target`.
+fn javac_options(options: &CompilerOptions, target: &str, add_exports: bool) -> Vec<String> {
+    let mut args = if add_exports {
+        vec!["-source".to_string(), target.to_string(), "-target".to_string(), target.to_string()]
+    } else {
+        vec!["--release".to_string(), target.to_string()]
+    };
+    if let Some(encoding) = &options.encoding {
+        args.extend(["-encoding".to_string(), encoding.clone()]);
+    }
+    if options.preview {
+        args.push("--enable-preview".to_string());
+    }
+    if options.warnings_as_errors {
+        args.push("-Werror".to_string());
+    }
+    args.extend(options.compiler_args.iter().cloned());
+    args
+}
 
-        Ok(jar_path)
+/// `-processorpath` con los procesadores de `[annotation-processors]` y `-s` con el
+/// directorio de fuentes generadas. Sin procesadores declarados no se agrega nada y
+/// javac sigue buscándolos en el classpath, como siempre.
+fn processor_options(processor_path: &str, generated_dir: &Path) -> Vec<String> {
+    if processor_path.is_empty() {
+        return Vec::new();
+    }
+    vec![
+        "-processorpath".to_string(),
+        processor_path.to_string(),
+        "-s".to_string(),
+        generated_dir.to_string_lossy().to_string(),
+    ]
+}
+
+/// Opciones de la JVM al ejecutar: las clases compiladas con preview lo exigen también.
+pub(crate) fn jvm_options(options: &CompilerOptions) -> Vec<&'static str> {
+    if options.preview {
+        vec!["--enable-preview"]
+    } else {
+        Vec::new()
     }
 }
 
+/// Entradas de un classpath ya armado con el separador de la plataforma.
+pub(crate) fn split_classpath(classpath: &str) -> Vec<String> {
+    std::env::split_paths(classpath)
+        .filter(|entry| !entry.as_os_str().is_empty())
+        .map(|entry| entry.to_string_lossy().to_string())
+        .collect()
+}
+
 /// Construye el classpath con todos los JARs en el directorio de dependencias.
-fn build_classpath(deps_dir: &Path) -> String {
+/// Lee `classpath.txt` (rutas al almacén global escritas por el resolver) y
+/// agrega cualquier JAR suelto que haya en el directorio.
+pub(crate) fn build_classpath(deps_dir: &Path) -> String {
     if !deps_dir.exists() {
         return String::new();
     }
 
     let separator = if cfg!(target_os = "windows") { ";" } else { ":" };
 
-    WalkDir::new(deps_dir)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| {
-            e.path()
-                .extension()
-                .map(|ext| ext == "jar")
-                .unwrap_or(false)
+    let mut entries: Vec<String> = std::fs::read_to_string(deps_dir.join("classpath.txt"))
+        .map(|content| {
+            content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(String::from)
+                .collect()
         })
-        .map(|e| e.path().to_string_lossy().to_string())
-        .collect::<Vec<_>>()
-        .join(separator)
+        .unwrap_or_default();
+
+    entries.extend(
+        WalkDir::new(deps_dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| {
+                e.path()
+                    .extension()
+                    .map(|ext| ext == "jar")
+                    .unwrap_or(false)
+            })
+            .map(|e| e.path().to_string_lossy().to_string()),
+    );
+
+    entries.join(separator)
 }
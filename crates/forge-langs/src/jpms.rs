@@ -0,0 +1,265 @@
+// =============================================================================
+// 🔥 FORGE — Módulos de Lenguaje: Módulos Java (JPMS)
+// =============================================================================
+// Si el código fuente tiene un module-info.java, el proyecto se compila y se
+// ejecuta como módulo: las dependencias modulares (con module-info.class o
+// Automatic-Module-Name) y los JARs planos que el descriptor `requires` van al
+// --module-path; el resto queda en el classpath (módulo sin nombre).
+// =============================================================================
+
+use std::io::Read;
+use std::path::Path;
+
+use zip::ZipArchive;
+
+use cyrce_forge_core::config::JavaConfig;
+
+/// Nombre del descriptor de módulo en el directorio de código fuente.
+pub const MODULE_INFO: &str = "module-info.java";
+
+/// Lo que FORGE necesita de un module-info.java.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleDescriptor {
+    /// Nombre del módulo (ej: com.acme.app)
+    pub name: String,
+
+    /// Módulos requeridos (`requires`, `requires transitive`, `requires static`)
+    pub requires: Vec<String>,
+}
+
+impl ModuleDescriptor {
+    /// Lee `module-info.java` de la raíz del directorio de código, si existe.
+    pub fn load(source_dir: &Path) -> Option<Self> {
+        Self::parse(&std::fs::read_to_string(source_dir.join(MODULE_INFO)).ok()?)
+    }
+
+    /// Extrae nombre y `requires` de un descriptor, ignorando comentarios,
+    /// imports y anotaciones.
+    pub fn parse(source: &str) -> Option<Self> {
+        let code = strip_comments(source);
+        let (header, body) = code.split_once('{')?;
+
+        let mut words = header
+            .split(|c: char| c.is_whitespace() || c == ';')
+            .filter(|w| !w.is_empty())
+            .skip_while(|w| *w != "module");
+        words.next()?;
+        let name = words.next()?.to_string();
+
+        let requires = body
+            .split(';')
+            .filter_map(|statement| {
+                let mut words = statement.split_whitespace();
+                if words.next()? != "requires" {
+                    return None;
+                }
+                words.find(|w| *w != "transitive" && *w != "static").map(String::from)
+            })
+            .collect();
+
+        Some(Self { name, requires })
+    }
+
+    /// Huella para la compilación incremental: cambiar el nombre o los `requires`
+    /// obliga a recompilar todo el módulo.
+    pub fn fingerprint(&self) -> String {
+        format!("module={} requires={:?}", self.name, self.requires)
+    }
+
+    /// Reparte un classpath entre `--module-path` y `-cp`.
+    pub fn split(&self, entries: &[String]) -> (Vec<String>, Vec<String>) {
+        entries.iter().cloned().partition(|entry| {
+            let path = Path::new(entry);
+            is_module(path)
+                || path
+                    .file_name()
+                    .map(|name| self.requires.contains(&automatic_module_name(&name.to_string_lossy())))
+                    .unwrap_or(false)
+        })
+    }
+}
+
+/// ¿Es un módulo explícito (module-info.class) o declara `Automatic-Module-Name`?
+/// Acepta JARs y directorios de clases (sub-proyectos locales).
+pub fn is_module(path: &Path) -> bool {
+    if path.is_dir() {
+        return path.join("module-info.class").is_file();
+    }
+
+    let Some(mut archive) = std::fs::File::open(path).ok().and_then(|f| ZipArchive::new(f).ok()) else {
+        return false;
+    };
+    let explicit = archive.file_names().any(|name| {
+        name == "module-info.class"
+            || (name.starts_with("META-INF/versions/") && name.ends_with("/module-info.class"))
+    });
+    if explicit {
+        return true;
+    }
+
+    let mut manifest = String::new();
+    let Ok(mut entry) = archive.by_name("META-INF/MANIFEST.MF") else {
+        return false;
+    };
+    entry.read_to_string(&mut manifest).is_ok()
+        && manifest.lines().any(|line| line.starts_with("Automatic-Module-Name:"))
+}
+
+/// Nombre que la JVM deriva para un JAR plano en el module path
+/// (`commons-lang3-3.14.0.jar` → `commons.lang3`).
+pub fn automatic_module_name(file_name: &str) -> String {
+    let stem = file_name.strip_suffix(".jar").unwrap_or(file_name);
+
+    // La versión empieza en el primer '-' seguido de un dígito
+    let bytes = stem.as_bytes();
+    let end = (1..bytes.len())
+        .find(|&i| bytes[i - 1] == b'-' && bytes[i].is_ascii_digit())
+        .map(|i| i - 1)
+        .unwrap_or(stem.len());
+
+    stem[..end]
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Flags de javac: `--add-exports` de [java], como `--add-exports=valor` para que cada
+/// flag sea un solo argumento (así también se pasan a jpackage con `--java-options`).
+pub fn compile_flags(java: Option<&JavaConfig>) -> Vec<String> {
+    let Some(java) = java else {
+        return Vec::new();
+    };
+    java.add_exports
+        .iter()
+        .map(|export| format!("--add-exports={}", export))
+        .collect()
+}
+
+/// Flags de la JVM: `--add-opens` y `--add-exports` de [java].
+pub fn runtime_flags(java: Option<&JavaConfig>) -> Vec<String> {
+    let Some(java) = java else {
+        return Vec::new();
+    };
+    let opens = java
+        .add_opens
+        .iter()
+        .map(|open| format!("--add-opens={}", open));
+    opens.chain(compile_flags(Some(java))).collect()
+}
+
+/// Flags de [java] para los tests, que se compilan y ejecutan en el classpath
+/// (módulo sin nombre): los `--add-exports`/`--add-opens` dirigidos al módulo del
+/// proyecto pasan a `ALL-UNNAMED`, porque ese módulo no existe al compilar los tests.
+pub fn unnamed_flags(flags: Vec<String>, module: &str) -> Vec<String> {
+    flags
+        .into_iter()
+        .map(|flag| {
+            let Some((option, targets)) = flag.rsplit_once('=') else {
+                return flag;
+            };
+            let mut retargeted: Vec<&str> = Vec::new();
+            for target in targets.split(',') {
+                let target = if target == module { "ALL-UNNAMED" } else { target };
+                if !retargeted.contains(&target) {
+                    retargeted.push(target);
+                }
+            }
+            format!("{}={}", option, retargeted.join(","))
+        })
+        .collect()
+}
+
+/// Quita los comentarios `//` y `/* */`.
+fn strip_comments(source: &str) -> String {
+    let mut code = String::with_capacity(source.len());
+    let mut rest = source;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("//") {
+            rest = after.find('\n').map(|i| &after[i..]).unwrap_or("");
+        } else if let Some(after) = rest.strip_prefix("/*") {
+            rest = after.find("*/").map(|i| &after[i + 2..]).unwrap_or("");
+            code.push(' ');
+        } else {
+            let c = rest.chars().next().unwrap_or(' ');
+            code.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_module_descriptor() {
+        let source = r#"
+            /* module falso { requires nada; } */
+            import com.acme.spi.Plugin;
+
+            @Deprecated
+            open module com.acme.app {
+                requires java.logging; // requires comentado;
+                requires transitive com.google.gson;
+                requires static commons.lang3;
+                exports com.acme.app.api;
+                uses Plugin;
+            }
+        "#;
+        let descriptor = ModuleDescriptor::parse(source).unwrap();
+
+        assert_eq!(descriptor.name, "com.acme.app");
+        assert_eq!(descriptor.requires, vec!["java.logging", "com.google.gson", "commons.lang3"]);
+        assert!(ModuleDescriptor::parse("public class Main {}").is_none());
+    }
+
+    #[test]
+    fn test_split_module_path() {
+        assert_eq!(automatic_module_name("commons-lang3-3.14.0.jar"), "commons.lang3");
+        assert_eq!(automatic_module_name("guava-33.0.0-jre.jar"), "guava");
+        assert_eq!(automatic_module_name("my_lib.jar"), "my.lib");
+
+        let dir = std::env::temp_dir().join("forge_test_jpms_split");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let jar = |name: &str, entries: &[(&str, &str)]| {
+            let path = dir.join(name);
+            crate::jar::tests::zip_with(&path, entries);
+            path.to_string_lossy().to_string()
+        };
+        let explicit = jar("gson-2.11.0.jar", &[("module-info.class", "")]);
+        let automatic = jar("slf4j-api-2.0.jar", &[("META-INF/MANIFEST.MF", "Automatic-Module-Name: org.slf4j\r\n")]);
+        let required = jar("commons-lang3-3.14.0.jar", &[("org/apache/A.class", "")]);
+        let plain = jar("legacy-1.0.jar", &[("legacy/B.class", "")]);
+
+        let descriptor = ModuleDescriptor {
+            name: "com.acme.app".to_string(),
+            requires: vec!["commons.lang3".to_string()],
+        };
+        let (modules, classpath) =
+            descriptor.split(&[explicit.clone(), automatic.clone(), required.clone(), plain.clone()]);
+        assert_eq!(modules, vec![explicit, automatic, required]);
+        assert_eq!(classpath, vec![plain]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_unnamed_flags_for_tests() {
+        let flags = vec![
+            "--add-opens=java.base/java.lang=com.acme.app".to_string(),
+            "--add-exports=java.base/sun.nio.ch=com.acme.app,ALL-UNNAMED,org.other".to_string(),
+            "--add-exports=java.base/sun.misc=org.other".to_string(),
+        ];
+        assert_eq!(
+            unnamed_flags(flags, "com.acme.app"),
+            vec![
+                "--add-opens=java.base/java.lang=ALL-UNNAMED",
+                "--add-exports=java.base/sun.nio.ch=ALL-UNNAMED,org.other",
+                "--add-exports=java.base/sun.misc=org.other",
+            ]
+        );
+    }
+}
@@ -0,0 +1,115 @@
+// =============================================================================
+// 🔥 FORGE — Módulos de Lenguaje: JUnit Platform
+// =============================================================================
+// Lanzador de tests compartido por Java y Kotlin: la consola standalone de
+// JUnit Platform (`[test] junit-platform`) se resuelve con MavenResolver —los
+// mismos repositorios, almacén y verificación de checksums que las
+// dependencias— en .forge/junit/, y se vuelve a resolver al cambiar la versión.
+// Los resultados se piden como XML para el resumen de `test_report`.
+// =============================================================================
+
+use std::path::{Path, PathBuf};
+
+use cyrce_forge_core::config::ForgeConfig;
+use cyrce_forge_core::error::{ForgeError, ForgeResult};
+use cyrce_forge_deps::maven::MavenResolver;
+
+use crate::java::{build_classpath, split_classpath};
+
+/// Versión resuelta por última vez, junto al classpath de .forge/junit/.
+const VERSION_FILE: &str = "junit-platform.txt";
+
+/// JAR de junit-platform-console-standalone, resolviéndolo si hace falta.
+pub(crate) async fn console_launcher(config: &ForgeConfig, project_dir: &Path) -> ForgeResult<PathBuf> {
+    let version = &config.test.junit_platform;
+    let junit_dir = project_dir.join(".forge").join("junit");
+
+    if let Some(jar) = cached_launcher(&junit_dir, version) {
+        return Ok(jar);
+    }
+
+    let mut resolver = MavenResolver::new(config, project_dir)?;
+    let jar = resolver
+        .resolve_junit_platform(version)
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| ForgeError::DependencyResolutionFailed {
+            dependency: format!("org.junit.platform:junit-platform-console-standalone:{}", version),
+        })?;
+
+    std::fs::write(junit_dir.join(VERSION_FILE), version).map_err(|e| ForgeError::IoError {
+        path: junit_dir.join(VERSION_FILE),
+        message: e.to_string(),
+    })?;
+    Ok(jar)
+}
+
+/// Opciones del ConsoleLauncher: reportes XML en `reports_dir`, salida en árbol
+/// (salvo otro `--details` en `[test] launcher-args`), sin banner, y luego las
+/// de `launcher-args`.
+pub(crate) fn launcher_args(config: &ForgeConfig, reports_dir: &Path) -> Vec<String> {
+    let extra = &config.test.launcher_args;
+    let mut args = vec![format!("--reports-dir={}", reports_dir.display())];
+    if !extra.iter().any(|arg| arg.starts_with("--details")) {
+        args.push("--details=tree".to_string());
+    }
+    args.push("--disable-banner".to_string());
+    args.extend(extra.iter().cloned());
+    args
+}
+
+/// El JAR ya resuelto, si corresponde a la versión pedida y sigue en el almacén.
+fn cached_launcher(junit_dir: &Path, version: &str) -> Option<PathBuf> {
+    let resolved = std::fs::read_to_string(junit_dir.join(VERSION_FILE)).ok()?;
+    if resolved.trim() != version {
+        return None;
+    }
+    split_classpath(&build_classpath(junit_dir))
+        .into_iter()
+        .map(PathBuf::from)
+        .find(|jar| jar.exists())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cached_launcher_requires_same_version() {
+        let dir = std::env::temp_dir().join(format!("forge-junit-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let jar = dir.join("junit-platform-console-standalone-1.12.0.jar");
+        std::fs::write(&jar, b"jar").unwrap();
+        std::fs::write(dir.join("classpath.txt"), jar.to_string_lossy().as_bytes()).unwrap();
+
+        assert_eq!(cached_launcher(&dir, "1.12.0"), None);
+
+        std::fs::write(dir.join(VERSION_FILE), "1.12.0").unwrap();
+        assert_eq!(cached_launcher(&dir, "1.12.0"), Some(jar));
+        assert_eq!(cached_launcher(&dir, "1.11.4"), None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_launcher_args_allow_overriding_details() {
+        let mut config = ForgeConfig::default();
+        let reports = Path::new("build/test-results/junit");
+        assert_eq!(
+            launcher_args(&config, reports),
+            ["--reports-dir=build/test-results/junit", "--details=tree", "--disable-banner"]
+        );
+
+        config.test.launcher_args = vec!["--details=flat".into(), "--include-tag=fast".into()];
+        assert_eq!(
+            launcher_args(&config, reports),
+            [
+                "--reports-dir=build/test-results/junit",
+                "--disable-banner",
+                "--details=flat",
+                "--include-tag=fast"
+            ]
+        );
+    }
+}
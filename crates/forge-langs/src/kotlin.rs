@@ -11,9 +11,17 @@ use anyhow::Context;
 use colored::Colorize;
 use walkdir::WalkDir;
 
-use cyrce_forge_core::config::ForgeConfig;
+use cyrce_forge_core::config::{CompilerOptions, ForgeConfig, PackageMode};
 use cyrce_forge_core::error::{ForgeError, ForgeResult};
 
+use crate::compiler_plugins::{self, Kapt};
+use crate::daemon::CompilerCommand;
+use crate::java::{self, jvm_options};
+use crate::{jar, junit, resources, test_report};
+
+/// Clase principal de kotlin-compiler-embeddable (la misma que usa kotlinc).
+const EMBEDDED_COMPILER_MAIN: &str = "org.jetbrains.kotlin.cli.jvm.K2JVMCompiler";
+
 /// Módulo de compilación Kotlin.
 pub struct KotlinModule;
 
@@ -42,6 +50,12 @@ impl KotlinModule {
         // Crear directorio de salida
         std::fs::create_dir_all(&output_dir).context("No se pudo crear el directorio de salida")?;
 
+        // Recursos (configuración, META-INF/services, ...) junto a las clases
+        let resources = resources::copy_resources(config, project_dir, false, &output_dir)?;
+        if resources > 0 {
+            println!("   {}", format!("📁 {} recursos copiados", resources).dimmed());
+        }
+
         // Encontrar todos los archivos .kt
         let kt_files: Vec<PathBuf> = WalkDir::new(&source_dir)
             .into_iter()
@@ -55,7 +69,12 @@ impl KotlinModule {
             .map(|e| e.path().to_path_buf())
             .collect();
 
-        if kt_files.is_empty() {
+        // Proyecto mixto: kotlinc ve los fuentes Java y luego javac compila contra sus clases
+        let java_source_dir = config
+            .has_java_sources()
+            .then(|| project_dir.join(config.java_source_dir(false)));
+
+        if kt_files.is_empty() && java_source_dir.is_none() {
             println!(
                 "   {}",
                 "⚠️  No se encontraron archivos .kt para compilar".yellow()
@@ -63,94 +82,185 @@ impl KotlinModule {
             return Ok(());
         }
 
-        println!(
-            "   {}",
-            format!("🟣 Compilando {} archivos Kotlin...", kt_files.len()).cyan()
-        );
+        // Construir classpath: stdlib + dependencias del proyecto + módulos locales
+        let sep = if cfg!(target_os = "windows") { ";" } else { ":" };
+        let mut cp_parts: Vec<String> = Vec::new();
+
+        // Buscar kotlin-stdlib.jar automáticamente
+        if let Some(stdlib_path) = kotlin_stdlib(config) {
+            cp_parts.push(stdlib_path);
+        }
+
+        let deps_cp = build_kotlin_classpath(&deps_dir);
+        if !deps_cp.is_empty() {
+            cp_parts.push(deps_cp);
+        }
 
-        // Construir classpath con dependencias y locales
-        let mut classpath = build_kotlin_classpath(&deps_dir);
         let local_cp = config.get_local_classpath(project_dir);
         if !local_cp.is_empty() {
-            let sep = if cfg!(target_os = "windows") { ";" } else { ":" };
-            if classpath.is_empty() {
-                classpath = local_cp;
-            } else {
-                classpath.push_str(sep);
-                classpath.push_str(&local_cp);
-            }
+            cp_parts.push(local_cp);
         }
+        let classpath = cp_parts.join(sep);
 
         let jvm_target = kotlin_config
             .map(|k| k.jvm_target.as_str())
             .unwrap_or("17");
+        let plugin_args = compiler_plugins::plugin_args(config, project_dir);
 
-        // En Windows kotlinc es un .bat, necesitamos ejecutar via cmd
-        let mut cmd = if cfg!(target_os = "windows") {
-            let mut c = tokio::process::Command::new("cmd");
-            c.arg("/C").arg("kotlinc");
-            c
+        // kapt ejecuta [annotation-processors] sobre los fuentes Kotlin; sin kapt,
+        // solo javac los aplica a los fuentes Java de un proyecto mixto
+        let kapt = if kt_files.is_empty() {
+            None
         } else {
-            tokio::process::Command::new("kotlinc")
+            Kapt::load(config, project_dir, find_kotlin_home)
         };
+        if kapt.is_none() && !config.annotation_processors.is_empty() && java_source_dir.is_none() {
+            println!(
+                "   {}",
+                "⚠️  [annotation-processors] necesita kapt: define [kotlin] version o instala kotlinc con kotlin-annotation-processing.jar".yellow()
+            );
+        }
 
-        cmd.arg("-d").arg(&output_dir);
-        cmd.arg("-jvm-target").arg(jvm_target);
+        // Fuentes generados por KSP y kapt: los Kotlin los compila kotlinc; los Java,
+        // kotlinc los lee para resolver símbolos y luego los compila javac
+        let mut generated_kotlin: Vec<PathBuf> = Vec::new();
+        let mut generated_java: Vec<PathBuf> = Vec::new();
+        let existing_java_dir = java_source_dir.as_ref().filter(|dir| dir.exists());
+
+        if !kt_files.is_empty() {
+            let java_roots: Vec<PathBuf> = existing_java_dir.into_iter().cloned().collect();
+            if let Some((kotlin_out, java_out)) = compiler_plugins::run_ksp(
+                config,
+                project_dir,
+                std::slice::from_ref(&source_dir),
+                &java_roots,
+                &classpath,
+                &output_dir,
+            )
+            .await?
+            {
+                generated_kotlin.push(kotlin_out);
+                generated_java.push(java_out);
+            }
+        }
 
-        // Construir classpath: stdlib + dependencias del proyecto
-        let mut cp_parts: Vec<String> = Vec::new();
+        if let Some(kapt) = &kapt {
+            let sources = compiler_plugins::generated_dir(config, project_dir, "kapt");
+            let stubs = project_dir.join(&config.project.output_dir).join("tmp").join("kapt").join("stubs");
+            for dir in [&sources, &stubs] {
+                let _ = std::fs::remove_dir_all(dir);
+                std::fs::create_dir_all(dir).context("No se pudo crear el directorio de kapt")?;
+            }
 
-        // Buscar kotlin-stdlib.jar automáticamente
-        if let Some(stdlib_path) = find_kotlin_stdlib() {
-            cp_parts.push(stdlib_path);
-        }
+            println!("   {}", "🧩 Ejecutando procesadores de anotaciones (kapt)...".cyan());
+            // Sin -Werror: analizar como Kotlin 1.9 genera avisos de deprecación
+            let mut cmd = kotlinc_command(config, project_dir)?;
+            cmd.arg("-d").arg(&output_dir);
+            cmd.arg("-jvm-target").arg(jvm_target);
+            cmd.args(&config.compiler_options().compiler_args);
+            cmd.args(&plugin_args);
+            cmd.args(kapt.args(&sources, &stubs, &output_dir));
+            if !classpath.is_empty() {
+                cmd.arg("-cp").arg(&classpath);
+            }
+            cmd.args(&kt_files);
+            cmd.args(existing_java_dir);
+            cmd.args(generated_kotlin.iter().chain(&generated_java).filter(|dir| dir.exists()));
 
-        // Agregar dependencias del proyecto
-        if !classpath.is_empty() {
-            cp_parts.push(classpath);
-        }
+            let output = cmd.output(project_dir).await.map_err(|e| ForgeError::CommandNotFound {
+                command: format!("kotlinc (kapt): {}", e),
+            })?;
+            if !output.success() {
+                eprintln!("{}", String::from_utf8_lossy(&output.stderr));
+                return Err(ForgeError::TaskFailed {
+                    task_name: "kapt".to_string(),
+                    exit_code: output.code,
+                }
+                .into());
+            }
 
-        if !cp_parts.is_empty() {
-            let sep = if cfg!(target_os = "windows") { ";" } else { ":" };
-            cmd.arg("-cp").arg(cp_parts.join(sep));
+            let generated = compiler_plugins::count_sources(&sources);
+            if generated > 0 {
+                println!("   {}", format!("🧩 kapt generó {} fuentes", generated).dimmed());
+            }
+            generated_java.push(sources);
         }
+        generated_kotlin.retain(|dir| dir.exists());
+        generated_java.retain(|dir| dir.exists());
 
-        // Agregar archivos fuente
-        for file in &kt_files {
-            cmd.arg(file);
-        }
+        if !kt_files.is_empty() {
+            println!(
+                "   {}",
+                format!("🟣 Compilando {} archivos Kotlin...", kt_files.len()).cyan()
+            );
+
+            let mut cmd = kotlinc_command(config, project_dir)?;
+            cmd.arg("-d").arg(&output_dir);
+            cmd.arg("-jvm-target").arg(jvm_target);
+            cmd.args(kotlinc_options(&config.compiler_options()));
+            cmd.args(&plugin_args);
+
+            if !classpath.is_empty() {
+                cmd.arg("-cp").arg(&classpath);
+            }
+
+            // Agregar archivos fuente
+            for file in &kt_files {
+                cmd.arg(file);
+            }
 
-        cmd.current_dir(project_dir)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
+            // kotlinc solo lee los .java para resolver símbolos: no los compila
+            cmd.args(existing_java_dir);
+            cmd.args(generated_kotlin.iter().chain(&generated_java));
 
-        let output = cmd.output().await.map_err(|e| {
-            if e.kind() == std::io::ErrorKind::NotFound {
-                ForgeError::CommandNotFound {
-                    command: "kotlinc".to_string(),
+            let output = cmd.output(project_dir).await.map_err(|e| {
+                if e.kind() == std::io::ErrorKind::NotFound {
+                    ForgeError::CommandNotFound {
+                        command: "kotlinc".to_string(),
+                    }
+                } else {
+                    ForgeError::IoError {
+                        path: project_dir.to_path_buf(),
+                        message: format!("Error al ejecutar kotlinc: {}", e),
+                    }
                 }
-            } else {
-                ForgeError::IoError {
-                    path: project_dir.to_path_buf(),
-                    message: format!("Error al ejecutar kotlinc: {}", e),
+            })?;
+
+            if !output.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                eprintln!("{}", stderr);
+                return Err(ForgeError::TaskFailed {
+                    task_name: "kotlinc".to_string(),
+                    exit_code: output.code,
                 }
+                .into());
             }
-        })?;
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            eprintln!("{}", stderr);
-            return Err(ForgeError::TaskFailed {
-                task_name: "kotlinc".to_string(),
-                exit_code: output.status.code().unwrap_or(-1),
-            }
-            .into());
+            println!(
+                "   {}",
+                format!("✅ {} archivos Kotlin compilados exitosamente", kt_files.len()).green()
+            );
         }
 
-        println!(
-            "   {}",
-            format!("✅ {} archivos Kotlin compilados exitosamente", kt_files.len()).green()
-        );
+        let java_dirs: Vec<PathBuf> = java_source_dir.into_iter().chain(generated_java).collect();
+        if !java_dirs.is_empty() {
+            let compiled = java::compile_mixed_sources(
+                config,
+                project_dir,
+                false,
+                &java_dirs,
+                &output_dir,
+                &classpath,
+                kapt.is_none(),
+            )
+            .await?;
+            if compiled > 0 {
+                println!(
+                    "   {}",
+                    format!("✅ {} archivos Java compilados exitosamente", compiled).green()
+                );
+            }
+        }
 
         Ok(())
     }
@@ -169,51 +279,38 @@ impl KotlinModule {
             .into());
         }
 
-        println!("   {}", "📦 Empaquetando JAR de Kotlin...".cyan());
+        let classpath = Self::runtime_classpath(config, project_dir);
 
-        let mut cmd = tokio::process::Command::new("jar");
-        cmd.arg("cf").arg(&jar_path);
-
-        if let Some(main_class) = config.main_entry() {
-            let manifest_dir = output_dir.join("META-INF");
-            std::fs::create_dir_all(&manifest_dir)?;
-            let manifest_path = manifest_dir.join("MANIFEST.MF");
-            std::fs::write(
-                &manifest_path,
-                format!(
-                    "Manifest-Version: 1.0\nMain-Class: {}\nBuilt-By: FORGE\n",
-                    main_class
-                ),
-            )?;
-            cmd.arg("--manifest").arg(&manifest_path);
+        if config.package.mode == PackageMode::FatJar {
+            println!("   {}", "📦 Empaquetando fat-jar (clases + dependencias)...".cyan());
+            jar::package_fat_jar(config, &classes_dir, &jar_path, &classpath)?;
+            return Ok(jar_path);
         }
 
-        cmd.arg("-C").arg(&classes_dir).arg(".");
-        cmd.current_dir(project_dir)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
-
-        let output = cmd.output().await.map_err(|e| ForgeError::CommandNotFound {
-            command: format!("jar: {}", e),
-        })?;
+        println!("   {}", "📦 Empaquetando JAR de Kotlin...".cyan());
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(ForgeError::TaskFailed {
-                task_name: format!("jar (kotlin): {}", stderr),
-                exit_code: output.status.code().unwrap_or(-1),
-            }
-            .into());
-        }
+        // Las dependencias se referencian por nombre: se esperan junto al JAR
+        let manifest = jar::Manifest::for_project(config, &jar::class_path_names(&classpath));
+        let entries = jar::write_jar(&jar_path, &manifest, &classes_dir)?;
 
         println!(
             "   {}",
-            format!("📦 JAR creado: {}", jar_path.display()).green()
+            format!("📦 JAR creado: {} ({} entradas)", jar_path.display(), entries).green()
         );
 
         Ok(jar_path)
     }
 
+    /// Classpath que necesita el JAR del proyecto para ejecutarse: módulos locales,
+    /// dependencias descargadas y kotlin-stdlib.
+    pub fn runtime_classpath(config: &ForgeConfig, project_dir: &Path) -> Vec<String> {
+        vec![
+            config.get_local_classpath(project_dir),
+            build_kotlin_classpath(&project_dir.join(".forge").join("deps")),
+            kotlin_stdlib(config).unwrap_or_default(),
+        ]
+    }
+
     /// Ejecuta el proyecto Kotlin.
     pub async fn run(config: &ForgeConfig, project_dir: &Path) -> ForgeResult<()> {
         let main_class = config
@@ -229,7 +326,7 @@ impl KotlinModule {
         let mut cp_parts: Vec<String> = vec![classes_dir.to_string_lossy().to_string()];
         
         // Agregar stdlib de Kotlin para que 'java' pueda encontrar las clases base
-        if let Some(stdlib_path) = find_kotlin_stdlib() {
+        if let Some(stdlib_path) = kotlin_stdlib(config) {
             cp_parts.push(stdlib_path);
         }
 
@@ -249,7 +346,8 @@ impl KotlinModule {
         println!();
 
         let mut cmd = tokio::process::Command::new("java");
-        cmd.arg("-cp")
+        cmd.args(jvm_options(&config.compiler_options()))
+            .arg("-cp")
             .arg(&classpath)
             .arg(&main_class)
             .current_dir(project_dir)
@@ -280,7 +378,13 @@ impl KotlinModule {
                 .unwrap_or("src/test/kotlin"),
         );
 
-        if !test_source_dir.exists() {
+        // Proyecto mixto: también tests Java, compilados después de los de Kotlin
+        let java_test_dir = config
+            .has_java_sources()
+            .then(|| project_dir.join(config.java_source_dir(true)))
+            .filter(|dir| dir.exists());
+
+        if !test_source_dir.exists() && java_test_dir.is_none() {
             println!(
                 "   {}",
                 "ℹ️  No se encontró directorio de tests (src/test/kotlin). Ignorando...".dimmed()
@@ -303,6 +407,11 @@ impl KotlinModule {
         std::fs::create_dir_all(&test_classes_dir)
             .context("No se pudo crear el directorio test-classes")?;
 
+        let test_resources = resources::copy_resources(config, project_dir, true, &test_classes_dir)?;
+        if test_resources > 0 {
+            println!("   {}", format!("📁 {} recursos de test copiados", test_resources).dimmed());
+        }
+
         let test_files: Vec<PathBuf> = WalkDir::new(&test_source_dir)
             .into_iter()
             .filter_map(|e| e.ok())
@@ -315,7 +424,7 @@ impl KotlinModule {
             .map(|e| e.path().to_path_buf())
             .collect();
 
-        if test_files.is_empty() {
+        if test_files.is_empty() && java_test_dir.is_none() {
             println!(
                 "   {}",
                 "⚠️  No se encontraron archivos .kt en el directorio de tests".yellow()
@@ -323,11 +432,6 @@ impl KotlinModule {
             return Ok(());
         }
 
-        println!(
-            "   {}",
-            format!("🧪 Compilando {} archivos de test Kotlin...", test_files.len()).cyan()
-        );
-
         let mut cp_parts = vec![classes_dir.to_string_lossy().to_string()];
         
         let deps_cp = build_kotlin_classpath(&deps_dir);
@@ -339,7 +443,7 @@ impl KotlinModule {
         let local_cp = config.get_local_classpath(project_dir);
         if !local_cp.is_empty() { cp_parts.push(local_cp); }
 
-        let junit_console_jar = Self::download_junit_standalone().await?;
+        let junit_console_jar = junit::console_launcher(config, project_dir).await?;
         cp_parts.push(junit_console_jar.to_string_lossy().to_string());
 
         let separator = if cfg!(target_os = "windows") { ";" } else { ":" };
@@ -349,47 +453,64 @@ impl KotlinModule {
             .map(|k| k.jvm_target.as_str())
             .unwrap_or("17");
 
-        let kotlinc_cmd = if cfg!(target_os = "windows") {
-            "kotlinc.bat"
-        } else {
-            "kotlinc"
-        };
-
-        let mut cmd = tokio::process::Command::new(if cfg!(target_os = "windows") { "cmd" } else { kotlinc_cmd });
-        
-        if cfg!(target_os = "windows") {
-            cmd.arg("/C").arg("kotlinc");
-        }
+        if !test_files.is_empty() {
+            println!(
+                "   {}",
+                format!("🧪 Compilando {} archivos de test Kotlin...", test_files.len()).cyan()
+            );
 
-        cmd.arg("-d")
-            .arg(&test_classes_dir)
-            .arg("-jvm-target")
-            .arg(jvm_target)
-            .arg("-include-runtime")
-            .arg("-cp")
-            .arg(&compile_classpath);
+            let mut cmd = kotlinc_command(config, project_dir)?;
+            cmd.arg("-d")
+                .arg(&test_classes_dir)
+                .arg("-jvm-target")
+                .arg(jvm_target)
+                .arg("-include-runtime")
+                .args(kotlinc_options(&config.compiler_options()))
+                .args(compiler_plugins::plugin_args(config, project_dir))
+                .arg("-cp")
+                .arg(&compile_classpath);
+
+            for file in &test_files {
+                cmd.arg(file);
+            }
+            if let Some(java_dir) = &java_test_dir {
+                cmd.arg(java_dir);
+            }
 
-        for file in &test_files {
-            cmd.arg(file);
+            let output = cmd
+                .output(project_dir)
+                .await
+                .map_err(|e| ForgeError::CommandNotFound {
+                    command: format!("kotlinc (test): {}", e),
+                })?;
+
+            if !output.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(ForgeError::TaskFailed {
+                    task_name: format!("kotlinc tests: {}", stderr),
+                    exit_code: output.code,
+                }
+                .into());
+            }
         }
 
-        let output = cmd
-            .current_dir(project_dir)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
-            .await
-            .map_err(|e| ForgeError::CommandNotFound {
-                command: format!("kotlinc (test): {}", e),
-            })?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(ForgeError::TaskFailed {
-                task_name: format!("kotlinc tests: {}", stderr),
-                exit_code: output.status.code().unwrap_or(-1),
+        if let Some(java_dir) = &java_test_dir {
+            // javac necesita kotlin-stdlib para las firmas de las clases de Kotlin
+            let mut javac_classpath = compile_classpath.clone();
+            if let Some(stdlib_path) = kotlin_stdlib(config) {
+                javac_classpath.push_str(separator);
+                javac_classpath.push_str(&stdlib_path);
             }
-            .into());
+            java::compile_mixed_sources(
+                config,
+                project_dir,
+                true,
+                std::slice::from_ref(java_dir),
+                &test_classes_dir,
+                &javac_classpath,
+                true,
+            )
+            .await?;
         }
 
         println!("   {}", "✅ Tests compilados. Ejecutando JUnit...".green());
@@ -402,7 +523,7 @@ impl KotlinModule {
         ];
         
         // Agregar Kotlin stdlib al classpath runtime
-        if let Some(stdlib_path) = find_kotlin_stdlib() {
+        if let Some(stdlib_path) = kotlin_stdlib(config) {
             exec_cp_parts.push(stdlib_path);
         }
 
@@ -417,15 +538,16 @@ impl KotlinModule {
 
         let exec_classpath = exec_cp_parts.join(separator);
 
+        let reports_dir = test_report::reports_dir(config, project_dir, "junit")?;
         let mut java_cmd = tokio::process::Command::new("java");
         java_cmd
+            .args(jvm_options(&config.compiler_options()))
             .arg("-jar")
             .arg(&junit_console_jar)
             .arg("--class-path")
             .arg(&exec_classpath)
             .arg("--scan-class-path")
-            .arg("--details=tree")
-            .arg("--disable-banner");
+            .args(junit::launcher_args(config, &reports_dir));
 
         let status = java_cmd
             .current_dir(project_dir)
@@ -437,6 +559,8 @@ impl KotlinModule {
                 command: format!("java (junit kotlin): {}", e),
             })?;
 
+        test_report::summarize(&reports_dir)?;
+
         if !status.success() {
             return Err(ForgeError::TaskFailed {
                 task_name: "kotlin test".to_string(),
@@ -448,60 +572,32 @@ impl KotlinModule {
         Ok(())
     }
 
-    /// Descarga la consola standalone de JUnit si no existe
-    async fn download_junit_standalone() -> ForgeResult<PathBuf> {
-        let tools_dir = dirs::home_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join(".forge")
-            .join("tools");
-
-        std::fs::create_dir_all(&tools_dir).map_err(|e| ForgeError::IoError {
-            path: tools_dir.clone(),
-            message: e.to_string(),
-        })?;
-
-        let jar_name = "junit-platform-console-standalone-6.0.3.jar";
-        let jar_path = tools_dir.join(jar_name);
-
-        if jar_path.exists() {
-            return Ok(jar_path);
-        }
-
-        println!(
-            "   {}",
-            "⬇️  Descargando JUnit Platform Console Standalone...".dimmed()
-        );
-
-        let url = "https://repo1.maven.org/maven2/org/junit/platform/junit-platform-console-standalone/1.12.0/junit-platform-console-standalone-1.12.0.jar";
-        
-        let client = reqwest::Client::new();
-        let response = client.get(url).send().await.map_err(|e: reqwest::Error| ForgeError::DownloadError {
-            url: url.to_string(),
-            message: e.to_string()
-        })?;
+}
 
-        if !response.status().is_success() {
-            return Err(ForgeError::DownloadError {
-                url: url.to_string(),
-                message: format!("HTTP {}", response.status()),
-            }.into());
+/// Opciones de kotlinc: `-Werror` y `compiler-args` (ej: `-Xjsr305=strict`). kotlinc
+/// siempre lee los fuentes como UTF-8 y no tiene equivalente a `-encoding`; `preview`
+/// solo afecta a la JVM al ejecutar (APIs preview del JDK).
+fn kotlinc_options(options: &CompilerOptions) -> Vec<String> {
+    if let Some(encoding) = &options.encoding {
+        if !encoding.eq_ignore_ascii_case("utf-8") && !encoding.eq_ignore_ascii_case("utf8") {
+            println!(
+                "   {}",
+                format!("⚠️  kotlinc solo admite fuentes UTF-8: se ignora encoding = \"{}\"", encoding).yellow()
+            );
         }
+    }
 
-        let bytes = response.bytes().await.map_err(|e: reqwest::Error| ForgeError::DownloadError {
-            url: url.to_string(),
-            message: e.to_string()
-        })?;
-
-        std::fs::write(&jar_path, &bytes).map_err(|e| ForgeError::IoError {
-            path: jar_path.clone(),
-            message: e.to_string(),
-        })?;
-
-        Ok(jar_path)
+    let mut args = Vec::new();
+    if options.warnings_as_errors {
+        args.push("-Werror".to_string());
     }
+    args.extend(options.compiler_args.iter().cloned());
+    args
 }
 
 /// Construye classpath con JARs de dependencias.
+/// Lee `classpath.txt` (rutas al almacén global escritas por el resolver) y
+/// agrega cualquier JAR suelto que haya en el directorio.
 fn build_kotlin_classpath(deps_dir: &Path) -> String {
     if !deps_dir.exists() {
         return String::new();
@@ -509,18 +605,85 @@ fn build_kotlin_classpath(deps_dir: &Path) -> String {
 
     let separator = if cfg!(target_os = "windows") { ";" } else { ":" };
 
-    WalkDir::new(deps_dir)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| {
-            e.path()
-                .extension()
-                .map(|ext| ext == "jar")
-                .unwrap_or(false)
+    let mut entries: Vec<String> = std::fs::read_to_string(deps_dir.join("classpath.txt"))
+        .map(|content| {
+            content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(String::from)
+                .collect()
         })
-        .map(|e| e.path().to_string_lossy().to_string())
-        .collect::<Vec<_>>()
-        .join(separator)
+        .unwrap_or_default();
+
+    entries.extend(
+        WalkDir::new(deps_dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| {
+                e.path()
+                    .extension()
+                    .map(|ext| ext == "jar")
+                    .unwrap_or(false)
+            })
+            .map(|e| e.path().to_string_lossy().to_string()),
+    );
+
+    entries.join(separator)
+}
+
+/// kotlinc del sistema o, con `[kotlin] embedded-compiler`, kotlin-compiler-embeddable
+/// (resuelto en .forge/kotlin-compiler/) ejecutado con `java`. Con `[kotlin] version`
+/// la stdlib llega por el classpath, así que kotlinc no agrega la de su instalación.
+/// En ambos casos el daemon de compilación carga el compilador en lugar de lanzarlo.
+fn kotlinc_command(config: &ForgeConfig, project_dir: &Path) -> ForgeResult<CompilerCommand> {
+    let kotlin_config = config.kotlin.as_ref();
+    let mut cmd = if kotlin_config.map(|k| k.embedded_compiler).unwrap_or(false) {
+        if config.kotlin_version().is_none() {
+            return Err(ForgeError::ConfigMissingField {
+                field: "kotlin.version".to_string(),
+            }
+            .into());
+        }
+        let compiler_dir = project_dir.join(".forge").join("kotlin-compiler");
+        let compiler_cp = build_kotlin_classpath(&compiler_dir);
+        if compiler_cp.is_empty() {
+            return Err(ForgeError::IoError {
+                path: compiler_dir,
+                message: "Compilador de Kotlin no resuelto. Ejecuta 'forge deps'.".to_string(),
+            }
+            .into());
+        }
+        CompilerCommand::kotlinc(&["java", "-cp", &compiler_cp, EMBEDDED_COMPILER_MAIN], Some(compiler_cp.clone()))
+    } else {
+        // En Windows kotlinc es un .bat, necesitamos ejecutar via cmd
+        let launcher: &[&str] = if cfg!(target_os = "windows") { &["cmd", "/C", "kotlinc"] } else { &["kotlinc"] };
+        // Fuera de su script, el compilador necesita saber dónde está la instalación
+        let kotlin_home = find_kotlin_home();
+        let compiler_jar = kotlin_home
+            .as_ref()
+            .map(|home| home.join("lib").join("kotlin-compiler.jar"))
+            .filter(|jar| jar.exists());
+        let mut c = CompilerCommand::kotlinc(launcher, compiler_jar.map(|jar| jar.to_string_lossy().to_string()));
+        if let Some(home) = kotlin_home {
+            c.arg("-kotlin-home").arg(home);
+        }
+        c
+    };
+
+    if config.kotlin_version().is_some() {
+        cmd.arg("-no-stdlib").arg("-no-reflect");
+    }
+    Ok(cmd)
+}
+
+/// kotlin-stdlib para el classpath: con `[kotlin] version` ya viene entre las
+/// dependencias resueltas; si no, la de la instalación local de kotlinc.
+fn kotlin_stdlib(config: &ForgeConfig) -> Option<String> {
+    if config.kotlin_version().is_some() {
+        return None;
+    }
+    find_kotlin_stdlib()
 }
 
 /// Busca kotlin-stdlib.jar en el sistema.
@@ -534,40 +697,48 @@ fn find_kotlin_stdlib() -> Option<String> {
         }
     }
 
-    // 2. Buscar donde está kotlinc instalado (via `where` en Windows, `which` en Unix)
-    let which_cmd = if cfg!(target_os = "windows") { "where" } else { "which" };
-    let which_arg = if cfg!(target_os = "windows") { "kotlinc.bat" } else { "kotlinc" };
+    // 2. Buscar en lib/ de la instalación de kotlinc (el nombre puede variar)
+    let lib_dir = find_kotlin_home()?.join("lib");
+    let mut stdlib_jars: Vec<String> = Vec::new();
+    if let Ok(entries) = std::fs::read_dir(&lib_dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with("kotlin-stdlib") && name.ends_with(".jar") {
+                stdlib_jars.push(entry.path().to_string_lossy().to_string());
+            }
+        }
+    }
 
-    if let Ok(output) = std::process::Command::new(which_cmd).arg(which_arg).output() {
-        if output.status.success() {
-            let kotlinc_path = String::from_utf8_lossy(&output.stdout);
-            let kotlinc_path = kotlinc_path.trim();
-            if let Some(bin_dir) = PathBuf::from(kotlinc_path).parent() {
-                // kotlinc está en .../kotlinc/bin/ → stdlib está en .../kotlinc/lib/
-                let kotlin_home = bin_dir.parent().unwrap_or(bin_dir);
-                let lib_dir = kotlin_home.join("lib");
-
-                // Buscar kotlin-stdlib.jar (puede variar el nombre)
-                if lib_dir.exists() {
-                    // Buscar todos los JARs de Kotlin en lib/
-                    let mut stdlib_jars: Vec<String> = Vec::new();
-                    if let Ok(entries) = std::fs::read_dir(&lib_dir) {
-                        for entry in entries.flatten() {
-                            let name = entry.file_name().to_string_lossy().to_string();
-                            if name.starts_with("kotlin-stdlib") && name.ends_with(".jar") {
-                                stdlib_jars.push(entry.path().to_string_lossy().to_string());
-                            }
-                        }
-                    }
+    if stdlib_jars.is_empty() {
+        return None;
+    }
+    let sep = if cfg!(target_os = "windows") { ";" } else { ":" };
+    Some(stdlib_jars.join(sep))
+}
 
-                    if !stdlib_jars.is_empty() {
-                        let sep = if cfg!(target_os = "windows") { ";" } else { ":" };
-                        return Some(stdlib_jars.join(sep));
-                    }
-                }
-            }
+/// Directorio de instalación de Kotlin: KOTLIN_HOME o el padre del bin/ donde
+/// está kotlinc (via `where` en Windows, `which` en Unix, siguiendo enlaces).
+fn find_kotlin_home() -> Option<PathBuf> {
+    if let Ok(kotlin_home) = std::env::var("KOTLIN_HOME") {
+        let kotlin_home = PathBuf::from(kotlin_home);
+        if kotlin_home.join("lib").exists() {
+            return Some(kotlin_home);
         }
     }
 
-    None
+    let which_cmd = if cfg!(target_os = "windows") { "where" } else { "which" };
+    let which_arg = if cfg!(target_os = "windows") { "kotlinc.bat" } else { "kotlinc" };
+
+    let output = std::process::Command::new(which_cmd).arg(which_arg).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let kotlinc_path = String::from_utf8_lossy(&output.stdout);
+    let kotlinc_path = PathBuf::from(kotlinc_path.lines().next()?.trim());
+    let kotlinc_path = std::fs::canonicalize(&kotlinc_path).unwrap_or(kotlinc_path);
+
+    // kotlinc está en .../kotlinc/bin/ → stdlib y compilador están en .../kotlinc/lib/
+    let bin_dir = kotlinc_path.parent()?;
+    let kotlin_home = bin_dir.parent().unwrap_or(bin_dir);
+    kotlin_home.join("lib").exists().then(|| kotlin_home.to_path_buf())
 }
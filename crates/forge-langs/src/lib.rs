@@ -2,9 +2,21 @@
 // 🔥 FORGE — Módulos de Lenguaje: Punto de Entrada
 // =============================================================================
 
+pub mod classfile;
+pub mod compiler_plugins;
+pub mod daemon;
+pub mod dist;
+pub mod image;
+pub mod incremental;
+pub mod jar;
 pub mod java;
+pub mod jpms;
+pub mod junit;
 pub mod kotlin;
 pub mod python;
+pub mod resources;
+pub mod shade;
+pub mod test_report;
 
 /// Extensiones de archivo por lenguaje (para caché incremental).
 pub fn extensions_for_lang(lang: &str) -> &[&str] {
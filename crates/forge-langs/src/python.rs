@@ -11,6 +11,10 @@ use colored::Colorize;
 
 use cyrce_forge_core::config::ForgeConfig;
 use cyrce_forge_core::error::{ForgeError, ForgeResult};
+use cyrce_forge_core::settings::offline_mode;
+use cyrce_forge_deps::lockfile::Lockfile;
+
+use crate::test_report;
 
 /// Módulo de gestión Python.
 pub struct PythonModule;
@@ -71,21 +75,30 @@ impl PythonModule {
             .cyan()
         );
 
-        // Construir lista de dependencias con versiones
+        // Construir lista de dependencias con versiones ("*" usa la versión de forge.lock)
+        let lock = Lockfile::load(project_dir)?;
         let deps: Vec<String> = config
             .dependencies
             .iter()
             .map(|(name, version)| {
-                if version == "*" || version.is_empty() {
-                    name.clone()
-                } else {
-                    format!("{}=={}", name, version)
+                let pinned = lock.as_ref().and_then(|l| l.pypi_pin(name, &[]));
+                match pinned {
+                    Some(pinned) if version == "*" || version.is_empty() => {
+                        format!("{}=={}", name, pinned)
+                    }
+                    _ if version == "*" || version.is_empty() => name.clone(),
+                    _ => format!("{}=={}", name, version),
                 }
             })
             .collect();
 
         let mut cmd = tokio::process::Command::new(&pip);
-        cmd.arg("install").args(&deps);
+        cmd.arg("install");
+        if offline_mode() {
+            // Solo vale lo que ya está instalado en el venv
+            cmd.arg("--no-index");
+        }
+        cmd.args(&deps);
         cmd.current_dir(project_dir)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped());
@@ -234,18 +247,34 @@ impl PythonModule {
 
         println!("   {}", "🧪 Ejecutando tests Python...".cyan());
 
+        let reports_dir = test_report::reports_dir(config, project_dir, "pytest")?;
         let mut cmd = tokio::process::Command::new(&python);
         cmd.args(["-m", "pytest", "tests/"]) // Opcional pero recomendada
+            .arg(format!("--junitxml={}", reports_dir.join("TEST-pytest.xml").display()))
             .current_dir(project_dir)
             .stdout(Stdio::inherit())
             .stderr(Stdio::inherit());
 
         let status = cmd.status().await;
 
+        // Con reporte, pytest sí se ejecutó y recolectó tests (código 5 = ninguno):
+        // su resultado es el definitivo
+        let report = match &status {
+            Ok(s) if s.code() != Some(5) => test_report::summarize(&reports_dir)?,
+            _ => None,
+        };
+
         match status {
             Ok(s) if s.success() => {
                 println!("   {}", "✅ Todos los tests pasaron exitosamente!".green());
             }
+            Ok(s) if report.is_some() => {
+                return Err(ForgeError::TaskFailed {
+                    task_name: "python test".to_string(),
+                    exit_code: s.code().unwrap_or(-1),
+                }
+                .into());
+            }
             Ok(s) => {
                 // Intentar con unittest si pytest no está instalado
                 println!(
@@ -0,0 +1,194 @@
+// =============================================================================
+// 🔥 FORGE — Módulos de Lenguaje: Recursos JVM
+// =============================================================================
+// Copia `src/main/resources` a las clases compiladas (y de ahí al JAR) y
+// `src/test/resources` a test-classes. Con `filter-resources = true` los
+// recursos de texto reemplazan `${project.version}` y compañía.
+// Los recursos borrados del código fuente también se borran de la salida.
+// =============================================================================
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use walkdir::WalkDir;
+
+use cyrce_forge_core::config::ForgeConfig;
+use cyrce_forge_core::error::{ForgeError, ForgeResult};
+
+/// Valores disponibles al filtrar recursos, como `(nombre, valor)`.
+pub fn filter_properties(config: &ForgeConfig) -> Vec<(&'static str, String)> {
+    vec![
+        ("project.name", config.project.name.clone()),
+        ("project.version", config.project.version.clone()),
+        ("project.description", config.project.description.clone()),
+    ]
+}
+
+/// Huella de los valores de filtrado: si cambia (ej: nueva versión en forge.toml),
+/// los recursos filtrados deben volver a copiarse aunque no hayan cambiado.
+pub fn filter_fingerprint(config: &ForgeConfig) -> String {
+    filter_properties(config)
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Reemplaza `${nombre}` por su valor. Los nombres desconocidos quedan como están.
+pub fn filter_text(text: &str, properties: &[(&str, String)]) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find('}') {
+            Some(end) => {
+                let name = &after[..end];
+                match properties.iter().find(|(n, _)| *n == name) {
+                    Some((_, value)) => out.push_str(value),
+                    None => out.push_str(&rest[start..start + 2 + end + 1]),
+                }
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Copia los recursos del proyecto (`test`: los de tests) a `dest`. Devuelve cuántos copió.
+pub fn copy_resources(config: &ForgeConfig, project_dir: &Path, test: bool, dest: &Path) -> ForgeResult<usize> {
+    let Some(resources) = config.resources_dir(test) else {
+        return Ok(0);
+    };
+    let resources_dir = project_dir.join(resources);
+
+    // Lista de lo copiado la vez anterior, para borrar de la salida lo que ya no existe
+    let listing = project_dir
+        .join(".forge")
+        .join(if test { "test-resources.txt" } else { "resources.txt" });
+    let previous: BTreeSet<String> = std::fs::read_to_string(&listing)
+        .map(|content| content.lines().map(String::from).collect())
+        .unwrap_or_default();
+
+    let mut copied = BTreeSet::new();
+    if resources_dir.is_dir() {
+        let properties = config.filter_resources().then(|| filter_properties(config));
+
+        for entry in WalkDir::new(&resources_dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let relative = entry
+                .path()
+                .strip_prefix(&resources_dir)
+                .unwrap_or(entry.path())
+                .to_string_lossy()
+                .replace('\\', "/");
+            let target = dest.join(&relative);
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| io_error(parent, e))?;
+            }
+
+            let bytes = std::fs::read(entry.path()).map_err(|e| io_error(entry.path(), e))?;
+            // Solo se filtran los recursos de texto: los binarios se copian intactos
+            let bytes = match (&properties, std::str::from_utf8(&bytes)) {
+                (Some(properties), Ok(text)) if text.contains("${") => {
+                    filter_text(text, properties).into_bytes()
+                }
+                _ => bytes,
+            };
+            std::fs::write(&target, bytes).map_err(|e| io_error(&target, e))?;
+            copied.insert(relative);
+        }
+    }
+
+    for stale in previous.difference(&copied) {
+        let _ = std::fs::remove_file(dest.join(stale));
+    }
+
+    if !copied.is_empty() || !previous.is_empty() {
+        if let Some(parent) = listing.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| io_error(parent, e))?;
+        }
+        let content: Vec<&str> = copied.iter().map(String::as_str).collect();
+        std::fs::write(&listing, content.join("\n")).map_err(|e| io_error(&listing, e))?;
+    }
+
+    Ok(copied.len())
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> ForgeError {
+    ForgeError::IoError {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn java_config(project: &Path, filter: bool) -> ForgeConfig {
+        std::fs::create_dir_all(project).unwrap();
+        std::fs::write(
+            project.join("forge.toml"),
+            format!(
+                "[project]\nname = \"app\"\nversion = \"2.1.0\"\nlang = \"java\"\n\n[java]\nfilter-resources = {}\n",
+                filter
+            ),
+        )
+        .unwrap();
+        ForgeConfig::load(project).unwrap()
+    }
+
+    #[test]
+    fn test_filter_text() {
+        let project = std::env::temp_dir().join("forge_test_filter_text");
+        let properties = filter_properties(&java_config(&project, true));
+        let _ = std::fs::remove_dir_all(&project);
+        assert_eq!(
+            filter_text("app=${project.name} v${project.version}", &properties),
+            "app=app v2.1.0"
+        );
+        // Desconocidos y llaves sin cerrar se conservan
+        assert_eq!(filter_text("${user.home} ${project.version", &properties), "${user.home} ${project.version");
+    }
+
+    #[test]
+    fn test_copy_resources_filters_and_removes_stale() {
+        let project = std::env::temp_dir().join("forge_test_resources");
+        let _ = std::fs::remove_dir_all(&project);
+        let resources = project.join("src/main/resources");
+        std::fs::create_dir_all(resources.join("META-INF/services")).unwrap();
+        std::fs::write(resources.join("app.properties"), "version=${project.version}\n").unwrap();
+        std::fs::write(resources.join("META-INF/services/app.Spi"), "app.Impl\n").unwrap();
+        std::fs::write(resources.join("logo.bin"), [0xFFu8, b'$', b'{', 0x00]).unwrap();
+
+        let dest = project.join("build/classes");
+        let config = java_config(&project, true);
+        assert_eq!(copy_resources(&config, &project, false, &dest).unwrap(), 3);
+        assert_eq!(std::fs::read_to_string(dest.join("app.properties")).unwrap(), "version=2.1.0\n");
+        assert!(dest.join("META-INF/services/app.Spi").exists());
+        assert_eq!(std::fs::read(dest.join("logo.bin")).unwrap(), [0xFFu8, b'$', b'{', 0x00]);
+
+        // Un recurso borrado desaparece de la salida en la siguiente copia
+        std::fs::remove_file(resources.join("logo.bin")).unwrap();
+        assert_eq!(copy_resources(&config, &project, false, &dest).unwrap(), 2);
+        assert!(!dest.join("logo.bin").exists());
+
+        // Sin filtrado, el texto se copia tal cual
+        copy_resources(&java_config(&project, false), &project, false, &dest).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dest.join("app.properties")).unwrap(),
+            "version=${project.version}\n"
+        );
+
+        let _ = std::fs::remove_dir_all(&project);
+    }
+}
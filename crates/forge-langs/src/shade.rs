@@ -0,0 +1,224 @@
+// =============================================================================
+// 🔥 FORGE — Módulos de Lenguaje: Shading (reubicación de paquetes)
+// =============================================================================
+// Reubica paquetes de dependencias dentro de un fat-jar para evitar choques
+// con otras versiones en el classpath: `com.google.gson` → `app.shaded.gson`.
+// Se reescriben las rutas de las entradas, las constantes UTF-8 del constant
+// pool de cada .class (nombres, descriptores, firmas y strings usados por
+// reflexión) y los nombres listados en META-INF/services.
+// =============================================================================
+
+use cyrce_forge_core::config::RelocationConfig;
+
+/// Regla de reubicación lista para aplicar.
+#[derive(Debug, Clone)]
+pub struct Relocation {
+    /// `com/google/gson/`
+    from_path: String,
+    /// `app/shaded/gson/`
+    to_path: String,
+    /// `com.google.gson.`
+    from_name: String,
+    /// `app.shaded.gson.`
+    to_name: String,
+}
+
+impl Relocation {
+    pub fn new(pattern: &str, shaded_pattern: &str) -> Self {
+        let pattern = pattern.trim_end_matches('.');
+        let shaded = shaded_pattern.trim_end_matches('.');
+        Self {
+            from_path: format!("{}/", pattern.replace('.', "/")),
+            to_path: format!("{}/", shaded.replace('.', "/")),
+            from_name: format!("{}.", pattern),
+            to_name: format!("{}.", shaded),
+        }
+    }
+
+    pub fn from_config(config: &[RelocationConfig]) -> Vec<Self> {
+        config
+            .iter()
+            .map(|r| Self::new(&r.pattern, &r.shaded_pattern))
+            .collect()
+    }
+}
+
+/// Ruta de una entrada del JAR tras aplicar las reubicaciones (`com/google/gson/Gson.class`).
+/// Las clases de JARs multi-release (`META-INF/versions/<N>/...`) conservan su prefijo.
+pub fn relocate_path(path: &str, relocations: &[Relocation]) -> String {
+    let (prefix, rest) = match path.strip_prefix("META-INF/versions/").and_then(|v| v.split_once('/')) {
+        Some((_, rest)) => (&path[..path.len() - rest.len()], rest),
+        None => ("", path),
+    };
+    relocations
+        .iter()
+        .find(|r| rest.starts_with(&r.from_path))
+        .map(|r| format!("{}{}{}", prefix, r.to_path, &rest[r.from_path.len()..]))
+        .unwrap_or_else(|| path.to_string())
+}
+
+/// Nombre de clase con puntos tras las reubicaciones (para META-INF/services).
+pub fn relocate_name(name: &str, relocations: &[Relocation]) -> String {
+    relocations
+        .iter()
+        .find(|r| name.starts_with(&r.from_name))
+        .map(|r| format!("{}{}", r.to_name, &name[r.from_name.len()..]))
+        .unwrap_or_else(|| name.to_string())
+}
+
+/// Reescribe las constantes UTF-8 de un .class. Devuelve `None` si el archivo no es un
+/// class file válido (se copia tal cual) o si ninguna constante cambió.
+pub fn relocate_class(bytes: &[u8], relocations: &[Relocation]) -> Option<Vec<u8>> {
+    if relocations.is_empty() || bytes.len() < 10 || bytes[..4] != [0xCA, 0xFE, 0xBA, 0xBE] {
+        return None;
+    }
+
+    let count = u16::from_be_bytes([bytes[8], bytes[9]]) as usize;
+    let mut out = Vec::with_capacity(bytes.len() + 64);
+    out.extend_from_slice(&bytes[..10]);
+
+    let mut pos = 10;
+    let mut changed = false;
+    let mut index = 1;
+    while index < count {
+        let tag = *bytes.get(pos)?;
+        let size = match tag {
+            // CONSTANT_Utf8: u2 longitud + bytes
+            1 => {
+                let len = u16::from_be_bytes([*bytes.get(pos + 1)?, *bytes.get(pos + 2)?]) as usize;
+                let value = bytes.get(pos + 3..pos + 3 + len)?;
+                let relocated = relocate_utf8(value, relocations);
+                if relocated != value {
+                    let new_len = u16::try_from(relocated.len()).ok()?;
+                    out.push(1);
+                    out.extend_from_slice(&new_len.to_be_bytes());
+                    out.extend_from_slice(&relocated);
+                    changed = true;
+                    pos += 3 + len;
+                    index += 1;
+                    continue;
+                }
+                3 + len
+            }
+            // Integer, Float, Fieldref, Methodref, InterfaceMethodref, NameAndType, Dynamic, InvokeDynamic
+            3 | 4 | 9 | 10 | 11 | 12 | 17 | 18 => 5,
+            // Long y Double ocupan dos posiciones del constant pool
+            5 | 6 => {
+                index += 1;
+                9
+            }
+            // Class, String, MethodType, Module, Package
+            7 | 8 | 16 | 19 | 20 => 3,
+            // MethodHandle
+            15 => 4,
+            _ => return None,
+        };
+        out.extend_from_slice(bytes.get(pos..pos + size)?);
+        pos += size;
+        index += 1;
+    }
+
+    if !changed {
+        return None;
+    }
+    out.extend_from_slice(&bytes[pos..]);
+    Some(out)
+}
+
+/// Aplica las reubicaciones a una constante: formato interno (`com/google/gson/Gson`,
+/// `Lcom/google/gson/Gson;`) y con puntos (`"com.google.gson.Gson"` en Class.forName).
+fn relocate_utf8(value: &[u8], relocations: &[Relocation]) -> Vec<u8> {
+    let mut result = value.to_vec();
+    for r in relocations {
+        result = replace_bytes(&result, r.from_path.as_bytes(), r.to_path.as_bytes());
+        result = replace_bytes(&result, r.from_name.as_bytes(), r.to_name.as_bytes());
+    }
+    result
+}
+
+/// Reemplaza las apariciones de `from` que empiezan un identificador (no `xcom/google/...`).
+/// La `L` de un descriptor (`Lcom/google/gson/Gson;`) cuenta como inicio.
+fn replace_bytes(haystack: &[u8], from: &[u8], to: &[u8]) -> Vec<u8> {
+    let starts_identifier = |i: usize| {
+        i == 0
+            || !is_identifier_byte(haystack[i - 1])
+            || (haystack[i - 1] == b'L' && (i == 1 || !is_identifier_byte(haystack[i - 2])))
+    };
+
+    let mut out = Vec::with_capacity(haystack.len());
+    let mut i = 0;
+    while i < haystack.len() {
+        if starts_identifier(i) && haystack[i..].starts_with(from) {
+            out.extend_from_slice(to);
+            i += from.len();
+        } else {
+            out.push(haystack[i]);
+            i += 1;
+        }
+    }
+    out
+}
+
+fn is_identifier_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b == b'$' || b == b'.' || b == b'/'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Class file mínimo: constant pool con un Utf8, un Class, un Long y un String.
+    fn class_with(name: &str, text: &str) -> Vec<u8> {
+        let mut bytes = vec![0xCA, 0xFE, 0xBA, 0xBE, 0, 0, 0, 61];
+        bytes.extend_from_slice(&7u16.to_be_bytes()); // 6 constantes (Long ocupa 2)
+        bytes.push(1);
+        bytes.extend_from_slice(&(name.len() as u16).to_be_bytes());
+        bytes.extend_from_slice(name.as_bytes());
+        bytes.extend_from_slice(&[7, 0, 1]);
+        bytes.extend_from_slice(&[5, 0, 0, 0, 0, 0, 0, 0, 42]);
+        bytes.push(1);
+        bytes.extend_from_slice(&(text.len() as u16).to_be_bytes());
+        bytes.extend_from_slice(text.as_bytes());
+        bytes.extend_from_slice(&[8, 0, 5]);
+        bytes.extend_from_slice(&[0, 0x21, 0, 2]); // resto del archivo
+        bytes
+    }
+
+    #[test]
+    fn test_relocate_paths_and_names() {
+        let relocations = vec![Relocation::new("com.google.gson", "app.shaded.gson")];
+        assert_eq!(
+            relocate_path("com/google/gson/Gson.class", &relocations),
+            "app/shaded/gson/Gson.class"
+        );
+        assert_eq!(relocate_path("com/google/gsonx/A.class", &relocations), "com/google/gsonx/A.class");
+        assert_eq!(
+            relocate_path("META-INF/versions/11/com/google/gson/Gson.class", &relocations),
+            "META-INF/versions/11/app/shaded/gson/Gson.class"
+        );
+        assert_eq!(
+            relocate_name("com.google.gson.internal.Excluder", &relocations),
+            "app.shaded.gson.internal.Excluder"
+        );
+    }
+
+    #[test]
+    fn test_relocate_class_constant_pool() {
+        let relocations = vec![Relocation::new("com.google.gson", "app.shaded.gson")];
+        let original = class_with("com/google/gson/Gson", "Lcom/google/gson/Gson;com.google.gson.X");
+        let relocated = relocate_class(&original, &relocations).unwrap();
+
+        let expected = class_with("app/shaded/gson/Gson", "Lapp/shaded/gson/Gson;app.shaded.gson.X");
+        assert_eq!(relocated, expected);
+
+        // Sin coincidencias o sin formato de class file, no se toca
+        assert!(relocate_class(&class_with("org/example/A", "hola"), &relocations).is_none());
+        assert!(relocate_class(b"not a class", &relocations).is_none());
+    }
+
+    #[test]
+    fn test_replace_respects_identifier_boundaries() {
+        let out = replace_bytes(b"(Lcom/a/B;[Lcom/a/C;)XLcom/a/D", b"com/a/", b"z/");
+        assert_eq!(out, b"(Lz/B;[Lz/C;)XLcom/a/D");
+    }
+}
@@ -0,0 +1,359 @@
+// =============================================================================
+// 🔥 FORGE — Módulos de Lenguaje: Resultados de tests
+// =============================================================================
+// JUnit Platform (`--reports-dir`) y pytest (`--junitxml`) escriben el mismo
+// formato XML de JUnit. Aquí se lee en un modelo común (suites, casos,
+// duraciones, fallos y omitidos), se imprime un resumen unificado y cada caso
+// se publica como evento `TestFinished` en el bus de telemetría.
+// =============================================================================
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use colored::Colorize;
+use quick_xml::events::{BytesStart, Event};
+
+use cyrce_forge_core::config::ForgeConfig;
+use cyrce_forge_core::error::{ForgeError, ForgeResult};
+use cyrce_forge_core::telemetry::{global_event_bus, ForgeEvent};
+
+/// Resultado de todos los reportes XML de una ejecución de tests.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TestReport {
+    pub suites: Vec<TestSuite>,
+}
+
+/// Un `<testsuite>` del reporte.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TestSuite {
+    pub name: String,
+    pub cases: Vec<TestCase>,
+}
+
+/// Un `<testcase>` del reporte.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestCase {
+    pub class_name: String,
+    pub name: String,
+    pub duration: Duration,
+    pub outcome: TestOutcome,
+}
+
+/// Resultado de un caso: `<failure>` es una aserción fallida, `<error>` una
+/// excepción inesperada.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TestOutcome {
+    Passed,
+    Failed { message: String },
+    Errored { message: String },
+    Skipped { message: Option<String> },
+}
+
+impl TestOutcome {
+    /// Nombre del resultado en los eventos de telemetría.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Passed => "passed",
+            Self::Failed { .. } => "failed",
+            Self::Errored { .. } => "error",
+            Self::Skipped { .. } => "skipped",
+        }
+    }
+
+    fn is_failure(&self) -> bool {
+        matches!(self, Self::Failed { .. } | Self::Errored { .. })
+    }
+}
+
+impl TestReport {
+    /// Lee un reporte XML de JUnit (`<testsuites>` o un único `<testsuite>`).
+    /// Las suites anidadas se aplanan.
+    pub fn parse(xml: &str) -> Result<Self, quick_xml::Error> {
+        let mut reader = quick_xml::Reader::from_str(xml);
+        reader.config_mut().trim_text(true);
+
+        let mut report = TestReport::default();
+        let mut suites: Vec<TestSuite> = Vec::new();
+        let mut case: Option<TestCase> = None;
+        // Texto del `<failure>`/`<error>` en curso, si no traía atributo message
+        let mut pending_message = false;
+
+        loop {
+            match reader.read_event()? {
+                Event::Start(ref e) => match e.name().as_ref() {
+                    b"testsuite" => suites.push(TestSuite {
+                        name: attribute(e, "name").unwrap_or_default(),
+                        cases: Vec::new(),
+                    }),
+                    b"testcase" => case = Some(test_case(e)),
+                    b"failure" | b"error" | b"skipped" => {
+                        if let Some(case) = case.as_mut() {
+                            case.outcome = outcome(e);
+                            pending_message = attribute(e, "message").is_none();
+                        }
+                    }
+                    _ => {}
+                },
+                Event::Empty(ref e) => match e.name().as_ref() {
+                    b"testcase" => {
+                        if let Some(suite) = suites.last_mut() {
+                            suite.cases.push(test_case(e));
+                        }
+                    }
+                    b"failure" | b"error" | b"skipped" => {
+                        if let Some(case) = case.as_mut() {
+                            case.outcome = outcome(e);
+                        }
+                    }
+                    _ => {}
+                },
+                Event::Text(ref e) if pending_message => {
+                    let text = e.unescape().unwrap_or_default();
+                    let first_line = text.lines().next().unwrap_or_default().trim().to_string();
+                    if let Some(case) = case.as_mut() {
+                        match &mut case.outcome {
+                            TestOutcome::Failed { message } | TestOutcome::Errored { message } => {
+                                *message = first_line;
+                            }
+                            TestOutcome::Skipped { message } => *message = Some(first_line),
+                            TestOutcome::Passed => {}
+                        }
+                    }
+                    pending_message = false;
+                }
+                Event::End(ref e) => match e.name().as_ref() {
+                    b"testcase" => {
+                        if let (Some(case), Some(suite)) = (case.take(), suites.last_mut()) {
+                            suite.cases.push(case);
+                        }
+                    }
+                    b"failure" | b"error" | b"skipped" => pending_message = false,
+                    b"testsuite" => report.suites.extend(suites.pop()),
+                    _ => {}
+                },
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        report.suites.retain(|suite| !suite.cases.is_empty());
+        Ok(report)
+    }
+
+    /// Lee todos los reportes .xml de un directorio. `None` si no hay ninguno
+    /// (la herramienta no llegó a ejecutar los tests).
+    pub fn load_dir(dir: &Path) -> ForgeResult<Option<Self>> {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Ok(None);
+        };
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "xml"))
+            .collect();
+        if files.is_empty() {
+            return Ok(None);
+        }
+        files.sort();
+
+        let mut report = TestReport::default();
+        for file in files {
+            let xml = std::fs::read_to_string(&file).map_err(|e| ForgeError::IoError {
+                path: file.clone(),
+                message: e.to_string(),
+            })?;
+            let parsed = Self::parse(&xml).map_err(|e| ForgeError::IoError {
+                path: file.clone(),
+                message: format!("Reporte de tests inválido: {}", e),
+            })?;
+            report.suites.extend(parsed.suites);
+        }
+        Ok(Some(report))
+    }
+
+    pub fn cases(&self) -> impl Iterator<Item = (&TestSuite, &TestCase)> {
+        self.suites
+            .iter()
+            .flat_map(|suite| suite.cases.iter().map(move |case| (suite, case)))
+    }
+
+    pub fn total(&self) -> usize {
+        self.cases().count()
+    }
+
+    pub fn passed(&self) -> usize {
+        self.cases().filter(|(_, c)| c.outcome == TestOutcome::Passed).count()
+    }
+
+    /// Fallos y errores.
+    pub fn failed(&self) -> usize {
+        self.cases().filter(|(_, c)| c.outcome.is_failure()).count()
+    }
+
+    pub fn skipped(&self) -> usize {
+        self.cases()
+            .filter(|(_, c)| matches!(c.outcome, TestOutcome::Skipped { .. }))
+            .count()
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.cases().map(|(_, c)| c.duration).sum()
+    }
+
+    /// Resumen unificado: totales y el detalle de cada fallo.
+    pub fn print_summary(&self) {
+        println!();
+        println!(
+            "   {} {} tests — {} pasaron, {} fallaron, {} omitidos ({:.2}s)",
+            "📊 Resultados:".bold(),
+            self.total(),
+            self.passed().to_string().green(),
+            if self.failed() > 0 {
+                self.failed().to_string().red()
+            } else {
+                self.failed().to_string().normal()
+            },
+            self.skipped().to_string().yellow(),
+            self.duration().as_secs_f64()
+        );
+
+        for (_, case) in self.cases().filter(|(_, c)| c.outcome.is_failure()) {
+            let message = match &case.outcome {
+                TestOutcome::Failed { message } | TestOutcome::Errored { message } => message.as_str(),
+                _ => "",
+            };
+            println!(
+                "      {} {} › {}{}",
+                "❌".red(),
+                case.class_name,
+                case.name.bold(),
+                if message.is_empty() {
+                    String::new()
+                } else {
+                    format!(": {}", message).dimmed().to_string()
+                }
+            );
+        }
+    }
+
+    /// Publica un evento `TestFinished` por caso.
+    pub fn publish(&self) {
+        for (suite, case) in self.cases() {
+            let message = match &case.outcome {
+                TestOutcome::Passed => None,
+                TestOutcome::Failed { message } | TestOutcome::Errored { message } => Some(message.clone()),
+                TestOutcome::Skipped { message } => message.clone(),
+            };
+            global_event_bus().send(ForgeEvent::TestFinished {
+                suite: suite.name.clone(),
+                class_name: case.class_name.clone(),
+                name: case.name.clone(),
+                status: case.outcome.as_str().to_string(),
+                time_ms: case.duration.as_millis() as u64,
+                message,
+            });
+        }
+    }
+}
+
+/// Directorio vacío para los reportes de una herramienta (build/test-results/<tool>).
+pub(crate) fn reports_dir(config: &ForgeConfig, project_dir: &Path, tool: &str) -> ForgeResult<PathBuf> {
+    let dir = project_dir
+        .join(&config.project.output_dir)
+        .join("test-results")
+        .join(tool);
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).map_err(|e| ForgeError::IoError {
+        path: dir.clone(),
+        message: e.to_string(),
+    })?;
+    Ok(dir)
+}
+
+/// Lee los reportes de `dir`, imprime el resumen y publica los eventos.
+pub(crate) fn summarize(dir: &Path) -> ForgeResult<Option<TestReport>> {
+    let report = TestReport::load_dir(dir)?;
+    if let Some(report) = &report {
+        report.print_summary();
+        report.publish();
+    }
+    Ok(report)
+}
+
+fn test_case(e: &BytesStart) -> TestCase {
+    TestCase {
+        class_name: attribute(e, "classname").unwrap_or_default(),
+        name: attribute(e, "name").unwrap_or_default(),
+        duration: attribute(e, "time")
+            .and_then(|time| time.replace(',', "").parse::<f64>().ok())
+            .filter(|secs| secs.is_finite() && *secs >= 0.0)
+            .map(Duration::from_secs_f64)
+            .unwrap_or_default(),
+        outcome: TestOutcome::Passed,
+    }
+}
+
+fn outcome(e: &BytesStart) -> TestOutcome {
+    let message = attribute(e, "message");
+    match e.name().as_ref() {
+        b"skipped" => TestOutcome::Skipped { message },
+        b"error" => TestOutcome::Errored {
+            message: message.unwrap_or_default(),
+        },
+        _ => TestOutcome::Failed {
+            message: message.unwrap_or_default(),
+        },
+    }
+}
+
+fn attribute(e: &BytesStart, name: &str) -> Option<String> {
+    e.try_get_attribute(name)
+        .ok()
+        .flatten()
+        .and_then(|attr| attr.unescape_value().ok())
+        .map(|value| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_junit_platform_report() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuite name="JUnit Jupiter" tests="3" skipped="1" failures="1" errors="0" time="0.05">
+  <properties><property name="java.version" value="17"/></properties>
+  <testcase name="suma()" classname="app.MainTest" time="0.012"/>
+  <testcase name="resta()" classname="app.MainTest" time="0.004">
+    <failure message="expected: &lt;4&gt; but was: &lt;5&gt;" type="org.opentest4j.AssertionFailedError">stack</failure>
+    <system-out><![CDATA[salida]]></system-out>
+  </testcase>
+  <testcase name="lento()" classname="app.MainTest" time="0">
+    <skipped/>
+  </testcase>
+</testsuite>"#;
+
+        let report = TestReport::parse(xml).unwrap();
+        assert_eq!(report.suites.len(), 1);
+        assert_eq!((report.total(), report.passed(), report.failed(), report.skipped()), (3, 1, 1, 1));
+        assert_eq!(report.duration(), Duration::from_millis(16));
+        assert_eq!(
+            report.suites[0].cases[1].outcome,
+            TestOutcome::Failed {
+                message: "expected: <4> but was: <5>".to_string()
+            }
+        );
+        assert_eq!(report.suites[0].cases[2].outcome, TestOutcome::Skipped { message: None });
+    }
+
+    #[test]
+    fn test_parse_pytest_report() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?><testsuites><testsuite name="pytest" errors="1" failures="0" skipped="1" tests="3" time="0.031"><testcase classname="tests.test_app" name="test_ok" time="0.001" /><testcase classname="tests.test_app" name="test_db" time="0.002"><error message="failed on setup with &quot;ConnectionError&quot;">traceback</error></testcase><testcase classname="tests.test_app" name="test_slow" time="0.000"><skipped type="pytest.skip" message="lento">tests/test_app.py:9: lento</skipped></testcase></testsuite></testsuites>"#;
+
+        let report = TestReport::parse(xml).unwrap();
+        assert_eq!((report.total(), report.passed(), report.failed(), report.skipped()), (3, 1, 1, 1));
+        let outcomes: Vec<&str> = report.cases().map(|(_, c)| c.outcome.as_str()).collect();
+        assert_eq!(outcomes, ["passed", "error", "skipped"]);
+        assert_eq!(report.suites[0].name, "pytest");
+    }
+}
@@ -106,6 +106,26 @@ export function TelemetryProvider({ children }: { children: ReactNode }) {
                             }
                         };
                     }
+                    else if (data.type === 'DependencyResolved') {
+                        logCounter++;
+                        newState.logs = [...prev.logs, {
+                            id: logCounter,
+                            level: 'INFO',
+                            text: `📦 ${data.name}:${data.version} ${data.cached ? '(caché)' : `(${data.time_ms}ms)`}`,
+                            timestamp: new Date().toISOString()
+                        }];
+                    }
+                    else if (data.type === 'TestFinished') {
+                        const icons: Record<string, string> = { passed: '✅', failed: '❌', error: '💥', skipped: '⏭️' };
+                        const levels: Record<string, string> = { passed: 'SUCCESS', failed: 'ERROR', error: 'ERROR', skipped: 'WARN' };
+                        logCounter++;
+                        newState.logs = [...prev.logs, {
+                            id: logCounter,
+                            level: levels[data.status] ?? 'INFO',
+                            text: `${icons[data.status] ?? '🧪'} ${data.class_name} › ${data.name} (${data.time_ms}ms)${data.message ? `: ${data.message}` : ''}`,
+                            timestamp: new Date().toISOString()
+                        }];
+                    }
                     else if (data.type === 'LogMessage') {
                         logCounter++;
                         newState.logs = [...prev.logs, {